@@ -11,7 +11,7 @@ use humantime::Duration;
 use lib_event::unbound::Rx;
 use lib_rules::EngineEvent;
 use tokio_util::sync::CancellationToken;
-use tracing::{debug, info};
+use tracing::{debug, info, warn};
 
 pub async fn _run_agent_sink(mut rx: Rx<AppEvent>, shutdown: CancellationToken) -> Result<()> {
 	info!("Agent sink started, waiting for events...");
@@ -30,6 +30,10 @@ pub async fn _run_agent_sink(mut rx: Rx<AppEvent>, shutdown: CancellationToken)
 				match event {
 					AppEvent::Engine(e) => print_alert(&e),
 					AppEvent::Cerberus(e) => debug!("{}", string_from_event(&e)),
+					AppEvent::EventLoss { lost, .. } => {
+						let lost = lost.iter().map(|(name, n)| format!("{name} {n}")).collect::<Vec<_>>().join(", ");
+						warn!("[ringbuf]: ring buffer full, events lost: {lost}");
+					}
 					_ => {}
 				}
 			}
@@ -1,11 +1,14 @@
 use std::path::PathBuf;
 
-use clap::{Parser, ValueEnum};
+use clap::{Parser, Subcommand, ValueEnum};
 use humantime::Duration;
 
 #[derive(Parser, Debug)]
-#[command(name = "cerberus")]
+#[command(name = "cerberus", subcommand_negates_reqs = true)]
 pub struct Cli {
+	#[command(subcommand)]
+	pub command: Option<Command>,
+
 	#[arg(long, value_enum, default_value = "tui")]
 	pub mode: RunMode,
 
@@ -16,8 +19,13 @@ pub struct Cli {
 	)]
 	pub rules: Option<PathBuf>,
 
-	#[arg(long, value_name = "IFACE", help = "Network interface to attach the XDP program")]
-	pub iface: String,
+	#[arg(
+		long,
+		value_name = "IFACE",
+		required = true,
+		help = "Network interface to attach the XDP program"
+	)]
+	pub iface: Option<String>,
 
 	#[arg(long, help = "Time duration (e.g., 20s, 5m, 1h). Optional when using --mode agent")]
 	pub time: Option<Duration>,
@@ -27,6 +35,133 @@ pub struct Cli {
 
 	#[arg(long, value_name = "PATH", help = "Write logs to the specified file or directory")]
 	pub log: Option<PathBuf>,
+
+	#[arg(
+		long,
+		value_name = "BYTES",
+		help = "Size of the kernel event ring buffer, a power of two multiple of the page size [default: 32768]"
+	)]
+	pub ring_size: Option<u32>,
+
+	#[arg(
+		long,
+		value_name = "SCORE",
+		default_value_t = 100.0,
+		help = "Accumulated rule risk at which a process, user or cgroup raises a risk alert"
+	)]
+	pub risk_threshold: f64,
+
+	#[arg(
+		long,
+		value_name = "DURATION",
+		default_value = "10m",
+		help = "Half-life of accumulated risk scores (e.g., 30s, 10m, 1h)"
+	)]
+	pub risk_half_life: Duration,
+
+	#[arg(
+		long,
+		value_name = "DURATION",
+		default_value = "24h",
+		help = "Learning period for first_seen rules, counted from the first run on this host"
+	)]
+	pub learning_period: Duration,
+
+	#[arg(
+		long,
+		value_name = "PATH",
+		help = "Extra path prefix (or bare file name) whose writes are reported, on top of the built-in list"
+	)]
+	pub watch_path: Vec<String>,
+
+	#[arg(
+		long,
+		value_name = "PATH",
+		help = "Extra path prefix (or bare file name) whose reads and writes are reported"
+	)]
+	pub watch_read_path: Vec<String>,
+
+	#[arg(
+		long,
+		value_name = "CAP",
+		help = "Capability to refuse outright, e.g. CAP_SYS_MODULE or sys_module"
+	)]
+	pub deny_cap: Vec<String>,
+
+	#[arg(
+		long,
+		value_name = "DURATION",
+		default_value = "10m",
+		help = "How long a deny_cap response refuses the capability to the matched process"
+	)]
+	pub deny_cap_ttl: Duration,
+
+	#[arg(
+		long,
+		value_name = "PATH",
+		help = "AF_UNIX socket whose stream connects are refused, by inode looked up every 5s, e.g. /run/docker.sock; @name for abstract sockets"
+	)]
+	pub deny_unix_connect: Vec<String>,
+
+	#[arg(
+		long,
+		value_name = "[TYPE:]COMM",
+		help = "Only report events from this command name, for every event type or just TYPE (e.g. EXEC:bash)"
+	)]
+	pub only_comm: Vec<String>,
+
+	#[arg(
+		long,
+		value_name = "[TYPE:]UID",
+		help = "Only report events from this user id, for every event type or just TYPE"
+	)]
+	pub only_uid: Vec<String>,
+
+	#[arg(
+		long,
+		value_name = "[TYPE:]ID",
+		help = "Only report events from this cgroup id, for every event type or just TYPE"
+	)]
+	pub only_cgroup: Vec<String>,
+
+	#[arg(
+		long,
+		value_name = "TYPE=N",
+		help = "Report one in N events of TYPE, chosen at random in the kernel (e.g. FILE=10)"
+	)]
+	pub sample: Vec<String>,
+
+	#[arg(
+		long,
+		help = "Keep reporting event types no loaded rule can match; by default the kernel drops them, so they do not show up in the TUI either"
+	)]
+	pub keep_unruled: bool,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum Command {
+	/// Manage detection rules
+	#[command(subcommand)]
+	Rules(RulesCommand),
+}
+
+#[derive(Subcommand, Debug)]
+pub enum RulesCommand {
+	/// Convert Sigma rules into Cerberus rules
+	ImportSigma {
+		#[arg(value_name = "FILE", required = true, help = "Sigma rule files (YAML)")]
+		files: Vec<PathBuf>,
+
+		#[arg(
+			long,
+			value_name = "DIR",
+			help = "Directory to write converted rules to [default: the rules directory]"
+		)]
+		out: Option<PathBuf>,
+
+		#[arg(long, help = "Also write rules that were only partially translated")]
+		allow_partial: bool,
+	},
 }
 
 #[derive(Copy, Clone, Debug, ValueEnum, PartialEq, Eq)]
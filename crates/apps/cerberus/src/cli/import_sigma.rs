@@ -0,0 +1,56 @@
+use std::path::{Path, PathBuf};
+
+use lib_rules::{Rule, RuleEngine, RuleSet, SigmaConversion, convert_sigma};
+
+use crate::error::Result;
+
+/// Converts each Sigma file into a Cerberus TOML rule in `out_dir` and prints a
+/// per-file report of anything that could not be translated.
+pub fn import_sigma(files: &[PathBuf], out_dir: &Path, allow_partial: bool) -> Result<()> {
+	std::fs::create_dir_all(out_dir)?;
+
+	let mut written = 0;
+	for file in files {
+		match import_one(file, out_dir, allow_partial) {
+			Ok(path) => {
+				written += 1;
+				println!("{}: wrote {}", file.display(), path.display());
+			}
+			Err(reason) => println!("{}: skipped, {reason}", file.display()),
+		}
+	}
+
+	println!("{written} of {} Sigma rules converted", files.len());
+	Ok(())
+}
+
+fn import_one(file: &Path, out_dir: &Path, allow_partial: bool) -> core::result::Result<PathBuf, String> {
+	let src = std::fs::read_to_string(file).map_err(|e| e.to_string())?;
+	let conversion = convert_sigma(&src).map_err(|e| e.to_string())?;
+	report(file, &conversion);
+
+	if !conversion.is_complete() && !allow_partial {
+		return Err("partially translated (pass --allow-partial to write it anyway)".into());
+	}
+
+	let toml = conversion.to_toml().map_err(|e| e.to_string())?;
+
+	// compile it once so a bad `|re` pattern fails here rather than at load time
+	RuleSet::new(vec![Rule::from_str(&toml).map_err(|e| e.to_string())?])
+		.and_then(RuleEngine::new_from_ruleset)
+		.map_err(|e| e.to_string())?;
+
+	let path = out_dir.join(conversion.file_name());
+	if path.exists() {
+		return Err(format!("{} already exists", path.display()));
+	}
+	std::fs::write(&path, toml).map_err(|e| e.to_string())?;
+
+	Ok(path)
+}
+
+fn report(file: &Path, conversion: &SigmaConversion) {
+	for item in &conversion.unsupported {
+		println!("{}: not translated: {item}", file.display());
+	}
+}
@@ -1 +1,2 @@
 pub mod args;
+pub mod import_sigma;
@@ -1,7 +1,7 @@
 use std::collections::{HashMap, VecDeque};
 use std::sync::Arc;
 
-use lib_rules::{CorrelationEvent, EvaluatedEvent, ResolvedAction, Severity};
+use lib_rules::{CorrelationEvent, EvaluatedEvent, ResolvedAction, RiskScore, Severity};
 use ratatui::layout::Rect;
 use time::OffsetDateTime;
 
@@ -13,6 +13,9 @@ use lib_common::event::CerberusEvent;
 
 const MAX_CORRELATIONS: usize = 250;
 
+/// Time of the last ring buffer loss and the events lost per type.
+pub type LossReport = (OffsetDateTime, Arc<[(&'static str, u64)]>);
+
 pub struct AppState {
 	pub(in crate::core) loaded_hooks: Vec<HookView>,
 	pub(in crate::core) hook_index: HashMap<Arc<str>, u32>,
@@ -24,6 +27,9 @@ pub struct AppState {
 	pub(in crate::core) cerberus_evts_matched: HashMap<Arc<str>, EvaluatedEntry>,
 	pub(in crate::core) response_evts: VecDeque<ResponseItem>,
 	pub(in crate::core) severity_counts: [u64; Severity::COUNT],
+	pub(in crate::core) risk_scores: Arc<[RiskScore]>,
+	pub(in crate::core) events_lost: u64,
+	pub(in crate::core) last_loss: Option<LossReport>,
 	pub correlated_groups: HashMap<(Arc<str>, Arc<str>), CorrelationGroup>,
 	scroll_zones: ScrollZones,
 	pub selected_matched_rule: usize,
@@ -61,6 +67,9 @@ impl AppState {
 
 			cerberus_evts_matched: HashMap::new(),
 			severity_counts: [0; Severity::COUNT],
+			risk_scores: Arc::from([]),
+			events_lost: 0,
+			last_loss: None,
 			current_view: View::Main,
 			tab: Tab::General,
 			selected_correlation_event: 0,
@@ -223,6 +232,19 @@ impl AppState {
 		self.response_evts.iter().rev()
 	}
 
+	pub fn risk_scores(&self) -> &[RiskScore] {
+		&self.risk_scores
+	}
+
+	/// Events dropped on a full ring buffer since startup.
+	pub fn events_lost(&self) -> u64 {
+		self.events_lost
+	}
+
+	pub fn last_loss(&self) -> Option<&LossReport> {
+		self.last_loss.as_ref()
+	}
+
 	pub fn cerberus_evts_matched(&self) -> impl Iterator<Item = &EvaluatedEntry> {
 		self.cerberus_evts_matched.values()
 	}
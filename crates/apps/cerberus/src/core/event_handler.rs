@@ -65,6 +65,15 @@ pub async fn _handle_app_event(
 			app_state.loaded_rules = rules.clone();
 		}
 
+		AppEvent::RiskScores { scores } => {
+			app_state.risk_scores = Arc::clone(scores);
+		}
+
+		AppEvent::EventLoss { lost, time } => {
+			app_state.events_lost += lost.iter().map(|(_, n)| n).sum::<u64>();
+			app_state.last_loss = Some((*time, Arc::clone(lost)));
+		}
+
 		AppEvent::ResponseExecuted {
 			rule_id,
 			actions,
@@ -96,9 +105,26 @@ fn handle_cerberus_event(event: &CerberusEvent, app_state: &mut AppState) {
 	let events = match event {
 		CerberusEvent::Generic(_) => &mut app_state.cerberus_evts_general,
 		CerberusEvent::Bprm(_) => &mut app_state.cerberus_evts_general,
+		CerberusEvent::ProcessFork(_) => &mut app_state.cerberus_evts_general,
+		CerberusEvent::ProcessExec(_) => &mut app_state.cerberus_evts_general,
+		CerberusEvent::ProcessExit(_) => &mut app_state.cerberus_evts_general,
+		CerberusEvent::CredChange(_) => &mut app_state.cerberus_evts_general,
+		CerberusEvent::IoUring(_) => &mut app_state.cerberus_evts_general,
+		CerberusEvent::MemProt(_) => &mut app_state.cerberus_evts_general,
+		CerberusEvent::File(_) => &mut app_state.cerberus_evts_general,
+		CerberusEvent::Capable(_) => &mut app_state.cerberus_evts_general,
+		CerberusEvent::Capset(_) => &mut app_state.cerberus_evts_general,
+		CerberusEvent::Mount(_) => &mut app_state.cerberus_evts_general,
+		CerberusEvent::Namespace(_) => &mut app_state.cerberus_evts_general,
+		CerberusEvent::InodeAttr(_) => &mut app_state.cerberus_evts_general,
 		CerberusEvent::Module(_) => &mut app_state.cerberus_evts_general,
 		CerberusEvent::InetSock(_) => &mut app_state.cerberus_evts_network,
 		CerberusEvent::Socket(_) => &mut app_state.cerberus_evts_network,
+		CerberusEvent::UnixSocket(_) => &mut app_state.cerberus_evts_network,
+		CerberusEvent::SocketCreate(_) => &mut app_state.cerberus_evts_network,
+		CerberusEvent::UdpSend(_) => &mut app_state.cerberus_evts_network,
+		CerberusEvent::InetAccept(_) => &mut app_state.cerberus_evts_network,
+		CerberusEvent::Dns(_) => &mut app_state.cerberus_evts_network,
 		CerberusEvent::BpfProgLoad(_) => &mut app_state.cerberus_evts_general,
 		CerberusEvent::Inode(_) => &mut app_state.cerberus_evts_general,
 		CerberusEvent::BpfMap(_) => &mut app_state.cerberus_evts_general,
@@ -28,10 +28,22 @@ pub enum Error {
 	NoTimeSpecified,
 	#[display("Invalid event rate")]
 	InvalidRate,
+	#[display("Watch path '{_0}' is empty or too long")]
+	InvalidWatchPath(String),
+	#[display("Unix socket '{_0}' is not a bound socket, or the name is empty or too long")]
+	InvalidUnixPath(String),
+	#[display("Unknown capability '{_0}'")]
+	UnknownCapability(String),
+	#[display("Invalid event filter '{_0}'")]
+	InvalidEventFilter(String),
+	#[display("Ring buffer size {_0} is not a power of two multiple of the page size")]
+	InvalidRingSize(u32),
 	#[display("No rules found in '{_0}'")]
 	NoRulesInDir(String),
 	#[display("Unknown event type '{_0}'")]
 	UnknownEventType(u8),
+	#[display("eBPF object sends event wire version {found}, expected {expected}; rebuild it with this agent")]
+	EventVersionMismatch { expected: u8, found: u8 },
 	#[from]
 	#[display("System time error: {_0}")]
 	SystemTime(std::time::SystemTimeError),
@@ -59,6 +71,8 @@ pub enum Error {
 	#[from]
 	#[display("IO error: {_0}")]
 	Io(std::io::Error),
+	#[display("No network interface given, pass --iface")]
+	MissingIface,
 	#[display("User home directory not found")]
 	HomeDirNotFound,
 	#[from]
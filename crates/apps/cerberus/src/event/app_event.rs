@@ -2,7 +2,7 @@ use std::sync::Arc;
 
 use derive_more::From;
 use lib_common::event::CerberusEvent;
-use lib_rules::{EngineEvent, ResolvedAction};
+use lib_rules::{EngineEvent, ResolvedAction, RiskScore};
 use time::OffsetDateTime;
 
 #[derive(From, Clone)]
@@ -30,10 +30,19 @@ pub enum AppEvent {
 		time: OffsetDateTime,
 		success: bool,
 	},
+	RiskScores {
+		scores: Arc<[RiskScore]>,
+	},
 	HookFailed {
 		hook: Arc<str>,
 		error: String,
 	},
+	/// Events the kernel could not fit in the ring buffer since the last
+	/// report, per event type.
+	EventLoss {
+		lost: Arc<[(&'static str, u64)]>,
+		time: OffsetDateTime,
+	},
 }
 
 #[derive(Debug, Clone)]
@@ -4,4 +4,6 @@ use std::sync::Arc;
 pub enum HookCommand {
 	Enable(Arc<str>),
 	Disable(Arc<str>),
+	/// Re-derive the kernel pre-filter from the current rules.
+	ApplyPolicy,
 }
@@ -0,0 +1,87 @@
+use aya::{
+	Ebpf,
+	maps::{
+		HashMap,
+		lpm_trie::{Key, LpmTrie},
+	},
+};
+use lib_ebpf_common::{FILE_NAME_LEN, FILE_PATH_LEN, FILE_WATCH_READS, FILE_WATCH_WRITES};
+
+use crate::{Error, Result};
+
+/// Path prefixes whose opens for writing are reported.
+const WATCH_WRITES: &[&str] = &[
+	"/etc/passwd",
+	"/etc/group",
+	"/etc/sudoers",
+	"/etc/cron",
+	"/etc/anacrontab",
+	"/var/spool/cron/",
+	"/etc/systemd/system/",
+	"/usr/lib/systemd/system/",
+	"/etc/ld.so.preload",
+	"/etc/pam.d/",
+	"/etc/ssh/sshd_config",
+	"/etc/profile",
+	"/etc/bash.bashrc",
+	"/root/.ssh/",
+];
+
+/// Path prefixes whose reads are reported as well, i.e. credential stores.
+const WATCH_READS: &[&str] = &["/etc/shadow", "/etc/gshadow"];
+
+/// File names watched for writes in any directory.
+const WATCH_NAMES: &[&str] = &["authorized_keys", "authorized_keys2", ".bashrc", ".bash_profile", ".profile"];
+
+/// Fills the in-kernel filters of the `file_open`/`file_permission` hooks with
+/// the default watch list plus `extra_writes` and `extra_reads` from the CLI.
+/// Entries without a `/` are file names, anything else is a path prefix.
+pub fn populate_file_watch(ebpf: &mut Ebpf, extra_writes: &[String], extra_reads: &[String]) -> Result<()> {
+	let writes = WATCH_WRITES.iter().copied().chain(extra_writes.iter().map(String::as_str));
+	let reads = WATCH_READS.iter().copied().chain(extra_reads.iter().map(String::as_str));
+	let entries = writes
+		.map(|path| (path, FILE_WATCH_WRITES))
+		.chain(reads.map(|path| (path, FILE_WATCH_WRITES | FILE_WATCH_READS)))
+		.chain(WATCH_NAMES.iter().map(|name| (*name, FILE_WATCH_WRITES)));
+
+	let mut names: Vec<(&str, u8)> = Vec::new();
+	{
+		let mut trie: LpmTrie<_, [u8; FILE_PATH_LEN], u8> =
+			LpmTrie::try_from(ebpf.map_mut("FILE_WATCH").ok_or(Error::EbpfMapNotFound {
+				map: "FILE_WATCH".into(),
+			})?)?;
+
+		for (path, flags) in entries {
+			if !path.contains('/') {
+				names.push((path, flags));
+				continue;
+			}
+			let data = padded::<FILE_PATH_LEN>(path)?;
+			let key = Key::new((path.len() * 8) as u32, data);
+			let flags = trie.get(&key, 0).unwrap_or(0) | flags;
+			trie.insert(&key, flags, 0)?;
+		}
+	}
+
+	let mut name_map: HashMap<_, [u8; FILE_NAME_LEN], u8> =
+		HashMap::try_from(ebpf.map_mut("FILE_WATCH_NAMES").ok_or(Error::EbpfMapNotFound {
+			map: "FILE_WATCH_NAMES".into(),
+		})?)?;
+	for (name, flags) in names {
+		let key = padded::<FILE_NAME_LEN>(name)?;
+		let flags = name_map.get(&key, 0).unwrap_or(0) | flags;
+		name_map.insert(key, flags, 0)?;
+	}
+
+	Ok(())
+}
+
+/// Zero-padded copy of `path`; the last byte stays NUL like the kernel's copy.
+fn padded<const N: usize>(path: &str) -> Result<[u8; N]> {
+	if path.is_empty() || path.len() >= N {
+		return Err(Error::InvalidWatchPath(path.to_string()));
+	}
+	let mut buf = [0u8; N];
+	buf[..path.len()].copy_from_slice(path.as_bytes());
+	Ok(buf)
+}
@@ -1,6 +1,6 @@
 use aya::{
 	Btf, Ebpf,
-	programs::{KProbe, Lsm, TracePoint, Xdp, XdpMode},
+	programs::{BtfTracePoint, KProbe, Lsm, TracePoint, Xdp, XdpMode},
 };
 
 use crate::{
@@ -64,6 +64,29 @@ pub fn register_tracepoint(
 	Ok(())
 }
 
+pub fn register_btf_tracepoint(
+	ebpf: &mut Ebpf,
+	registry: &mut HookRegistry,
+	program_name: &str,
+	tracepoint: &str,
+	btf: &Btf,
+) -> Result<()> {
+	let prog: &mut BtfTracePoint = ebpf
+		.program_mut(program_name)
+		.ok_or(Error::EbpfProgNotFound {
+			program: program_name.into(),
+		})?
+		.try_into()?;
+
+	prog.load(tracepoint, btf)?;
+
+	let link = prog.attach()?;
+
+	registry.add(Hook::new(program_name, HookKind::BtfTracepoint, link.into()))?;
+
+	Ok(())
+}
+
 pub fn register_kprobe(
 	ebpf: &mut Ebpf,
 	registry: &mut HookRegistry,
@@ -3,8 +3,8 @@ use std::sync::Arc;
 use aya::{
 	Ebpf,
 	programs::{
-		KProbe, Lsm, TracePoint, Xdp, XdpMode, kprobe::KProbeLinkId, lsm::LsmLinkId, trace_point::TracePointLinkId,
-		xdp::XdpLinkId,
+		BtfTracePoint, KProbe, Lsm, TracePoint, Xdp, XdpMode, kprobe::KProbeLinkId, lsm::LsmLinkId,
+		tp_btf::BtfTracePointLinkId, trace_point::TracePointLinkId, xdp::XdpLinkId,
 	},
 };
 use derive_more::From;
@@ -18,6 +18,8 @@ pub enum HookLink {
 	#[from]
 	Tracepoint(TracePointLinkId),
 	#[from]
+	BtfTracepoint(BtfTracePointLinkId),
+	#[from]
 	Kprobe(KProbeLinkId),
 	#[from]
 	Xdp(XdpLinkId),
@@ -27,6 +29,7 @@ pub enum HookKind {
 	Lsm,
 	Xdp { iface: Arc<str> },
 	Tracepoint { category: Arc<str>, event: Arc<str> },
+	BtfTracepoint,
 	Kprobe { function: Arc<str>, offset: u64 },
 }
 
@@ -83,6 +86,17 @@ impl Hook {
 				prog.unload()?;
 			}
 
+			HookKind::BtfTracepoint => {
+				let prog: &mut BtfTracePoint = ebpf
+					.program_mut(&self.program_name)
+					.ok_or(Error::EbpfProgNotFound {
+						program: self.program_name.clone(),
+					})?
+					.try_into()?;
+
+				prog.unload()?;
+			}
+
 			HookKind::Kprobe { .. } => {
 				let prog: &mut KProbe = ebpf
 					.program_mut(&self.program_name)
@@ -137,6 +151,17 @@ impl Hook {
 				self.link = Some(prog.attach(category, event)?.into());
 			}
 
+			HookKind::BtfTracepoint => {
+				let prog: &mut BtfTracePoint = ebpf
+					.program_mut(&self.program_name)
+					.ok_or(Error::EbpfProgNotFound {
+						program: self.program_name.clone(),
+					})?
+					.try_into()?;
+
+				self.link = Some(prog.attach()?.into());
+			}
+
 			HookKind::Xdp { iface } => {
 				let prog: &mut Xdp = ebpf
 					.program_mut(&self.program_name)
@@ -191,6 +216,17 @@ impl Hook {
 				prog.detach(id)?;
 			}
 
+			HookLink::BtfTracepoint(id) => {
+				let prog: &mut BtfTracePoint = ebpf
+					.program_mut(&self.program_name)
+					.ok_or(Error::EbpfProgNotFound {
+						program: self.program_name.clone(),
+					})?
+					.try_into()?;
+
+				prog.detach(id)?;
+			}
+
 			HookLink::Kprobe(id) => {
 				let prog: &mut KProbe = ebpf
 					.program_mut(&self.program_name)
@@ -1,6 +1,8 @@
 mod hook;
 
 pub mod event;
+pub mod file_watch;
 pub mod helper_fns;
+pub mod policy;
 pub mod registry;
 pub use hook::{HookState, HookView};
@@ -0,0 +1,208 @@
+use aya::{
+	Ebpf,
+	maps::{Array, HashMap},
+};
+use lib_ebpf_common::{
+	EVT_BPF_MAP, EVT_BPF_PROG_LOAD, EVT_BPRM_CHECK_SEC, EVT_CAP, EVT_COMMIT_CREDS, EVT_DNS, EVT_ENTER_PTRACE, EVT_FILE,
+	EVT_INET_ACCEPT, EVT_INET_SOCK_SET_STATE, EVT_INODE, EVT_INODE_ATTR, EVT_INODE_MUTATE, EVT_IO_URING, EVT_KILL,
+	EVT_MEM_PROT, EVT_MODULE, EVT_MOUNT, EVT_NS, EVT_PROCESS_EXEC, EVT_PROCESS_EXIT, EVT_PROCESS_FORK,
+	EVT_PTRACE_ACCESS_CHECK, EVT_SOCKET, EVT_SOCKET_CREATE, EVT_TYPE_COUNT, EVT_UDP_SEND, EVT_UNIX_SOCKET,
+	POLICY_CGROUP_ALLOW, POLICY_COMM_ALLOW, POLICY_DROP, POLICY_UID_ALLOW,
+};
+use lib_rules::{EventKind, RuleIndex};
+
+use crate::{Error, Result, workers::event_name};
+
+const ALL_TYPES: u32 = u32::MAX;
+
+/// In-kernel pre-filter settings from the CLI. Allowlist entries carry the mask
+/// of event types they apply to; an event type with at least one entry only
+/// reports events that match one.
+#[derive(Debug, Default, Clone)]
+pub struct EventPolicy {
+	pub comms: Vec<([u8; 16], u32)>,
+	pub uids: Vec<(u32, u32)>,
+	pub cgroups: Vec<(u64, u32)>,
+	/// Event type and 1-in-N rate.
+	pub sample: Vec<(u8, u32)>,
+	/// Drop event types that no loaded rule can match.
+	pub drop_unruled: bool,
+}
+
+impl EventPolicy {
+	/// Parses `[TYPE:]VALUE` allowlist entries and `TYPE=N` sampling rates, where
+	/// TYPE is an event name as shown in the logs, e.g. `EXEC` or `udp_send`.
+	pub fn from_args(
+		comms: &[String],
+		uids: &[String],
+		cgroups: &[String],
+		sample: &[String],
+		drop_unruled: bool,
+	) -> Result<Self> {
+		let comms = comms
+			.iter()
+			.map(|arg| {
+				let (mask, comm) = scoped(arg)?;
+				if comm.is_empty() || comm.len() >= 16 {
+					return Err(Error::InvalidEventFilter(arg.clone()));
+				}
+				let mut key = [0u8; 16];
+				key[..comm.len()].copy_from_slice(comm.as_bytes());
+				Ok((key, mask))
+			})
+			.collect::<Result<_>>()?;
+		let uids = uids
+			.iter()
+			.map(|arg| {
+				let (mask, uid) = scoped(arg)?;
+				Ok((uid.parse().map_err(|_| Error::InvalidEventFilter(arg.clone()))?, mask))
+			})
+			.collect::<Result<_>>()?;
+		let cgroups = cgroups
+			.iter()
+			.map(|arg| {
+				let (mask, id) = scoped(arg)?;
+				Ok((id.parse().map_err(|_| Error::InvalidEventFilter(arg.clone()))?, mask))
+			})
+			.collect::<Result<_>>()?;
+		let sample = sample
+			.iter()
+			.map(|arg| {
+				let (name, rate) = arg.split_once('=').ok_or_else(|| Error::InvalidEventFilter(arg.clone()))?;
+				let event_type = event_type_from_name(name).ok_or_else(|| Error::InvalidEventFilter(arg.clone()))?;
+				let rate = rate.parse().map_err(|_| Error::InvalidEventFilter(arg.clone()))?;
+				Ok((event_type, rate))
+			})
+			.collect::<Result<_>>()?;
+
+		Ok(Self {
+			comms,
+			uids,
+			cgroups,
+			sample,
+			drop_unruled,
+		})
+	}
+
+	/// Writes the allowlists, sampling rates and per-type flags to the kernel.
+	/// With `drop_unruled`, types whose event kinds have no candidate rule in
+	/// `index` are dropped at the source.
+	pub fn apply(&self, ebpf: &mut Ebpf, index: &RuleIndex) -> Result<()> {
+		let mut flags = [0u32; EVT_TYPE_COUNT as usize];
+		{
+			let mut map: HashMap<_, [u8; 16], u32> = HashMap::try_from(map_mut(ebpf, "POLICY_COMM")?)?;
+			for (comm, mask) in &self.comms {
+				set_allow(&mut flags, *mask, POLICY_COMM_ALLOW);
+				let mask = map.get(comm, 0).unwrap_or(0) | mask;
+				map.insert(comm, mask, 0)?;
+			}
+		}
+		{
+			let mut map: HashMap<_, u32, u32> = HashMap::try_from(map_mut(ebpf, "POLICY_UID")?)?;
+			for (uid, mask) in &self.uids {
+				set_allow(&mut flags, *mask, POLICY_UID_ALLOW);
+				let mask = map.get(uid, 0).unwrap_or(0) | mask;
+				map.insert(uid, mask, 0)?;
+			}
+		}
+		{
+			let mut map: HashMap<_, u64, u32> = HashMap::try_from(map_mut(ebpf, "POLICY_CGROUP")?)?;
+			for (cgroup, mask) in &self.cgroups {
+				set_allow(&mut flags, *mask, POLICY_CGROUP_ALLOW);
+				let mask = map.get(cgroup, 0).unwrap_or(0) | mask;
+				map.insert(cgroup, mask, 0)?;
+			}
+		}
+		{
+			let mut map: Array<_, u32> = Array::try_from(map_mut(ebpf, "EVT_SAMPLE")?)?;
+			for (event_type, rate) in &self.sample {
+				map.set(*event_type as u32, rate, 0)?;
+			}
+		}
+
+		if self.drop_unruled {
+			let mut used = 0u32;
+			let mut known = 0u32;
+			for kind in EventKind::all() {
+				let types = kind_event_types(kind).iter().fold(0u32, |acc, t| acc | 1 << t);
+				known |= types;
+				if !index.candidates(kind).is_empty() {
+					used |= types;
+				}
+			}
+			for (event_type, f) in flags.iter_mut().enumerate() {
+				if known & !used & (1 << event_type) != 0 {
+					*f |= POLICY_DROP;
+				}
+			}
+		}
+
+		let mut map: Array<_, u32> = Array::try_from(map_mut(ebpf, "EVT_POLICY")?)?;
+		for (event_type, f) in flags.iter().enumerate() {
+			map.set(event_type as u32, f, 0)?;
+		}
+
+		Ok(())
+	}
+}
+
+/// Kernel event types an event kind is parsed from.
+const fn kind_event_types(kind: EventKind) -> &'static [u8] {
+	match kind {
+		EventKind::Generic => &[EVT_KILL, EVT_ENTER_PTRACE],
+		EventKind::InetSock => &[EVT_INET_SOCK_SET_STATE],
+		EventKind::UdpSend => &[EVT_UDP_SEND],
+		EventKind::InetAccept => &[EVT_INET_ACCEPT],
+		EventKind::Dns => &[EVT_DNS],
+		EventKind::Socket => &[EVT_SOCKET],
+		EventKind::UnixSocket => &[EVT_UNIX_SOCKET],
+		EventKind::SocketCreate => &[EVT_SOCKET_CREATE],
+		EventKind::Module => &[EVT_MODULE],
+		EventKind::BpfProgLoad => &[EVT_BPF_PROG_LOAD],
+		EventKind::PtraceAccessCheck => &[EVT_PTRACE_ACCESS_CHECK],
+		EventKind::BpfMap => &[EVT_BPF_MAP],
+		EventKind::Inode => &[EVT_INODE],
+		EventKind::InodeMutate => &[EVT_INODE_MUTATE],
+		EventKind::InodeAttr => &[EVT_INODE_ATTR],
+		EventKind::Bprm => &[EVT_BPRM_CHECK_SEC],
+		EventKind::ProcessFork => &[EVT_PROCESS_FORK],
+		EventKind::ProcessExec => &[EVT_PROCESS_EXEC],
+		EventKind::ProcessExit => &[EVT_PROCESS_EXIT],
+		EventKind::CredChange => &[EVT_COMMIT_CREDS],
+		EventKind::IoUring => &[EVT_IO_URING],
+		EventKind::MemProt => &[EVT_MEM_PROT],
+		EventKind::File => &[EVT_FILE],
+		EventKind::Capable | EventKind::Capset => &[EVT_CAP],
+		EventKind::Mount => &[EVT_MOUNT],
+		EventKind::Namespace => &[EVT_NS],
+		EventKind::Orthrus => &[],
+	}
+}
+
+fn event_type_from_name(name: &str) -> Option<u8> {
+	(0..EVT_TYPE_COUNT as u8).find(|&t| event_name(t) != "UNKNOWN" && event_name(t).eq_ignore_ascii_case(name))
+}
+
+/// Splits `TYPE:VALUE` into the type's mask and the value; without a known
+/// prefix the entry applies to every event type.
+fn scoped(arg: &str) -> Result<(u32, &str)> {
+	match arg.split_once(':') {
+		Some((name, value)) => {
+			let event_type = event_type_from_name(name).ok_or_else(|| Error::InvalidEventFilter(arg.to_string()))?;
+			Ok((1 << event_type, value))
+		}
+		None => Ok((ALL_TYPES, arg)),
+	}
+}
+
+fn set_allow(flags: &mut [u32], mask: u32, flag: u32) {
+	for (event_type, f) in flags.iter_mut().enumerate() {
+		if mask & (1 << event_type) != 0 {
+			*f |= flag;
+		}
+	}
+}
+
+fn map_mut<'a>(ebpf: &'a mut Ebpf, name: &str) -> Result<&'a mut aya::maps::Map> {
+	ebpf.map_mut(name).ok_or(Error::EbpfMapNotFound { map: name.into() })
+}
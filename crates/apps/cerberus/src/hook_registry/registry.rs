@@ -7,13 +7,18 @@ use std::{
 };
 
 use aya::Ebpf;
+use lib_rules::RuleIndex;
 
-use crate::{Error, Result, hook_registry::hook::Hook};
+use crate::{
+	Error, Result,
+	hook_registry::{hook::Hook, policy::EventPolicy},
+};
 
 #[derive(Default)]
 pub struct HookRegistry {
 	hooks: HashMap<Arc<str>, Hook>,
 	prog_count: Option<Arc<AtomicUsize>>,
+	policy: EventPolicy,
 }
 
 impl HookRegistry {
@@ -22,6 +27,16 @@ impl HookRegistry {
 		self.refresh_count();
 	}
 
+	pub fn set_policy(&mut self, policy: EventPolicy) {
+		self.policy = policy;
+	}
+
+	/// Pushes the pre-filter policy to the kernel; called again whenever the
+	/// rules, and so the set of event kinds they need, change.
+	pub fn apply_policy(&self, ebpf: &mut Ebpf, index: &RuleIndex) -> Result<()> {
+		self.policy.apply(ebpf, index)
+	}
+
 	fn refresh_count(&self) {
 		if let Some(c) = &self.prog_count {
 			let n = self.hooks.values().filter(|h| h.is_enabled()).count();
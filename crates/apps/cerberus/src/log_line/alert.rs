@@ -50,6 +50,18 @@ pub fn alert_from_engine_event(e: &EngineEvent) -> String {
 		EngineEvent::Response(r) => {
 			format!("[RESPONSE] rule={} action={:?}", r.rule_id, r.response_chain)
 		}
+
+		EngineEvent::Risk(r) => {
+			format!(
+				"[RISK] {} score={:.1} threshold={:.1} (PID: {}, UID: {}) rules: {}",
+				r.entity,
+				r.score,
+				r.threshold,
+				r.event_meta.pid,
+				r.event_meta.uid,
+				r.contributors.join(", "),
+			)
+		}
 	}
 }
 
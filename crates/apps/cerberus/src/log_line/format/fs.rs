@@ -1,4 +1,4 @@
-use lib_common::event::{InodeEvent, InodeMutationEvent};
+use lib_common::event::{FileEvent, InodeAttrEvent, InodeEvent, InodeMutationEvent, MountEvent};
 
 use crate::log_line::utils::{inode_mutation_to_string, inode_op_to_string};
 
@@ -12,7 +12,7 @@ pub fn render_inode(i: &InodeEvent) -> String {
 		h.pid,
 		h.tgid,
 		h.comm,
-		i.filename
+		i.path.as_deref().unwrap_or(&i.filename)
 	)
 }
 
@@ -26,7 +26,74 @@ pub fn render_inode_mutation(m: &InodeMutationEvent) -> String {
 		h.pid,
 		h.tgid,
 		h.comm,
-		m.old_filename,
-		m.new_filename
+		m.old_path.as_deref().unwrap_or(&m.old_filename),
+		m.new_path.as_deref().unwrap_or(&m.new_filename)
+	)
+}
+
+pub fn render_inode_attr(a: &InodeAttrEvent) -> String {
+	let h = &a.header;
+
+	let mut changes = Vec::new();
+	if let Some(mode) = a.mode {
+		changes.push(format!("MODE:{mode:o}"));
+	}
+	if let Some(uid) = a.uid {
+		changes.push(format!("OWNER:{uid}"));
+	}
+	if let Some(gid) = a.gid {
+		changes.push(format!("GROUP:{gid}"));
+	}
+	if let Some(mtime) = a.mtime.or(a.atime) {
+		changes.push(format!("TIME:{mtime}"));
+	}
+
+	format!(
+		"[INODE_{}] UID:{} | PID:{} | TGID:{} | CMD:{} | FILE:{} | {}",
+		a.op_name().to_uppercase(),
+		h.uid,
+		h.pid,
+		h.tgid,
+		h.comm,
+		a.path.as_deref().unwrap_or(&a.filename),
+		changes.join(" | ")
+	)
+}
+
+pub fn render_file(f: &FileEvent) -> String {
+	let h = &f.header;
+
+	format!(
+		"[FILE_{}] UID:{} | PID:{} | TGID:{} | CMD:{} | ACCESS:{} | FILE:{}",
+		f.op_name().to_uppercase(),
+		h.uid,
+		h.pid,
+		h.tgid,
+		h.comm,
+		f.access_names().join(","),
+		f.path
+	)
+}
+
+pub fn render_mount(m: &MountEvent) -> String {
+	let h = &m.header;
+	let flags = m.flag_names();
+
+	format!(
+		"[MOUNT_{}] UID:{} | PID:{} | TGID:{} | CMD:{} | {} -> {} | TYPE:{}{}{}",
+		m.op_name().to_uppercase(),
+		h.uid,
+		h.pid,
+		h.tgid,
+		h.comm,
+		m.source.as_deref().unwrap_or("?"),
+		m.target.as_deref().unwrap_or("?"),
+		m.fstype.as_deref().unwrap_or("-"),
+		if flags.is_empty() {
+			String::new()
+		} else {
+			format!(" | FLAGS:{}", flags.join(","))
+		},
+		if m.host_ns { "" } else { " | CONTAINER_NS" }
 	)
 }
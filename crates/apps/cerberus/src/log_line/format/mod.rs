@@ -12,13 +12,30 @@ pub fn string_from_event(evt: &CerberusEvent) -> String {
 		CerberusEvent::Generic(e) => process::render_generic(e),
 		CerberusEvent::Module(e) => process::render_module(e),
 		CerberusEvent::Bprm(e) => process::render_bprm(e),
+		CerberusEvent::ProcessFork(e) => process::render_fork(e),
+		CerberusEvent::ProcessExec(e) => process::render_exec(e),
+		CerberusEvent::ProcessExit(e) => process::render_exit(e),
+		CerberusEvent::CredChange(e) => process::render_cred_change(e),
+		CerberusEvent::IoUring(e) => process::render_io_uring(e),
+		CerberusEvent::MemProt(e) => process::render_mem_prot(e),
 		CerberusEvent::PtraceAccessCheck(e) => process::render_ptrace(e),
+		CerberusEvent::Capable(e) => process::render_capable(e),
+		CerberusEvent::Capset(e) => process::render_capset(e),
+		CerberusEvent::Namespace(e) => process::render_namespace(e),
 
 		CerberusEvent::Inode(e) => fs::render_inode(e),
 		CerberusEvent::InodeMutation(e) => fs::render_inode_mutation(e),
+		CerberusEvent::InodeAttr(e) => fs::render_inode_attr(e),
+		CerberusEvent::File(e) => fs::render_file(e),
+		CerberusEvent::Mount(e) => fs::render_mount(e),
 
 		CerberusEvent::Socket(e) => network::render_socket(e),
+		CerberusEvent::UnixSocket(e) => network::render_unix_socket(e),
+		CerberusEvent::SocketCreate(e) => network::render_socket_create(e),
+		CerberusEvent::UdpSend(e) => network::render_udp_send(e),
+		CerberusEvent::Dns(e) => network::render_dns(e),
 		CerberusEvent::InetSock(e) => network::render_inet_sock(e),
+		CerberusEvent::InetAccept(e) => network::render_inet_accept(e),
 
 		CerberusEvent::BpfProgLoad(e) => bpf::render_bpf_prog(e),
 		CerberusEvent::BpfMap(e) => bpf::render_bpf_map(e),
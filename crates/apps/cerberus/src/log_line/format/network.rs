@@ -1,26 +1,78 @@
-use lib_common::event::{InetSockEvent, SocketEvent};
+use lib_common::event::{
+	DnsEvent, InetAcceptEvent, InetSockEvent, SocketCreateEvent, SocketEvent, UdpSendEvent, UnixSocketEvent,
+};
 
-use crate::log_line::utils::{family_to_string, ip_to_string, socket_op_to_string};
+use crate::log_line::utils::{family_to_string, socket_addr_to_string, socket_op_to_string, socket_type_to_string};
 
 pub fn render_socket(s: &SocketEvent) -> String {
 	format!(
-		"[SOCKET_{}] {}:{} | FAMILY:{}",
+		"[SOCKET_{}] {} | FAMILY:{}",
 		socket_op_to_string(s.op),
-		ip_to_string(s.addr),
-		s.port,
+		socket_addr_to_string(s.addr, s.port),
 		family_to_string(s.family)
 	)
 }
 
+pub fn render_unix_socket(s: &UnixSocketEvent) -> String {
+	let h = &s.header;
+
+	format!(
+		"[UNIX_{}] PID:{} | TGID:{} | CMD:{} | PATH:{}{}",
+		socket_op_to_string(s.op),
+		h.pid,
+		h.tgid,
+		h.comm,
+		s.path,
+		if s.denied { " | DENIED" } else { "" }
+	)
+}
+
+pub fn render_socket_create(s: &SocketCreateEvent) -> String {
+	format!(
+		"[SOCKET_CREATE] FAMILY:{} | TYPE:{} | PROTO:{}",
+		family_to_string(s.family),
+		socket_type_to_string(s.sock_type),
+		s.protocol
+	)
+}
+
 pub fn render_inet_sock(n: &InetSockEvent) -> String {
 	format!(
-		"[INET_SOCK] {}:{} -> {}:{} | PROTO:{} | {} -> {}",
-		ip_to_string(n.saddr),
-		n.sport,
-		ip_to_string(n.daddr),
-		n.dport,
+		"[INET_SOCK] {} -> {} | PROTO:{} | {} -> {}",
+		socket_addr_to_string(n.saddr, n.sport),
+		socket_addr_to_string(n.daddr, n.dport),
 		n.protocol,
 		n.old_state,
 		n.new_state
 	)
 }
+
+pub fn render_inet_accept(a: &InetAcceptEvent) -> String {
+	format!(
+		"[INET_ACCEPT] {} <- {} | PROTO:{}",
+		socket_addr_to_string(a.saddr, a.sport),
+		socket_addr_to_string(a.daddr, a.dport),
+		a.protocol
+	)
+}
+
+pub fn render_udp_send(u: &UdpSendEvent) -> String {
+	format!(
+		"[UDP_SEND] {} -> {}",
+		socket_addr_to_string(u.saddr, u.sport),
+		socket_addr_to_string(u.daddr, u.dport)
+	)
+}
+
+pub fn render_dns(d: &DnsEvent) -> String {
+	let answer = match d.rcode {
+		None => "NO_RESPONSE".to_string(),
+		Some(rcode) if d.answers.is_empty() => format!("RCODE:{rcode}"),
+		Some(rcode) => {
+			let ips: Vec<String> = d.answers.iter().map(|ip| ip.to_string()).collect();
+			format!("RCODE:{rcode} | {}", ips.join(","))
+		}
+	};
+
+	format!("[DNS] {} | QTYPE:{} | SERVER:{} | {answer}", d.qname, d.qtype, d.server)
+}
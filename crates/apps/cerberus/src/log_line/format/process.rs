@@ -1,6 +1,13 @@
-use lib_common::event::{BprmSecurityEvent, ModuleEvent, PtraceAccessCheckEvent, RingBufEvent};
+use lib_common::event::{
+	BprmSecurityEvent, CapableEvent, CapsetEvent, CredChangeEvent, IoUringEvent, MemProtEvent, ModuleEvent,
+	NamespaceEvent, ProcessExecEvent, ProcessExitEvent, ProcessForkEvent, PtraceAccessCheckEvent, RingBufEvent,
+};
 
-use crate::log_line::utils::{module_op_to_string, ptrace_stage_to_string};
+use lib_ebpf_common::MODULE_OP_INIT;
+
+use crate::log_line::utils::{
+	module_op_to_string, module_source_to_string, ptrace_stage_to_string, socket_addr_to_string,
+};
 
 pub fn render_generic(g: &RingBufEvent) -> String {
 	let h = &g.header;
@@ -15,13 +22,19 @@ pub fn render_module(m: &ModuleEvent) -> String {
 	let h = &m.header;
 
 	format!(
-		"[MODULE_{}] UID:{} | PID:{} | TGID:{} | CMD:{} | MODULE:{}",
+		"[MODULE_{}] UID:{} | PID:{} | TGID:{} | CMD:{} | MODULE:{}{}{}",
 		module_op_to_string(m.op),
 		h.uid,
 		h.pid,
 		h.tgid,
 		h.comm,
-		m.module_name
+		m.module_name,
+		if m.op == MODULE_OP_INIT {
+			format!(" | SRC:{}", module_source_to_string(m.source))
+		} else {
+			String::new()
+		},
+		m.path.as_ref().map(|p| format!(" | PATH:{p}")).unwrap_or_default()
 	)
 }
 
@@ -34,6 +47,148 @@ pub fn render_bprm(b: &BprmSecurityEvent) -> String {
 	)
 }
 
+pub fn render_fork(f: &ProcessForkEvent) -> String {
+	let h = &f.header;
+
+	format!(
+		"[FORK] UID:{} | PID:{} | TGID:{} | CMD:{} -> CHILD_PID:{}",
+		h.uid, h.pid, h.tgid, h.comm, f.child_pid
+	)
+}
+
+pub fn render_exec(e: &ProcessExecEvent) -> String {
+	let h = &e.header;
+
+	if e.success {
+		format!(
+			"[EXEC_COMMIT] UID:{} | PID:{} | TGID:{} | CMD:{} | FILE:{} | ARGS:{}",
+			h.uid, h.pid, h.tgid, h.comm, e.filename, e.cmdline
+		)
+	} else {
+		format!(
+			"[EXEC_FAILED] UID:{} | PID:{} | TGID:{} | CMD:{} | ERRNO:{}",
+			h.uid, h.pid, h.tgid, h.comm, e.errno
+		)
+	}
+}
+
+pub fn render_exit(e: &ProcessExitEvent) -> String {
+	let h = &e.header;
+
+	format!(
+		"[EXIT] UID:{} | PID:{} | TGID:{} | CMD:{} | CODE:{} | SIGNAL:{}",
+		h.uid, h.pid, h.tgid, h.comm, e.exit_code, e.signal
+	)
+}
+
+pub fn render_cred_change(c: &CredChangeEvent) -> String {
+	let h = &c.header;
+
+	format!(
+		"[CRED_CHANGE] PID:{} | TGID:{} | CMD:{} | UID:{}->{} | EUID:{}->{} | GID:{}->{} | CAPS:{:#x}->{:#x}",
+		h.pid,
+		h.tgid,
+		h.comm,
+		c.old_uid,
+		c.new_uid,
+		c.old_euid,
+		c.new_euid,
+		c.old_gid,
+		c.new_gid,
+		c.old_cap_effective,
+		c.new_cap_effective
+	)
+}
+
+pub fn render_mem_prot(m: &MemProtEvent) -> String {
+	let h = &m.header;
+
+	let prot = match m.transition() {
+		Some(transition) => transition,
+		None => MemProtEvent::prot_str(m.prot).to_string(),
+	};
+
+	format!(
+		"[MEM_{}] PID:{} | TGID:{} | CMD:{} | PROT:{} | {}",
+		m.op_name().to_uppercase(),
+		h.pid,
+		h.tgid,
+		h.comm,
+		prot,
+		m.path.as_deref().unwrap_or(if m.anonymous { "ANON" } else { "?" })
+	)
+}
+
+pub fn render_capable(c: &CapableEvent) -> String {
+	let h = &c.header;
+
+	format!(
+		"[CAPABLE] PID:{} | TGID:{} | CMD:{} | CAP:{}{}{}{}",
+		h.pid,
+		h.tgid,
+		h.comm,
+		c.cap_name(),
+		if c.init_userns { "" } else { " | USERNS" },
+		if c.audit { "" } else { " | NOAUDIT" },
+		if c.denied { " | DENIED" } else { "" }
+	)
+}
+
+pub fn render_capset(c: &CapsetEvent) -> String {
+	let h = &c.header;
+	let gained = c.gained();
+
+	format!(
+		"[CAPSET] PID:{} | TGID:{} | CMD:{} | GAINED:{} | EFF:{:#x}",
+		h.pid,
+		h.tgid,
+		h.comm,
+		if gained.is_empty() {
+			"-".to_string()
+		} else {
+			gained.join(",")
+		},
+		c.effective
+	)
+}
+
+pub fn render_namespace(n: &NamespaceEvent) -> String {
+	let h = &n.header;
+
+	format!(
+		"[{}] PID:{} | TGID:{} | CMD:{} | CHANGED:{} | MNT_NS:{}->{}{}",
+		n.op_name().to_uppercase(),
+		h.pid,
+		h.tgid,
+		h.comm,
+		NamespaceEvent::ns_names(n.changed).join(","),
+		n.old_mnt_ns,
+		h.mnt_ns,
+		if n.joins_host() { " | JOINED_HOST" } else { "" }
+	)
+}
+
+pub fn render_io_uring(u: &IoUringEvent) -> String {
+	let h = &u.header;
+
+	let target = match (&u.path, u.addr) {
+		(Some(path), _) => format!(" | PATH:{path}"),
+		(None, Some(addr)) => format!(" | {}", socket_addr_to_string(addr, u.port)),
+		(None, None) => String::new(),
+	};
+
+	format!(
+		"[IO_URING] PID:{} | TGID:{} | CMD:{} | OP:{} | FD:{}{}{}",
+		h.pid,
+		h.tgid,
+		h.comm,
+		u.op_name(),
+		u.fd,
+		target,
+		if u.sqpoll { " | SQPOLL" } else { "" }
+	)
+}
+
 pub fn render_ptrace(p: &PtraceAccessCheckEvent) -> String {
 	let h = &p.header;
 
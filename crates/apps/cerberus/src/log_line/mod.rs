@@ -57,5 +57,20 @@ pub fn log_engine_event(evt: &EngineEvent) {
 				response.action = %format!("{:?}", r.response_chain),
 			);
 		}
+
+		EngineEvent::Risk(r) => {
+			warn!(
+				event.kind = "risk",
+
+				risk.entity = %r.entity,
+				risk.score = r.score,
+				risk.threshold = r.threshold,
+				risk.rules = %r.contributors.join(","),
+
+				process.uid = r.event_meta.uid,
+				process.pid = r.event_meta.pid,
+				process.comm = %r.event_meta.comm,
+			);
+		}
 	}
 }
@@ -9,6 +9,14 @@ pub fn module_op_to_string(op: u8) -> &'static str {
 	}
 }
 
+pub fn module_source_to_string(source: u8) -> &'static str {
+	match source {
+		MODULE_SOURCE_INIT_MODULE => "INIT_MODULE",
+		MODULE_SOURCE_FINIT_MODULE => "FINIT_MODULE",
+		_ => "UNKNOWN",
+	}
+}
+
 pub fn inode_op_to_string(op: u8) -> &'static str {
 	match op {
 		INODE_OP_UNLINK => "UNLINK",
@@ -43,6 +51,19 @@ pub fn socket_op_to_string(op: u8) -> &'static str {
 	}
 }
 
+pub fn socket_type_to_string(sock_type: u32) -> &'static str {
+	match sock_type {
+		1 => "SOCK_STREAM",
+		2 => "SOCK_DGRAM",
+		3 => "SOCK_RAW",
+		4 => "SOCK_RDM",
+		5 => "SOCK_SEQPACKET",
+		6 => "SOCK_DCCP",
+		10 => "SOCK_PACKET",
+		_ => "UNKNOWN",
+	}
+}
+
 pub fn attach_type_to_string(atype: u32) -> &'static str {
 	match atype {
 		0 => "CGROUP_INET_INGRESS",
@@ -146,8 +167,8 @@ pub fn prog_type_to_string(ptype: u32) -> &'static str {
 	}
 }
 
-pub fn ip_to_string(ip: u32) -> String {
-	std::net::Ipv4Addr::from(ip.to_be()).to_string()
+pub fn socket_addr_to_string(ip: std::net::IpAddr, port: u16) -> String {
+	std::net::SocketAddr::new(ip, port).to_string()
 }
 pub fn family_to_string<T: Into<i32>>(family: T) -> &'static str {
 	let family = family.into();
@@ -13,19 +13,24 @@ mod workers;
 // endregion: --- Modules
 
 use crate::{
-	cli::args::{Cli, RunMode},
+	cli::{
+		args::{Cli, Command, RulesCommand, RunMode},
+		import_sigma::import_sigma,
+	},
 	core::start_tui,
 	event::AppEvent,
 	hook_registry::{
 		HookView,
 		event::HookCommand,
-		helper_fns::{register_kprobe, register_lsm, register_tracepoint, register_xdp},
+		file_watch::populate_file_watch,
+		helper_fns::{register_btf_tracepoint, register_kprobe, register_lsm, register_tracepoint, register_xdp},
+		policy::EventPolicy,
 		registry::HookRegistry,
 	},
 	supervisor::Supervisor,
 	workers::{
-		ContainerResolver, HookWorker, OrthrusWorker, ResponseExecutor, RingBufWorker, RuleEngineWorker,
-		RuleWatchWorker,
+		CapDeny, ContainerResolver, HookWorker, OrthrusWorker, ResponseExecutor, RingBufWorker, RuleEngineWorker,
+		RuleWatchWorker, UnixDenyWorker,
 	},
 };
 
@@ -37,10 +42,14 @@ use aya::{
 };
 use clap::Parser;
 
-use lib_common::event::CerberusEvent;
+use lib_common::event::{CerberusEvent, cap_from_name};
 use lib_container::{container_manager::ContainerManager, runtime::k8s_connect};
+use lib_ebpf_common::UNIX_PATH_LEN;
 use lib_event::unbound::new_channel_unbounded_async;
-use lib_rules::{ResponseRequest, RuleEngine, RuleSet};
+use lib_rules::{
+	DEFAULT_NOVELTY_CAPACITY, NoveltyConfig, NoveltyStore, ResponseRequest, RiskConfig, RuleEngine, RuleSet,
+	SystemClock,
+};
 use std::{
 	path::Path,
 	sync::{Arc, atomic::AtomicUsize},
@@ -88,6 +97,23 @@ async fn main() -> Result<()> {
 		(None, false)
 	};
 
+	let cerberus_dir = dirs::home_dir().ok_or(Error::HomeDirNotFound)?.join(".cerberus");
+	let rule_dir = match args.rules {
+		Some(path) => path,
+		None => cerberus_dir.join("rules"),
+	};
+
+	if let Some(Command::Rules(RulesCommand::ImportSigma {
+		files,
+		out,
+		allow_partial,
+	})) = &args.command
+	{
+		return import_sigma(files, out.as_ref().unwrap_or(&rule_dir), *allow_partial);
+	}
+
+	let iface = args.iface.as_deref().ok_or(Error::MissingIface)?;
+
 	if args.time.is_some() && args.mode != RunMode::Agent {
 		return Err(Error::InvalidTimeMode);
 	}
@@ -103,7 +129,19 @@ async fn main() -> Result<()> {
 		debug!("remove limit on locked memory failed, ret is: {ret}");
 	}
 
-	let mut ebpf = aya::Ebpf::load(aya::include_bytes_aligned!(concat!(env!("OUT_DIR"), "/cerberus")))?;
+	let btf = aya::Btf::from_sys_fs().ok();
+	let agent_tgid = std::process::id();
+	let mut loader = aya::EbpfLoader::new();
+	loader.btf(btf.as_ref());
+	loader.override_global("AGENT_TGID", &agent_tgid, true);
+	if let Some(size) = args.ring_size {
+		let page_size = unsafe { libc::sysconf(libc::_SC_PAGESIZE) } as u32;
+		if !size.is_power_of_two() || size < page_size {
+			return Err(Error::InvalidRingSize(size));
+		}
+		loader.map_max_entries("EVT_MAP", size);
+	}
+	let mut ebpf = loader.load(aya::include_bytes_aligned!(concat!(env!("OUT_DIR"), "/cerberus")))?;
 	// match aya_log::EbpfLogger::init(&mut ebpf) {
 	// 	Err(e) => {
 	// 		// This can happen if you remove all log statements from your eBPF program.
@@ -120,10 +158,6 @@ async fn main() -> Result<()> {
 	// 		});
 	// 	}
 	// }
-	let rule_dir = match args.rules {
-		Some(path) => path,
-		None => dirs::home_dir().ok_or(Error::HomeDirNotFound)?.join(".cerberus").join("rules"),
-	};
 	std::fs::create_dir_all(&rule_dir)?;
 	let ruleset = RuleSet::load_from_dir(&rule_dir)?;
 	let rules: Arc<[Arc<str>]> = ruleset
@@ -136,11 +170,36 @@ async fn main() -> Result<()> {
 	if ruleset.rule_count() == 0 {
 		return Err(Error::NoRulesInDir(rule_dir.display().to_string()));
 	}
-	let rule_engine = Arc::new(RuleEngine::new_from_ruleset(ruleset)?);
+	let novelty = NoveltyStore::open(
+		NoveltyConfig {
+			path: Some(cerberus_dir.join("first_seen.json")),
+			learning_period: args.learning_period.into(),
+			capacity: DEFAULT_NOVELTY_CAPACITY,
+			max_age: None,
+		},
+		&SystemClock,
+	);
+	let rule_engine = Arc::new(
+		RuleEngine::new_from_ruleset(ruleset)?
+			.with_risk_config(RiskConfig {
+				threshold: args.risk_threshold,
+				half_life: args.risk_half_life.into(),
+			})
+			.with_novelty_store(novelty),
+	);
 
 	let mut registry = HookRegistry::default();
 
-	let ringbuf_fd = load_hooks(&mut ebpf, &mut registry, &args.iface)?;
+	populate_file_watch(&mut ebpf, &args.watch_path, &args.watch_read_path)?;
+	let ringbuf_fd = load_hooks(&mut ebpf, &mut registry, iface)?;
+	registry.set_policy(EventPolicy::from_args(
+		&args.only_comm,
+		&args.only_uid,
+		&args.only_cgroup,
+		&args.sample,
+		!args.keep_unruled,
+	)?);
+	registry.apply_policy(&mut ebpf, rule_engine.snapshot().index())?;
 	let prog_count: Arc<AtomicUsize> = Arc::new(AtomicUsize::new(0));
 	registry.set_prog_count(prog_count.clone());
 
@@ -167,15 +226,66 @@ async fn main() -> Result<()> {
 		aya::maps::HashMap::try_from(ebpf.take_map("BLOCKLIST").ok_or(Error::EbpfMapNotFound {
 			map: "BLOCKLIST".into(),
 		})?)?;
+	let blocklist_v6: aya::maps::HashMap<_, [u8; 16], u32> =
+		aya::maps::HashMap::try_from(ebpf.take_map("BLOCKLIST_V6").ok_or(Error::EbpfMapNotFound {
+			map: "BLOCKLIST_V6".into(),
+		})?)?;
 	let lsm_exec_deny: aya::maps::HashMap<_, [u8; 128], u8> =
 		aya::maps::HashMap::try_from(ebpf.take_map("LSM_EXEC_DENY").ok_or(Error::EbpfMapNotFound {
 			map: "LSM_EXEC_DENY".into(),
 		})?)?;
+	let mut cap_deny: aya::maps::HashMap<_, u64, u64> = aya::maps::HashMap::try_from(
+		ebpf.take_map("CAP_DENY")
+			.ok_or(Error::EbpfMapNotFound { map: "CAP_DENY".into() })?,
+	)?;
+	for name in &args.deny_cap {
+		let cap = cap_from_name(name).ok_or_else(|| Error::UnknownCapability(name.clone()))?;
+		cap_deny.insert(cap as u64, 0, 0)?;
+	}
+	let unix_connect_deny: aya::maps::HashMap<_, [u64; 2], u8> =
+		aya::maps::HashMap::try_from(ebpf.take_map("UNIX_CONNECT_DENY").ok_or(Error::EbpfMapNotFound {
+			map: "UNIX_CONNECT_DENY".into(),
+		})?)?;
+	let mut unix_connect_deny_abstract: aya::maps::HashMap<_, [u8; UNIX_PATH_LEN], u8> = aya::maps::HashMap::try_from(
+		ebpf.map_mut("UNIX_CONNECT_DENY_ABSTRACT").ok_or(Error::EbpfMapNotFound {
+			map: "UNIX_CONNECT_DENY_ABSTRACT".into(),
+		})?,
+	)?;
+	for name in args.deny_unix_connect.iter().filter_map(|p| p.strip_prefix('@')) {
+		unix_connect_deny_abstract.insert(unix_abstract_key(name)?, 1, 0)?;
+	}
 	let token = supervisor.token();
-	let response_worker =
-		ResponseExecutor::start(response_rx, blocklist, lsm_exec_deny, app_tx.clone(), token.clone())?;
-	let ringbuf_worker = RingBufWorker::start(ringbuf_fd, ringbuf_tx.clone(), token.clone())?;
-	let hook_worker = HookWorker::start(ebpf, app_tx.clone(), hook_rx, registry, token.clone())?;
+	let unix_deny_worker = UnixDenyWorker::start(
+		unix_connect_deny,
+		args.deny_unix_connect.iter().filter(|p| !p.starts_with('@')).cloned(),
+		token.clone(),
+	)?;
+	let response_worker = ResponseExecutor::start(
+		response_rx,
+		blocklist,
+		blocklist_v6,
+		lsm_exec_deny,
+		CapDeny {
+			map: cap_deny,
+			ttl: args.deny_cap_ttl.into(),
+		},
+		app_tx.clone(),
+		token.clone(),
+	)?;
+	let evt_drops: aya::maps::PerCpuArray<_, u64> =
+		aya::maps::PerCpuArray::try_from(ebpf.take_map("EVT_DROPS").ok_or(Error::EbpfMapNotFound {
+			map: "EVT_DROPS".into(),
+		})?)?;
+	let ringbuf_worker =
+		RingBufWorker::start(ringbuf_fd, evt_drops, ringbuf_tx.clone(), app_tx.clone(), token.clone())?;
+	let hook_worker = HookWorker::start(
+		ebpf,
+		app_tx.clone(),
+		hook_rx,
+		registry,
+		rule_engine.clone(),
+		token.clone(),
+	)?;
 
 	let rule_input_rx = if args.container_resolver {
 		let k8s_client = k8s_connect().await?;
@@ -196,8 +306,13 @@ async fn main() -> Result<()> {
 		rule_input_rx,
 		token.clone(),
 	)?;
-	let rule_watch_worker =
-		RuleWatchWorker::start(app_tx.clone(), rule_engine.clone(), rule_dir.clone(), token.clone())?;
+	let rule_watch_worker = RuleWatchWorker::start(
+		app_tx.clone(),
+		hook_tx.clone(),
+		rule_engine.clone(),
+		rule_dir.clone(),
+		token.clone(),
+	)?;
 	supervisor.spawn(ringbuf_worker.run());
 	supervisor.spawn(hook_worker.run());
 	supervisor.spawn(rule_worker.run(logging_enabled));
@@ -213,6 +328,7 @@ async fn main() -> Result<()> {
 	}
 	supervisor.spawn(response_worker.run());
 	supervisor.spawn(rule_watch_worker.run());
+	supervisor.spawn(unix_deny_worker.run());
 
 	match args.mode {
 		RunMode::Tui => {
@@ -230,24 +346,76 @@ async fn main() -> Result<()> {
 	Ok(())
 }
 
+/// `sun_path` as the kernel sees it; `@name` is the abstract name `\0name`.
+fn unix_abstract_key(name: &str) -> Result<[u8; UNIX_PATH_LEN]> {
+	if name.is_empty() || name.len() >= UNIX_PATH_LEN {
+		return Err(Error::InvalidUnixPath(format!("@{name}")));
+	}
+
+	let mut key = [0u8; UNIX_PATH_LEN];
+	key[1..=name.len()].copy_from_slice(name.as_bytes());
+	Ok(key)
+}
+
 pub fn load_hooks(ebpf: &mut Ebpf, registry: &mut HookRegistry, iface: &str) -> Result<AsyncFd<RingBuf<MapData>>> {
 	let btf = Btf::from_sys_fs()?;
 	register_lsm(ebpf, registry, "sys_enter_kill", "task_kill", &btf)?;
 	register_lsm(ebpf, registry, "socket_connect", "socket_connect", &btf)?;
+	register_lsm(ebpf, registry, "unix_stream_connect", "unix_stream_connect", &btf)?;
 	register_lsm(ebpf, registry, "socket_bind", "socket_bind", &btf)?;
+	register_lsm(ebpf, registry, "socket_create", "socket_create", &btf)?;
+	register_lsm(ebpf, registry, "socket_sendmsg", "socket_sendmsg", &btf)?;
 	register_lsm(ebpf, registry, "inode_unlink", "inode_unlink", &btf)?;
 	register_lsm(ebpf, registry, "inode_mkdir", "inode_mkdir", &btf)?;
 	register_lsm(ebpf, registry, "inode_rmdir", "inode_rmdir", &btf)?;
 	register_lsm(ebpf, registry, "inode_link", "inode_link", &btf)?;
 	register_lsm(ebpf, registry, "inode_symlink", "inode_symlink", &btf)?;
 	register_lsm(ebpf, registry, "inode_rename", "inode_rename", &btf)?;
+	register_lsm(ebpf, registry, "inode_create", "inode_create", &btf)?;
+	register_lsm(ebpf, registry, "inode_setattr", "inode_setattr", &btf)?;
 	register_lsm(ebpf, registry, "bpf_prog_load", "bpf_prog_load", &btf)?;
 	register_lsm(ebpf, registry, "bpf_map", "bpf_map", &btf)?;
 	register_lsm(ebpf, registry, "ptrace_access_check", "ptrace_access_check", &btf)?;
 	register_lsm(ebpf, registry, "bprm_check_security", "bprm_check_security", &btf)?;
+	register_lsm(ebpf, registry, "file_open", "file_open", &btf)?;
+	register_lsm(ebpf, registry, "file_permission", "file_permission", &btf)?;
+	register_lsm(ebpf, registry, "file_free_security", "file_free_security", &btf)?;
+	register_lsm(ebpf, registry, "mmap_file", "mmap_file", &btf)?;
+	register_lsm(ebpf, registry, "file_mprotect", "file_mprotect", &btf)?;
+	register_lsm(ebpf, registry, "kernel_read_file", "kernel_read_file", &btf)?;
+	register_lsm(ebpf, registry, "kernel_load_data", "kernel_load_data", &btf)?;
+	register_lsm(ebpf, registry, "kernel_module_request", "kernel_module_request", &btf)?;
+	register_lsm(ebpf, registry, "capable", "capable", &btf)?;
+	register_lsm(ebpf, registry, "capset", "capset", &btf)?;
+	register_lsm(ebpf, registry, "sb_mount", "sb_mount", &btf)?;
+	register_lsm(ebpf, registry, "move_mount", "move_mount", &btf)?;
 	register_tracepoint(ebpf, registry, "inet_sock_set_state", "sock", "inet_sock_set_state")?;
 	register_tracepoint(ebpf, registry, "sys_enter_ptrace", "syscalls", "sys_enter_ptrace")?;
+	register_tracepoint(ebpf, registry, "sys_exit_execve", "syscalls", "sys_exit_execve")?;
+	register_tracepoint(ebpf, registry, "sys_exit_execveat", "syscalls", "sys_exit_execveat")?;
+	register_tracepoint(ebpf, registry, "sys_enter_setns", "syscalls", "sys_enter_setns")?;
+	register_tracepoint(ebpf, registry, "sys_exit_setns", "syscalls", "sys_exit_setns")?;
+	register_tracepoint(ebpf, registry, "sys_enter_unshare", "syscalls", "sys_enter_unshare")?;
+	register_tracepoint(ebpf, registry, "sys_exit_unshare", "syscalls", "sys_exit_unshare")?;
+	register_tracepoint(
+		ebpf,
+		registry,
+		"sys_enter_delete_module",
+		"syscalls",
+		"sys_enter_delete_module",
+	)?;
+	register_btf_tracepoint(ebpf, registry, "sched_process_fork", "sched_process_fork", &btf)?;
+	register_btf_tracepoint(ebpf, registry, "sched_process_exec", "sched_process_exec", &btf)?;
+	register_btf_tracepoint(ebpf, registry, "sched_process_exit", "sched_process_exit", &btf)?;
+	// io_uring_submit_req is io_uring_submit_sqe before 6.0, and neither exists without io_uring
+	for name in ["io_uring_create", "io_uring_submit_req"] {
+		if let Err(e) = register_btf_tracepoint(ebpf, registry, name, name, &btf) {
+			tracing::warn!(error = %e, "[hooks]: '{name}' not attached, continuing without it");
+		}
+	}
 	register_kprobe(ebpf, registry, "do_init_module", "do_init_module", 0)?;
+	register_kprobe(ebpf, registry, "commit_creds", "commit_creds", 0)?;
+	register_kprobe(ebpf, registry, "inet_csk_accept", "inet_csk_accept", 0)?;
 	register_xdp(ebpf, registry, "xdp_hook", iface)?;
 
 	let ring_buf = RingBuf::try_from(
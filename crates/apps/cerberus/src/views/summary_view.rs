@@ -1,3 +1,4 @@
+use lib_common::event::cap_name;
 use lib_rules::ResolvedAction;
 use ratatui::{
 	buffer::Buffer,
@@ -35,13 +36,24 @@ impl StatefulWidget for SummaryView {
 			])
 			.areas(area);
 
-		let [rules_area, chart1_area] = Layout::default()
+		let [rules_area, chart1_area, risk_area] = Layout::default()
 			.direction(Direction::Horizontal)
-			.constraints([Constraint::Percentage(20), Constraint::Percentage(80)])
+			.constraints([
+				Constraint::Percentage(20),
+				Constraint::Percentage(50),
+				Constraint::Percentage(30),
+			])
 			.areas(top_row);
 
+		let [rules_area, health_area] = Layout::default()
+			.direction(Direction::Vertical)
+			.constraints([Constraint::Min(3), Constraint::Length(4)])
+			.areas(rules_area);
+
 		render_loaded_rules_count(rules_area, buf, state);
+		render_ringbuf_health(health_area, buf, state);
 		render_severity_chart(chart1_area, buf, state);
+		render_risk_scores(risk_area, buf, state);
 
 		let [last_event_area, hooks_area] = Layout::default()
 			.direction(Direction::Horizontal)
@@ -96,6 +108,38 @@ pub fn render_loaded_rules_count(area: Rect, buf: &mut Buffer, state: &AppState)
 	paragraph.render(area, buf);
 }
 
+fn render_ringbuf_health(area: Rect, buf: &mut Buffer, state: &AppState) {
+	let block = Block::bordered().title("Ring Buffer");
+
+	let Some((time, lost)) = state.last_loss() else {
+		Paragraph::new("No events lost")
+			.block(block)
+			.style(Style::default().fg(Color::Green))
+			.render(area, buf);
+		return;
+	};
+
+	let timestamp = time.format(TIME_FORMAT).unwrap_or_else(|_| "--:--:--".into());
+	let last = lost
+		.iter()
+		.map(|(name, n)| format!("{name} {n}"))
+		.collect::<Vec<_>>()
+		.join(", ");
+	let lines = vec![
+		Line::from(vec![
+			Span::styled(
+				format!("{} lost", state.events_lost()),
+				Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+			),
+			Span::raw("  "),
+			Span::styled(timestamp, Style::default().fg(Color::DarkGray)),
+		]),
+		Line::from(Span::styled(last, Style::default().fg(Color::White))),
+	];
+
+	Paragraph::new(lines).block(block).render(area, buf);
+}
+
 fn render_last_event_meta(area: Rect, buf: &mut Buffer, state: &AppState) {
 	let last_meta = state
 		.cerberus_evts_matched()
@@ -134,6 +178,41 @@ fn render_severity_chart(area: Rect, buf: &mut Buffer, state: &AppState) {
 	chart.render(area, buf);
 }
 
+fn render_risk_scores(area: Rect, buf: &mut Buffer, state: &AppState) {
+	let block = Block::bordered().title("Risk Scores");
+
+	let max_items = area.height.saturating_sub(2) as usize;
+
+	let items: Vec<Line> = state
+		.risk_scores()
+		.iter()
+		.take(max_items)
+		.map(|s| {
+			let color = if s.alerted { Color::Red } else { Color::Yellow };
+
+			Line::from(vec![
+				Span::styled(
+					format!("{:>7.1}", s.score),
+					Style::default().fg(color).add_modifier(Modifier::BOLD),
+				),
+				Span::raw("  "),
+				Span::styled(s.entity.to_string(), Style::default().fg(Color::White)),
+				Span::raw("  "),
+				Span::styled(s.contributors.join(","), Style::default().fg(Color::DarkGray)),
+			])
+		})
+		.collect();
+
+	if items.is_empty() {
+		Paragraph::new("No risk yet")
+			.block(block)
+			.style(Style::default().fg(Color::DarkGray))
+			.render(area, buf);
+	} else {
+		Paragraph::new(items).block(block).render(area, buf);
+	}
+}
+
 fn render_response_queue(area: Rect, buf: &mut Buffer, state: &AppState) {
 	let block = Block::bordered().title("Response Queue");
 
@@ -166,6 +245,7 @@ fn render_response_queue(area: Rect, buf: &mut Buffer, state: &AppState) {
 							let path = String::from_utf8_lossy(path_key);
 							format!("deny_exec {}", path.trim_end_matches('\0'))
 						}
+						ResolvedAction::DenyCap { cap, tgid } => format!("deny_cap {} tgid {tgid}", cap_name(*cap)),
 					})
 					.collect::<Vec<_>>()
 					.join(" -> ")
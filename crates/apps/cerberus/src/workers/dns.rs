@@ -0,0 +1,413 @@
+use std::{
+	collections::HashMap,
+	net::{IpAddr, Ipv4Addr, Ipv6Addr},
+	sync::Arc,
+	time::{Duration, Instant},
+};
+
+use lib_common::event::{DnsEvent, EventHeader};
+
+const DNS_HEADER_LEN: usize = 12;
+const DNS_TYPE_A: u16 = 1;
+const DNS_TYPE_AAAA: u16 = 28;
+const MAX_NAME_LEN: usize = 253;
+// bounds compression pointer chains
+const MAX_LABELS: usize = 128;
+
+/// Queries not answered within this window are reported without answers.
+pub const DNS_QUERY_TIMEOUT: Duration = Duration::from_secs(2);
+const MAX_PENDING: usize = 4096;
+
+/// A DNS message as far as detection cares about it. Truncated payloads yield
+/// whatever answers fit in the captured bytes.
+#[derive(Debug)]
+pub struct DnsMessage {
+	pub id: u16,
+	pub response: bool,
+	pub rcode: u8,
+	pub qname: String,
+	pub qtype: u16,
+	pub answers: Vec<IpAddr>,
+}
+
+impl DnsMessage {
+	pub fn parse(buf: &[u8]) -> Option<Self> {
+		if buf.len() < DNS_HEADER_LEN {
+			return None;
+		}
+
+		let id = u16::from_be_bytes([buf[0], buf[1]]);
+		let flags = u16::from_be_bytes([buf[2], buf[3]]);
+		let qdcount = u16::from_be_bytes([buf[4], buf[5]]);
+		let ancount = u16::from_be_bytes([buf[6], buf[7]]);
+		if qdcount == 0 {
+			return None;
+		}
+
+		let (qname, mut off) = read_name(buf, DNS_HEADER_LEN)?;
+		let qtype = read_u16(buf, off)?;
+		off += 4; // qtype + qclass
+
+		// only the first question is reported; resolvers never send more
+		for _ in 1..qdcount {
+			off = skip_name(buf, off)? + 4;
+		}
+
+		let mut answers = Vec::new();
+		for _ in 0..ancount {
+			let Some(next) = skip_name(buf, off) else {
+				break;
+			};
+			let (Some(rtype), Some(rdlen)) = (read_u16(buf, next), read_u16(buf, next + 8)) else {
+				break;
+			};
+			let rdata = next + 10;
+			let Some(data) = buf.get(rdata..rdata + rdlen as usize) else {
+				break;
+			};
+
+			match (rtype, data.len()) {
+				(DNS_TYPE_A, 4) => answers.push(IpAddr::V4(Ipv4Addr::new(data[0], data[1], data[2], data[3]))),
+				(DNS_TYPE_AAAA, 16) => {
+					let mut octets = [0u8; 16];
+					octets.copy_from_slice(data);
+					answers.push(IpAddr::V6(Ipv6Addr::from(octets)));
+				}
+				_ => {}
+			}
+			off = rdata + rdlen as usize;
+		}
+
+		Some(Self {
+			id,
+			response: flags & 0x8000 != 0,
+			rcode: (flags & 0x000f) as u8,
+			qname,
+			qtype,
+			answers,
+		})
+	}
+}
+
+fn read_u16(buf: &[u8], off: usize) -> Option<u16> {
+	Some(u16::from_be_bytes([*buf.get(off)?, *buf.get(off + 1)?]))
+}
+
+/// Decodes a possibly compressed name starting at `off`. Returns the name in
+/// lowercase without the trailing dot, and the offset just past it.
+fn read_name(buf: &[u8], mut off: usize) -> Option<(String, usize)> {
+	let mut name = String::new();
+	let mut end = None;
+
+	for _ in 0..MAX_LABELS {
+		let len = *buf.get(off)? as usize;
+		match len & 0xc0 {
+			0x00 if len == 0 => {
+				return Some((name, end.unwrap_or(off + 1)));
+			}
+			0x00 => {
+				let label = buf.get(off + 1..off + 1 + len)?;
+				if !name.is_empty() {
+					name.push('.');
+				}
+				name.extend(label.iter().map(|b| b.to_ascii_lowercase() as char));
+				if name.len() > MAX_NAME_LEN {
+					return None;
+				}
+				off += 1 + len;
+			}
+			0xc0 => {
+				let ptr = (read_u16(buf, off)? & 0x3fff) as usize;
+				end.get_or_insert(off + 2);
+				off = ptr;
+			}
+			_ => return None,
+		}
+	}
+
+	None
+}
+
+fn skip_name(buf: &[u8], mut off: usize) -> Option<usize> {
+	loop {
+		let len = *buf.get(off)? as usize;
+		match len & 0xc0 {
+			0x00 if len == 0 => return Some(off + 1),
+			0x00 => off += 1 + len,
+			0xc0 => return Some(off + 2),
+			_ => return None,
+		}
+	}
+}
+
+#[derive(Hash, PartialEq, Eq)]
+struct QueryKey {
+	id: u16,
+	qname: Arc<str>,
+	qtype: u16,
+}
+
+struct PendingQuery {
+	header: EventHeader,
+	server: IpAddr,
+	seen: Instant,
+}
+
+/// Pairs queries seen in `socket_sendmsg` with responses seen in XDP. The
+/// response carries no task, so the merged event keeps the query's header.
+///
+/// XDP only sees traffic on the attached interface, never on `lo`: replies
+/// from a local stub resolver such as systemd-resolved on `127.0.0.53` are
+/// not captured, and queries to it expire unanswered. The stub's own upstream
+/// lookup is paired, but attributed to the stub rather than the caller.
+#[derive(Default)]
+pub struct DnsTracker {
+	pending: HashMap<QueryKey, PendingQuery>,
+}
+
+impl DnsTracker {
+	/// Returns an event once a query is complete. Responses without a matching
+	/// query (other hosts, or resolvers outside the XDP interface) are dropped.
+	pub fn observe(&mut self, msg: DnsMessage, header: EventHeader, server: IpAddr, now: Instant) -> Option<DnsEvent> {
+		let key = QueryKey {
+			id: msg.id,
+			qname: Arc::from(msg.qname),
+			qtype: msg.qtype,
+		};
+
+		if msg.response {
+			let query = self.pending.remove(&key)?;
+			return Some(DnsEvent {
+				header: query.header,
+				server: query.server,
+				qname: key.qname,
+				qtype: key.qtype,
+				rcode: Some(msg.rcode),
+				answers: msg.answers,
+			});
+		}
+
+		// under a query flood, report right away rather than grow without bound
+		if self.pending.len() >= MAX_PENDING {
+			return Some(unanswered(key, header, server));
+		}
+
+		self.pending.insert(
+			key,
+			PendingQuery {
+				header,
+				server,
+				seen: now,
+			},
+		);
+		None
+	}
+
+	/// Reports queries that have waited longer than [`DNS_QUERY_TIMEOUT`].
+	pub fn expire(&mut self, now: Instant) -> Vec<DnsEvent> {
+		self.pending
+			.extract_if(|_, q| now.duration_since(q.seen) >= DNS_QUERY_TIMEOUT)
+			.map(|(key, q)| unanswered(key, q.header, q.server))
+			.collect()
+	}
+}
+
+fn unanswered(key: QueryKey, header: EventHeader, server: IpAddr) -> DnsEvent {
+	DnsEvent {
+		header,
+		server,
+		qname: key.qname,
+		qtype: key.qtype,
+		rcode: None,
+		answers: Vec::new(),
+	}
+}
+
+// region:    --- Tests
+
+#[cfg(test)]
+mod tests {
+	type Result<T> = core::result::Result<T, Box<dyn std::error::Error>>;
+
+	use super::*;
+
+	const QR: u16 = 0x8000;
+
+	fn header() -> EventHeader {
+		EventHeader {
+			container: None,
+			comm: Arc::from("curl"),
+			parent_comm: Arc::from("bash"),
+			ts: 0,
+			cgroup_id: 0,
+			mnt_ns: 0,
+			pid: 42,
+			ppid: 1,
+			uid: 1000,
+			tgid: 42,
+		}
+	}
+
+	fn name(name: &str) -> Vec<u8> {
+		let mut out = Vec::new();
+		for label in name.split('.') {
+			out.push(label.len() as u8);
+			out.extend_from_slice(label.as_bytes());
+		}
+		out.push(0);
+		out
+	}
+
+	/// Header and one `qname`/`qtype` question, with `ancount` answers to follow.
+	fn message(id: u16, flags: u16, qname: &str, qtype: u16, ancount: u16) -> Vec<u8> {
+		let mut out = Vec::new();
+		for v in [id, flags, 1, ancount, 0, 0] {
+			out.extend_from_slice(&v.to_be_bytes());
+		}
+		out.extend(name(qname));
+		out.extend_from_slice(&qtype.to_be_bytes());
+		out.extend_from_slice(&1u16.to_be_bytes());
+		out
+	}
+
+	/// An answer whose owner name is a pointer to the question name.
+	fn answer(rtype: u16, rdata: &[u8]) -> Vec<u8> {
+		let mut out = vec![0xc0, DNS_HEADER_LEN as u8];
+		out.extend_from_slice(&rtype.to_be_bytes());
+		out.extend_from_slice(&1u16.to_be_bytes());
+		out.extend_from_slice(&300u32.to_be_bytes());
+		out.extend_from_slice(&(rdata.len() as u16).to_be_bytes());
+		out.extend_from_slice(rdata);
+		out
+	}
+
+	#[test]
+	fn parse_compressed_answers() -> Result<()> {
+		// -- Setup & Fixtures
+		let mut buf = message(7, QR, "Example.COM", DNS_TYPE_A, 2);
+		buf.extend(answer(DNS_TYPE_A, &[93, 184, 216, 34]));
+		buf.extend(answer(DNS_TYPE_AAAA, &Ipv6Addr::LOCALHOST.octets()));
+
+		// -- Exec
+		let msg = DnsMessage::parse(&buf).ok_or("should parse")?;
+
+		// -- Check
+		assert_eq!(msg.id, 7);
+		assert!(msg.response);
+		assert_eq!(msg.qname, "example.com");
+		assert_eq!(msg.qtype, DNS_TYPE_A);
+		assert_eq!(
+			msg.answers,
+			vec![IpAddr::V4(Ipv4Addr::new(93, 184, 216, 34)), IpAddr::V6(Ipv6Addr::LOCALHOST)]
+		);
+
+		Ok(())
+	}
+
+	#[test]
+	fn parse_compressed_question_suffix() -> Result<()> {
+		// -- Setup & Fixtures
+		// second question `www` + pointer to the first one's `example.com`
+		let mut buf = message(1, 0, "example.com", DNS_TYPE_A, 0);
+		buf[5] = 2;
+		buf.extend_from_slice(&[3, b'w', b'w', b'w', 0xc0, DNS_HEADER_LEN as u8]);
+		buf.extend_from_slice(&[0, 1, 0, 1]);
+
+		// -- Exec
+		let (suffix, end) = read_name(&buf, DNS_HEADER_LEN + 17).ok_or("should read")?;
+
+		// -- Check
+		assert_eq!(suffix, "www.example.com");
+		assert_eq!(end, buf.len() - 4);
+		assert!(DnsMessage::parse(&buf).is_some());
+
+		Ok(())
+	}
+
+	#[test]
+	fn parse_pointer_loop_fails() -> Result<()> {
+		// -- Setup & Fixtures
+		let mut buf = message(1, 0, "a", DNS_TYPE_A, 0);
+		// the question name points at itself
+		buf.truncate(DNS_HEADER_LEN);
+		buf.extend_from_slice(&[0xc0, DNS_HEADER_LEN as u8, 0, 1, 0, 1]);
+
+		// -- Exec & Check
+		assert!(read_name(&buf, DNS_HEADER_LEN).is_none());
+		assert!(DnsMessage::parse(&buf).is_none());
+
+		Ok(())
+	}
+
+	#[test]
+	fn parse_truncated_record_keeps_complete_answers() -> Result<()> {
+		// -- Setup & Fixtures
+		let mut buf = message(7, QR, "example.com", DNS_TYPE_A, 2);
+		buf.extend(answer(DNS_TYPE_A, &[10, 0, 0, 1]));
+		let mut cut = answer(DNS_TYPE_A, &[10, 0, 0, 2]);
+		cut.truncate(cut.len() - 2);
+		buf.extend(cut);
+
+		// -- Exec
+		let msg = DnsMessage::parse(&buf).ok_or("should parse")?;
+
+		// -- Check
+		assert_eq!(msg.answers, vec![IpAddr::V4(Ipv4Addr::new(10, 0, 0, 1))]);
+		assert!(DnsMessage::parse(&buf[..DNS_HEADER_LEN - 1]).is_none());
+		assert!(DnsMessage::parse(&buf[..DNS_HEADER_LEN + 4]).is_none());
+
+		Ok(())
+	}
+
+	#[test]
+	fn tracker_pairs_response_with_query() -> Result<()> {
+		// -- Setup & Fixtures
+		let mut tracker = DnsTracker::default();
+		let server = IpAddr::V4(Ipv4Addr::new(1, 1, 1, 1));
+		let now = Instant::now();
+		let query = DnsMessage::parse(&message(9, 0, "example.com", DNS_TYPE_A, 0)).ok_or("query")?;
+		let mut buf = message(9, QR, "example.com", DNS_TYPE_A, 1);
+		buf.extend(answer(DNS_TYPE_A, &[10, 0, 0, 1]));
+		let response = DnsMessage::parse(&buf).ok_or("response")?;
+
+		// -- Exec
+		let pending = tracker.observe(query, header(), server, now);
+		let evt = tracker.observe(response, header(), server, now).ok_or("should pair")?;
+
+		// -- Check
+		assert!(pending.is_none());
+		assert_eq!(evt.header.tgid, 42);
+		assert_eq!(evt.server, server);
+		assert_eq!(evt.rcode, Some(0));
+		assert_eq!(evt.answers, vec![IpAddr::V4(Ipv4Addr::new(10, 0, 0, 1))]);
+		assert!(tracker.pending.is_empty());
+
+		Ok(())
+	}
+
+	#[test]
+	fn tracker_expires_unanswered_queries() -> Result<()> {
+		// -- Setup & Fixtures
+		let mut tracker = DnsTracker::default();
+		let server = IpAddr::V4(Ipv4Addr::new(127, 0, 0, 53));
+		let now = Instant::now();
+		let query = DnsMessage::parse(&message(3, 0, "example.com", DNS_TYPE_AAAA, 0)).ok_or("query")?;
+		tracker.observe(query, header(), server, now);
+
+		// -- Exec
+		let early = tracker.expire(now + DNS_QUERY_TIMEOUT / 2);
+		let expired = tracker.expire(now + DNS_QUERY_TIMEOUT);
+
+		// -- Check
+		assert!(early.is_empty());
+		assert_eq!(expired.len(), 1);
+		assert_eq!(&*expired[0].qname, "example.com");
+		assert_eq!(expired[0].rcode, None);
+		assert!(expired[0].answers.is_empty());
+		assert!(tracker.pending.is_empty());
+
+		Ok(())
+	}
+}
+
+// endregion: --- Tests
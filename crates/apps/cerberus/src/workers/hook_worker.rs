@@ -4,9 +4,12 @@ use crate::{
 	hook_registry::{event::HookCommand, registry::HookRegistry},
 };
 
+use std::sync::Arc;
+
 use aya::Ebpf;
 
 use lib_event::unbound::{Rx, Tx};
+use lib_rules::RuleEngine;
 use tokio_util::sync::CancellationToken;
 
 pub struct HookWorker {
@@ -14,6 +17,7 @@ pub struct HookWorker {
 	rx: Rx<HookCommand>,
 	registry: HookRegistry,
 	ebpf: Ebpf,
+	rule_engine: Arc<RuleEngine>,
 	token: CancellationToken,
 }
 
@@ -23,6 +27,7 @@ impl HookWorker {
 		tx: Tx<AppEvent>,
 		rx: Rx<HookCommand>,
 		registry: HookRegistry,
+		rule_engine: Arc<RuleEngine>,
 		token: CancellationToken,
 	) -> Result<Self> {
 		Ok(HookWorker {
@@ -30,6 +35,7 @@ impl HookWorker {
 			tx,
 			rx,
 			registry,
+			rule_engine,
 			token,
 		})
 	}
@@ -72,6 +78,12 @@ impl HookWorker {
 										}
 									}
 								},
+								HookCommand::ApplyPolicy => {
+									let snapshot = self.rule_engine.snapshot();
+									if let Err(e) = self.registry.apply_policy(&mut self.ebpf, snapshot.index()) {
+										tracing::error!("Event policy update failed: {e}");
+									}
+								}
 							};
 						}
 						Err(_) => {
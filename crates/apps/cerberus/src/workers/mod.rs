@@ -1,15 +1,19 @@
 mod container_resolver;
+mod dns;
 mod hook_worker;
 mod orthrus_worker;
 mod response_executor;
 mod ringbuf;
 mod rule_engine;
 mod rule_watcher;
+mod unix_deny;
 
 pub use container_resolver::*;
+pub use dns::*;
 pub use hook_worker::*;
 pub use orthrus_worker::*;
 pub use response_executor::*;
 pub use ringbuf::*;
 pub use rule_engine::*;
 pub use rule_watcher::*;
+pub use unix_deny::*;
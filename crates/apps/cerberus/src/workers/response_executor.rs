@@ -1,4 +1,4 @@
-use std::sync::Arc;
+use std::{net::IpAddr, sync::Arc, time::Duration};
 
 use crate::error::Result;
 use crate::event::AppEvent;
@@ -13,10 +13,18 @@ use lib_rules::resolve_action;
 use time::OffsetDateTime;
 use tokio_util::sync::CancellationToken;
 
+/// `CAP_DENY` and how long `deny_cap` responses last.
+pub struct CapDeny {
+	pub map: AyaHashMap<MapData, u64, u64>,
+	pub ttl: Duration,
+}
+
 pub struct ResponseExecutor {
 	req_rx: Rx<ResponseRequest>,
 	ip_blocklist: AyaHashMap<MapData, u32, u32>,
+	ip6_blocklist: AyaHashMap<MapData, [u8; 16], u32>,
 	lsm_exec_deny: AyaHashMap<MapData, [u8; 128], u8>,
+	cap_deny: CapDeny,
 	app_tx: Tx<AppEvent>,
 	token: CancellationToken,
 }
@@ -25,7 +33,9 @@ impl ResponseExecutor {
 	pub fn start(
 		req_rx: Rx<ResponseRequest>,
 		ip_blocklist: AyaHashMap<MapData, u32, u32>,
+		ip6_blocklist: AyaHashMap<MapData, [u8; 16], u32>,
 		lsm_exec_deny: AyaHashMap<MapData, [u8; 128], u8>,
+		cap_deny: CapDeny,
 		app_tx: Tx<AppEvent>,
 		token: CancellationToken,
 	) -> Result<Self> {
@@ -33,7 +43,9 @@ impl ResponseExecutor {
 			req_rx,
 			app_tx,
 			ip_blocklist,
+			ip6_blocklist,
 			lsm_exec_deny,
+			cap_deny,
 			token,
 		})
 	}
@@ -54,8 +66,10 @@ impl ResponseExecutor {
 								.response_chain
 								.actions
 								.iter()
-								.map(|a| resolve_action(a, &request.fields).map_err(Into::into))
-								.collect();
+								.try_fold(Vec::new(), |mut acc, a| {
+									acc.extend(resolve_action(a, &request.fields)?);
+									Ok(acc)
+								});
 
 							let (actions, success) = match resolved {
 								Ok(actions) => {
@@ -96,10 +110,14 @@ impl ResponseExecutor {
 
 	fn execute_action(&mut self, action: &ResolvedAction) -> Result<()> {
 		match action {
-			ResolvedAction::BlockIp { ip } => {
+			ResolvedAction::BlockIp { ip: IpAddr::V4(ip) } => {
 				self.ip_blocklist.insert(ip.to_bits(), 1, 0)?;
 			}
 
+			ResolvedAction::BlockIp { ip: IpAddr::V6(ip) } => {
+				self.ip6_blocklist.insert(ip.octets(), 1, 0)?;
+			}
+
 			ResolvedAction::KillProcess { pid } => {
 				Self::kill_process(*pid)?;
 			}
@@ -107,6 +125,12 @@ impl ResponseExecutor {
 			ResolvedAction::DenyExec { path_key } => {
 				self.lsm_exec_deny.insert(path_key, 1, 0)?;
 			}
+
+			ResolvedAction::DenyCap { cap, tgid } => {
+				// the kernel drops the entry once it expires
+				let expiry = monotonic_ns() + self.cap_deny.ttl.as_nanos() as u64;
+				self.cap_deny.map.insert((*tgid as u64) << 32 | *cap as u64, expiry, 0)?;
+			}
 		}
 
 		Ok(())
@@ -123,11 +147,23 @@ impl ResponseExecutor {
 	}
 
 	#[allow(unused)]
-	fn is_blocked(&self, ip: u32) -> Result<bool> {
-		match self.ip_blocklist.get(&ip, 0) {
+	fn is_blocked(&self, ip: IpAddr) -> Result<bool> {
+		let value = match ip {
+			IpAddr::V4(ip) => self.ip_blocklist.get(&ip.to_bits(), 0),
+			IpAddr::V6(ip) => self.ip6_blocklist.get(&ip.octets(), 0),
+		};
+
+		match value {
 			Ok(value) => Ok(value == 1),
 			Err(aya::maps::MapError::KeyNotFound) => Ok(false),
 			Err(e) => Err(e.into()),
 		}
 	}
 }
+
+/// `CLOCK_MONOTONIC`, the clock behind `bpf_ktime_get_ns`.
+fn monotonic_ns() -> u64 {
+	let mut ts = libc::timespec { tv_sec: 0, tv_nsec: 0 };
+	unsafe { libc::clock_gettime(libc::CLOCK_MONOTONIC, &mut ts) };
+	ts.tv_sec as u64 * 1_000_000_000 + ts.tv_nsec as u64
+}
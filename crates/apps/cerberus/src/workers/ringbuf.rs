@@ -1,39 +1,106 @@
-use std::sync::Arc;
+use std::{
+	net::IpAddr,
+	os::unix::fs::MetadataExt,
+	sync::Arc,
+	time::{Duration, Instant},
+};
 
-use crate::error::{Error, Result};
+use crate::{
+	error::{Error, Result},
+	event::AppEvent,
+	workers::{DnsMessage, DnsTracker},
+};
 
-use aya::maps::{MapData, RingBuf};
+use aya::maps::{MapData, PerCpuArray, RingBuf};
 use lib_common::event::{
-	BpfMapEvent, BpfProgLoadEvent, BprmSecurityEvent, CerberusEvent, EventHeader, InetSockEvent, InodeEvent,
-	InodeMutationEvent, ModuleEvent, PtraceAccessCheckEvent, RingBufEvent,
+	BpfMapEvent, BpfProgLoadEvent, BprmSecurityEvent, CapableEvent, CapsetEvent, CerberusEvent, CredChangeEvent,
+	EventHeader, FileEvent, InetSockEvent, InodeAttrEvent, InodeEvent, InodeMutationEvent, IoUringEvent, MemProtEvent,
+	ModuleEvent, MountEvent, NamespaceEvent, ProcessExecEvent, ProcessExitEvent, ProcessForkEvent,
+	PtraceAccessCheckEvent, RingBufEvent, UnixSocketEvent,
 };
 use lib_ebpf_common::{
-	EVT_BPF_MAP, EVT_BPF_PROG_LOAD, EVT_BPRM_CHECK_SEC, EVT_COMMIT_CREDS, EVT_ENTER_PTRACE, EVT_INET_SOCK_SET_STATE,
-	EVT_INODE, EVT_INODE_MUTATE, EVT_IO_URING, EVT_KILL, EVT_MODULE, EVT_PTRACE_ACCESS_CHECK, EVT_SOCKET, EbpfEvent,
-	FILE_PATH_LEN,
+	AF_INET6, ATTR_ATIME_SET, ATTR_GID, ATTR_MODE, ATTR_MTIME_SET, ATTR_UID, CAP_OP_CAPSET, CAP_OPT_NOAUDIT,
+	DNS_DIR_QUERY, DNS_PAYLOAD_LEN, EVENT_WIRE_VERSION, EVT_BPF_MAP, EVT_BPF_PROG_LOAD, EVT_BPRM_CHECK_SEC, EVT_CAP,
+	EVT_COMMIT_CREDS, EVT_DNS, EVT_ENTER_PTRACE, EVT_FILE, EVT_INET_ACCEPT, EVT_INET_SOCK_SET_STATE, EVT_INODE,
+	EVT_INODE_ATTR, EVT_INODE_MUTATE, EVT_IO_URING, EVT_KILL, EVT_MEM_PROT, EVT_MODULE, EVT_MOUNT, EVT_NS,
+	EVT_PROCESS_EXEC, EVT_PROCESS_EXIT, EVT_PROCESS_FORK, EVT_PTRACE_ACCESS_CHECK, EVT_SOCKET, EVT_SOCKET_CREATE,
+	EVT_TYPE_COUNT, EVT_UDP_SEND, EVT_UNIX_SOCKET, EXEC_ARGS_LEN, EXEC_ENV_LEN, EbpfEvent, FILE_PATH_LEN,
+	INODE_MUTATION_SYMLINK, IORING_SETUP_SQPOLL, MEM_OP_MPROTECT, UNIX_PATH_LEN, VarSections,
 };
 use lib_event::unbound::Tx;
+use time::OffsetDateTime;
 use tokio::io::unix::AsyncFd;
 
 use tokio_util::sync::CancellationToken;
 use zerocopy::FromBytes;
 
+const DNS_EXPIRE_INTERVAL: Duration = Duration::from_secs(1);
+const DROP_CHECK_INTERVAL: Duration = Duration::from_secs(5);
+
+const EXEC_ARGS_MAX: usize = 64;
+
 pub struct RingBufWorker {
 	ringbuf_fd: AsyncFd<RingBuf<MapData>>,
+	drops: PerCpuArray<MapData, u64>,
+	// per event type, summed over CPUs at the last check
+	drops_seen: [u64; EVT_TYPE_COUNT as usize],
 	tx: Tx<CerberusEvent>,
+	app_tx: Tx<AppEvent>,
 	token: CancellationToken,
+	dns: DnsTracker,
+	host_mnt_ns: u32,
 }
 
 impl RingBufWorker {
 	pub fn start(
 		ringbuf_fd: AsyncFd<RingBuf<MapData>>,
+		drops: PerCpuArray<MapData, u64>,
 		tx: Tx<CerberusEvent>,
+		app_tx: Tx<AppEvent>,
 		token: CancellationToken,
 	) -> Result<Self> {
-		Ok(RingBufWorker { ringbuf_fd, tx, token })
+		Ok(RingBufWorker {
+			ringbuf_fd,
+			drops,
+			drops_seen: [0; EVT_TYPE_COUNT as usize],
+			tx,
+			app_tx,
+			token,
+			dns: DnsTracker::default(),
+			host_mnt_ns: host_mnt_ns(),
+		})
+	}
+
+	/// Reports events the kernel dropped on a full ring buffer since the last check.
+	fn check_drops(&mut self) {
+		let mut lost = Vec::new();
+		for (event_type, seen) in self.drops_seen.iter_mut().enumerate() {
+			let Ok(counts) = self.drops.get(&(event_type as u32), 0) else {
+				continue;
+			};
+			let total: u64 = counts.iter().sum();
+			if total > *seen {
+				lost.push((event_name(event_type as u8), total - *seen));
+				*seen = total;
+			}
+		}
+		if lost.is_empty() {
+			return;
+		}
+
+		let evt = AppEvent::EventLoss {
+			lost: lost.into(),
+			time: OffsetDateTime::now_utc(),
+		};
+		if let Err(e) = self.app_tx.send(evt) {
+			tracing::error!("RingBufWorker send failed: {e}");
+		}
 	}
 
 	pub async fn run(mut self) -> Result<()> {
+		let mut dns_expire = tokio::time::interval(DNS_EXPIRE_INTERVAL);
+		let mut drop_check = tokio::time::interval(DROP_CHECK_INTERVAL);
+
 		loop {
 			tokio::select! {
 			  biased;
@@ -41,20 +108,35 @@ impl RingBufWorker {
 				  tracing::info!("[RingBufWorker]: shutting down");
 				  break;
 			  }
+			  _ = drop_check.tick() => self.check_drops(),
+			  _ = dns_expire.tick() => {
+				  for evt in self.dns.expire(Instant::now()) {
+					  if let Err(e) = self.tx.send(CerberusEvent::Dns(evt)) {
+						  tracing::error!("RingBufWorker send failed: {e}");
+					  }
+				  }
+			  }
 			  res = self.ringbuf_fd.readable_mut() => {
 				  match res {
 					  Ok(mut guard) => {
 						  let ring_buf = guard.get_inner_mut();
 						  while let Some(item) = ring_buf.next() {
 							  let data = item.as_ref();
-							  match parse_event_from_bytes(data) {
-								  Ok(evt) => {
-									  let cerberus_evt = parse_cerberus_event(evt)?;
+							  let parsed = parse_event_from_bytes(data)
+								  .and_then(|evt| parse_cerberus_event(evt, &mut self.dns, self.host_mnt_ns));
+							  match parsed {
+								  Ok(Some(cerberus_evt)) => {
 									  if let Err(e) = self.tx.send(cerberus_evt) {
 										  tracing::error!("RingBufWorker send failed: {e}");
 									  }
 								  }
-								  Err(_) => continue,
+								  Ok(None) => continue,
+								  Err(e @ Error::EventVersionMismatch { .. }) => {
+									  tracing::error!("[RingBufWorker]: {e}");
+									  return Err(e);
+								  }
+								  // one malformed record must not stop intake
+								  Err(e) => tracing::debug!("[RingBufWorker]: skipping event: {e}"),
 							  }
 						  }
 						  guard.clear_ready();
@@ -76,6 +158,7 @@ pub const fn event_name(event_type: u8) -> &'static str {
 		EVT_KILL => "KILL",
 		EVT_IO_URING => "IO_URING",
 		EVT_SOCKET => "SOCKET",
+		EVT_UNIX_SOCKET => "UNIX_SOCKET",
 		EVT_COMMIT_CREDS => "COMMIT_CREDS",
 		EVT_MODULE => "MODULE",
 		EVT_INET_SOCK_SET_STATE => "INET_SOCK_SET_STATE",
@@ -85,12 +168,32 @@ pub const fn event_name(event_type: u8) -> &'static str {
 		EVT_INODE => "INODE",
 		EVT_BPF_MAP => "BPF_MAP",
 		EVT_INODE_MUTATE => "INODE_MUTATE",
+		EVT_INODE_ATTR => "INODE_ATTR",
 		EVT_PTRACE_ACCESS_CHECK => "PTRACE_ACCESS_CHECK",
+		EVT_SOCKET_CREATE => "SOCKET_CREATE",
+		EVT_UDP_SEND => "UDP_SEND",
+		EVT_INET_ACCEPT => "INET_ACCEPT",
+		EVT_DNS => "DNS",
+		EVT_PROCESS_FORK => "FORK",
+		EVT_PROCESS_EXEC => "EXEC_COMMIT",
+		EVT_PROCESS_EXIT => "EXIT",
+		EVT_MEM_PROT => "MEM_PROT",
+		EVT_FILE => "FILE",
+		EVT_CAP => "CAP",
+		EVT_MOUNT => "MOUNT",
+		EVT_NS => "NS",
 		_ => "UNKNOWN",
 	}
 }
 
-fn parse_cerberus_event(evt: EbpfEvent) -> Result<CerberusEvent> {
+/// Mount namespace of PID 1, which is the host's as long as the agent shares
+/// its PID namespace. 0 if it can't be read.
+fn host_mnt_ns() -> u32 {
+	std::fs::metadata("/proc/1/ns/mnt").map(|m| m.ino() as u32).unwrap_or(0)
+}
+
+/// `None` for DNS messages still waiting on their counterpart.
+fn parse_cerberus_event(evt: EbpfEvent<'_>, dns: &mut DnsTracker, host_mnt_ns: u32) -> Result<Option<CerberusEvent>> {
 	let cerberus_evt = match evt {
 		EbpfEvent::Generic(ref e) => CerberusEvent::Generic(RingBufEvent {
 			name: event_name(e.header.event_type),
@@ -109,17 +212,29 @@ fn parse_cerberus_event(evt: EbpfEvent) -> Result<CerberusEvent> {
 			header: build_header(&e.header),
 		}),
 
-		EbpfEvent::InodeMutation(ref e) => CerberusEvent::InodeMutation(InodeMutationEvent {
-			new_filename: arc_from_bytes(&e.new_filename),
-			new_filename_len: e.new_filename_len,
-			old_filename: arc_from_bytes(&e.old_filename),
-			old_filename_len: e.old_filename_len,
-			mutation: e.mutation,
-			header: build_header(&e.header),
-		}),
+		EbpfEvent::InodeMutation(ref e, sections) => {
+			let [old_filename, new_filename, old_path, new_path] = take_sections(sections)?;
+
+			CerberusEvent::InodeMutation(InodeMutationEvent {
+				new_filename: arc_from_bytes(new_filename),
+				new_filename_len: new_filename.len() as u32,
+				old_filename: arc_from_bytes(old_filename),
+				old_filename_len: old_filename.len() as u32,
+				mutation: e.mutation,
+				old_path: if e.mutation == INODE_MUTATION_SYMLINK {
+					(!old_path.is_empty()).then(|| arc_from_bytes(old_path))
+				} else {
+					path_from_dentry_names(old_path)
+				},
+				new_path: path_from_dentry_names(new_path),
+				header: build_header(&e.header),
+			})
+		}
 		EbpfEvent::Module(ref e) => CerberusEvent::Module(ModuleEvent {
 			module_name: arc_from_bytes(&e.module_name),
 			op: e.op,
+			source: e.source,
+			path: (e.path[0] != 0).then(|| arc_from_bytes(&e.path)),
 			header: build_header(&e.header),
 		}),
 
@@ -129,24 +244,151 @@ fn parse_cerberus_event(evt: EbpfEvent) -> Result<CerberusEvent> {
 			map_name: arc_from_bytes(&e.map_name),
 			header: build_header(&e.header),
 		}),
-		EbpfEvent::Inode(ref e) => CerberusEvent::Inode(InodeEvent {
+		EbpfEvent::Inode(ref e, sections) => {
+			let [filename, path] = take_sections(sections)?;
+
+			CerberusEvent::Inode(InodeEvent {
+				filename: arc_from_bytes(filename),
+				filename_len: filename.len() as u32,
+				op: e.op,
+				path: path_from_dentry_names(path),
+				header: build_header(&e.header),
+			})
+		}
+
+		EbpfEvent::InodeAttr(ref e) => CerberusEvent::InodeAttr(InodeAttrEvent {
 			filename: arc_from_bytes(&e.filename),
-			filename_len: e.filename_len,
 			op: e.op,
+			valid: e.valid,
+			mode: (e.valid & ATTR_MODE != 0).then_some(e.mode),
+			uid: (e.valid & ATTR_UID != 0).then_some(e.uid),
+			gid: (e.valid & ATTR_GID != 0).then_some(e.gid),
+			atime: (e.valid & ATTR_ATIME_SET != 0).then_some(e.atime),
+			mtime: (e.valid & ATTR_MTIME_SET != 0).then_some(e.mtime),
+			path: path_from_dentry_names(&e.path),
 			header: build_header(&e.header),
 		}),
 
-		EbpfEvent::BprmSecurityCheck(ref e) => {
-			let path_len = e.path_len as usize;
-			let filepath_bytes = &e.filepath[..path_len.min(FILE_PATH_LEN)];
+		EbpfEvent::BprmSecurityCheck(ref e, sections) => {
+			let [filepath] = take_sections(sections)?;
 
 			CerberusEvent::Bprm(BprmSecurityEvent {
-				filepath: arc_from_bytes(filepath_bytes),
+				filepath: arc_from_bytes(filepath),
+				header: build_header(&e.header),
+				path_len: filepath.len() as u32,
+			})
+		}
+
+		EbpfEvent::ProcessFork(ref e) => CerberusEvent::ProcessFork(ProcessForkEvent {
+			child_pid: e.child_pid,
+			child_tgid: e.child_tgid,
+			header: build_header(&e.header),
+		}),
+
+		EbpfEvent::ProcessExec(ref e) => {
+			let filename_len = (e.filename_len as usize).min(FILE_PATH_LEN);
+			let args = split_nul(&e.args[..(e.args_len as usize).min(EXEC_ARGS_LEN)])
+				.take(EXEC_ARGS_MAX)
+				.collect::<Vec<_>>();
+			// the kernel only ships the loader variables
+			let env = split_nul(&e.env[..(e.env_len as usize).min(EXEC_ENV_LEN)]).collect();
+
+			CerberusEvent::ProcessExec(ProcessExecEvent {
+				filename: arc_from_bytes(&e.filename[..filename_len]),
+				cmdline: Arc::from(args.join(" ")),
+				args,
+				env,
+				env_truncated: e.env_truncated != 0,
+				old_pid: e.old_pid,
+				success: e.success != 0,
+				errno: -e.ret,
 				header: build_header(&e.header),
-				path_len: e.path_len,
 			})
 		}
 
+		// exit_code is a wait(2) status
+		EbpfEvent::ProcessExit(ref e) => CerberusEvent::ProcessExit(ProcessExitEvent {
+			exit_code: ((e.exit_code >> 8) & 0xff) as u8,
+			signal: (e.exit_code & 0x7f) as u8,
+			header: build_header(&e.header),
+		}),
+
+		EbpfEvent::CredChange(ref e) => CerberusEvent::CredChange(CredChangeEvent {
+			old_uid: e.old_uid,
+			new_uid: e.new_uid,
+			old_euid: e.old_euid,
+			new_euid: e.new_euid,
+			old_gid: e.old_gid,
+			new_gid: e.new_gid,
+			old_egid: e.old_egid,
+			new_egid: e.new_egid,
+			old_cap_effective: e.old_cap_effective,
+			new_cap_effective: e.new_cap_effective,
+			header: build_header(&e.header),
+		}),
+		EbpfEvent::MemProt(ref e) => CerberusEvent::MemProt(MemProtEvent {
+			op: e.op,
+			prot: e.prot,
+			old_prot: (e.op == MEM_OP_MPROTECT).then_some(e.old_prot),
+			flags: e.flags,
+			anonymous: e.anonymous != 0,
+			path: (e.path[0] != 0).then(|| arc_from_bytes(&e.path)),
+			header: build_header(&e.header),
+		}),
+		EbpfEvent::File(ref e) => CerberusEvent::File(FileEvent {
+			op: e.op,
+			access: e.access,
+			flags: e.flags,
+			path: arc_from_bytes(&e.path),
+			header: build_header(&e.header),
+		}),
+		EbpfEvent::Cap(ref e) if e.op == CAP_OP_CAPSET => CerberusEvent::Capset(CapsetEvent {
+			effective: e.effective,
+			permitted: e.permitted,
+			old_effective: e.old_effective,
+			old_permitted: e.old_permitted,
+			init_userns: e.init_userns != 0,
+			header: build_header(&e.header),
+		}),
+		EbpfEvent::Cap(ref e) => CerberusEvent::Capable(CapableEvent {
+			cap: e.cap,
+			audit: e.opts & CAP_OPT_NOAUDIT == 0,
+			denied: e.denied != 0,
+			init_userns: e.init_userns != 0,
+			header: build_header(&e.header),
+		}),
+		EbpfEvent::Mount(ref e) => CerberusEvent::Mount(MountEvent {
+			op: e.op,
+			flags: e.flags,
+			source: match e.source_names {
+				0 => (e.source[0] != 0).then(|| arc_from_bytes(&e.source)),
+				_ => path_from_dentry_names(&e.source),
+			},
+			target: path_from_dentry_names(&e.target),
+			fstype: (e.fstype[0] != 0).then(|| arc_from_bytes(&e.fstype)),
+			host_ns: host_mnt_ns != 0 && e.header.mnt_ns == host_mnt_ns,
+			header: build_header(&e.header),
+		}),
+		EbpfEvent::Ns(ref e) => CerberusEvent::Namespace(NamespaceEvent {
+			op: e.op,
+			flags: e.flags,
+			changed: e.changed,
+			old_mnt_ns: e.old_mnt_ns,
+			fd: e.fd,
+			host_mnt_ns,
+			header: build_header(&e.header),
+		}),
+		EbpfEvent::IoUring(ref e) => CerberusEvent::IoUring(IoUringEvent {
+			opcode: e.opcode,
+			setup: e.setup != 0,
+			fd: e.fd,
+			path: (e.path[0] != 0).then(|| arc_from_bytes(&e.path)),
+			addr: (e.family != 0).then(|| ip_from_bytes(e.family, &e.addr)),
+			port: u16::from_be(e.port),
+			sqpoll: e.ring_flags & IORING_SETUP_SQPOLL != 0,
+			header: build_header(&e.header),
+		}),
+
 		EbpfEvent::BpfProgLoad(ref e) => CerberusEvent::BpfProgLoad(BpfProgLoadEvent {
 			flags: e.flags,
 			attach_type: e.attach_type,
@@ -161,20 +403,86 @@ fn parse_cerberus_event(evt: EbpfEvent) -> Result<CerberusEvent> {
 			sport: e.sport,
 			dport: e.dport,
 			protocol: Arc::from(protocol_to_str(e.protocol)),
-			saddr: e.saddr,
-			daddr: e.daddr,
+			saddr: ip_from_bytes(e.family, &e.saddr),
+			daddr: ip_from_bytes(e.family, &e.daddr),
 			header: build_header(&e.header),
 		}),
 		EbpfEvent::Socket(ref e) => CerberusEvent::Socket(lib_common::event::SocketEvent {
-			addr: e.addr,
+			addr: ip_from_bytes(e.family, &e.addr),
 			port: e.port,
 			family: e.family,
 			op: e.op,
 			header: build_header(&e.header),
 		}),
+		EbpfEvent::UnixSocket(ref e) => {
+			let path = &e.path[..(e.path_len as usize).min(UNIX_PATH_LEN)];
+			let path = match e.abstract_ns {
+				0 => arc_from_bytes(path),
+				_ => Arc::from(format!(
+					"@{}",
+					String::from_utf8_lossy(path.get(1..).unwrap_or_default())
+				)),
+			};
+
+			CerberusEvent::UnixSocket(UnixSocketEvent {
+				op: e.op,
+				path,
+				abstract_ns: e.abstract_ns != 0,
+				denied: e.denied != 0,
+				header: build_header(&e.header),
+			})
+		}
+		EbpfEvent::SocketCreate(ref e) => CerberusEvent::SocketCreate(lib_common::event::SocketCreateEvent {
+			family: e.family,
+			sock_type: e.sock_type,
+			protocol: e.protocol,
+			header: build_header(&e.header),
+		}),
+		EbpfEvent::UdpSend(ref e) => CerberusEvent::UdpSend(lib_common::event::UdpSendEvent {
+			saddr: ip_from_bytes(e.family, &e.saddr),
+			daddr: ip_from_bytes(e.family, &e.daddr),
+			sport: e.sport,
+			dport: e.dport,
+			header: build_header(&e.header),
+		}),
+		EbpfEvent::InetAccept(ref e) => CerberusEvent::InetAccept(lib_common::event::InetAcceptEvent {
+			protocol: Arc::from(protocol_to_str(e.protocol)),
+			saddr: ip_from_bytes(e.family, &e.saddr),
+			daddr: ip_from_bytes(e.family, &e.daddr),
+			sport: e.sport,
+			dport: e.dport,
+			header: build_header(&e.header),
+		}),
+		EbpfEvent::Dns(ref e) => {
+			let payload = &e.payload[..(e.payload_len as usize).min(DNS_PAYLOAD_LEN)];
+			let Some(msg) = DnsMessage::parse(payload) else {
+				return Ok(None);
+			};
+			// the resolver is the destination of a query and the source of a response
+			let server = if e.direction == DNS_DIR_QUERY {
+				ip_from_bytes(e.family, &e.daddr)
+			} else {
+				ip_from_bytes(e.family, &e.saddr)
+			};
+
+			match dns.observe(msg, build_header(&e.header), server, Instant::now()) {
+				Some(evt) => CerberusEvent::Dns(evt),
+				None => return Ok(None),
+			}
+		}
 	};
 
-	Ok(cerberus_evt)
+	Ok(Some(cerberus_evt))
+}
+
+/// v4 addresses sit in the first 4 bytes. v4-mapped v6 addresses from dual-stack
+/// sockets are reported as v4 so rules only need one form.
+fn ip_from_bytes(family: u16, addr: &[u8; 16]) -> IpAddr {
+	if family == AF_INET6 {
+		IpAddr::from(*addr).to_canonical()
+	} else {
+		IpAddr::from([addr[0], addr[1], addr[2], addr[3]])
+	}
 }
 
 fn build_header(h: &lib_ebpf_common::EventHeader) -> EventHeader {
@@ -192,13 +500,19 @@ fn build_header(h: &lib_ebpf_common::EventHeader) -> EventHeader {
 	}
 }
 
-fn parse_event_from_bytes(data: &[u8]) -> Result<EbpfEvent> {
+fn parse_event_from_bytes(data: &[u8]) -> Result<EbpfEvent<'_>> {
 	let header = lib_ebpf_common::EventHeader::ref_from_prefix(data)
 		.map_err(|_| Error::InvalidEventSize)?
 		.0;
+	if header.version != EVENT_WIRE_VERSION {
+		return Err(Error::EventVersionMismatch {
+			expected: EVENT_WIRE_VERSION,
+			found: header.version,
+		});
+	}
 
 	match header.event_type {
-		EVT_KILL | EVT_COMMIT_CREDS => {
+		EVT_KILL => {
 			let evt = lib_ebpf_common::GenericEvent::ref_from_prefix(data)
 				.map_err(|_| Error::InvalidEventSize)?
 				.0;
@@ -211,6 +525,40 @@ fn parse_event_from_bytes(data: &[u8]) -> Result<EbpfEvent> {
 				.0;
 			Ok(EbpfEvent::Socket(*evt))
 		}
+		EVT_UNIX_SOCKET => {
+			let evt = lib_ebpf_common::UnixSocketEvent::ref_from_prefix(data)
+				.map_err(|_| Error::InvalidEventSize)?
+				.0;
+			Ok(EbpfEvent::UnixSocket(*evt))
+		}
+
+		EVT_SOCKET_CREATE => {
+			let evt = lib_ebpf_common::SocketCreateEvent::ref_from_prefix(data)
+				.map_err(|_| Error::InvalidEventSize)?
+				.0;
+			Ok(EbpfEvent::SocketCreate(*evt))
+		}
+
+		EVT_UDP_SEND => {
+			let evt = lib_ebpf_common::UdpSendEvent::ref_from_prefix(data)
+				.map_err(|_| Error::InvalidEventSize)?
+				.0;
+			Ok(EbpfEvent::UdpSend(*evt))
+		}
+
+		EVT_INET_ACCEPT => {
+			let evt = lib_ebpf_common::InetAcceptEvent::ref_from_prefix(data)
+				.map_err(|_| Error::InvalidEventSize)?
+				.0;
+			Ok(EbpfEvent::InetAccept(*evt))
+		}
+
+		EVT_DNS => {
+			let evt = lib_ebpf_common::DnsEvent::ref_from_prefix(data)
+				.map_err(|_| Error::InvalidEventSize)?
+				.0;
+			Ok(EbpfEvent::Dns(*evt))
+		}
 
 		EVT_MODULE => {
 			let evt = lib_ebpf_common::ModuleEvent::ref_from_prefix(data)
@@ -225,10 +573,69 @@ fn parse_event_from_bytes(data: &[u8]) -> Result<EbpfEvent> {
 			Ok(EbpfEvent::InetSock(*evt))
 		}
 		EVT_BPRM_CHECK_SEC => {
-			let evt = lib_ebpf_common::BprmSecurityCheckEvent::ref_from_prefix(data)
+			let (evt, rest) =
+				lib_ebpf_common::BprmSecurityCheckEvent::ref_from_prefix(data).map_err(|_| Error::InvalidEventSize)?;
+			Ok(EbpfEvent::BprmSecurityCheck(*evt, VarSections(rest)))
+		}
+		EVT_COMMIT_CREDS => {
+			let evt = lib_ebpf_common::CredChangeEvent::ref_from_prefix(data)
+				.map_err(|_| Error::InvalidEventSize)?
+				.0;
+			Ok(EbpfEvent::CredChange(*evt))
+		}
+		EVT_MEM_PROT => {
+			let evt = lib_ebpf_common::MemProtEvent::ref_from_prefix(data)
+				.map_err(|_| Error::InvalidEventSize)?
+				.0;
+			Ok(EbpfEvent::MemProt(*evt))
+		}
+		EVT_FILE => {
+			let evt = lib_ebpf_common::FileEvent::ref_from_prefix(data)
+				.map_err(|_| Error::InvalidEventSize)?
+				.0;
+			Ok(EbpfEvent::File(*evt))
+		}
+		EVT_CAP => {
+			let evt = lib_ebpf_common::CapEvent::ref_from_prefix(data)
+				.map_err(|_| Error::InvalidEventSize)?
+				.0;
+			Ok(EbpfEvent::Cap(*evt))
+		}
+		EVT_MOUNT => {
+			let evt = lib_ebpf_common::MountEvent::ref_from_prefix(data)
+				.map_err(|_| Error::InvalidEventSize)?
+				.0;
+			Ok(EbpfEvent::Mount(*evt))
+		}
+		EVT_NS => {
+			let evt = lib_ebpf_common::NsEvent::ref_from_prefix(data)
+				.map_err(|_| Error::InvalidEventSize)?
+				.0;
+			Ok(EbpfEvent::Ns(*evt))
+		}
+		EVT_IO_URING => {
+			let evt = lib_ebpf_common::IoUringEvent::ref_from_prefix(data)
+				.map_err(|_| Error::InvalidEventSize)?
+				.0;
+			Ok(EbpfEvent::IoUring(*evt))
+		}
+		EVT_PROCESS_FORK => {
+			let evt = lib_ebpf_common::ProcessForkEvent::ref_from_prefix(data)
+				.map_err(|_| Error::InvalidEventSize)?
+				.0;
+			Ok(EbpfEvent::ProcessFork(*evt))
+		}
+		EVT_PROCESS_EXEC => {
+			let evt = lib_ebpf_common::ProcessExecEvent::ref_from_prefix(data)
+				.map_err(|_| Error::InvalidEventSize)?
+				.0;
+			Ok(EbpfEvent::ProcessExec(*evt))
+		}
+		EVT_PROCESS_EXIT => {
+			let evt = lib_ebpf_common::ProcessExitEvent::ref_from_prefix(data)
 				.map_err(|_| Error::InvalidEventSize)?
 				.0;
-			Ok(EbpfEvent::BprmSecurityCheck(*evt))
+			Ok(EbpfEvent::ProcessExit(*evt))
 		}
 		EVT_BPF_PROG_LOAD => {
 			let evt = lib_ebpf_common::BpfProgLoadEvent::ref_from_prefix(data)
@@ -237,10 +644,15 @@ fn parse_event_from_bytes(data: &[u8]) -> Result<EbpfEvent> {
 			Ok(EbpfEvent::BpfProgLoad(*evt))
 		}
 		EVT_INODE => {
-			let evt = lib_ebpf_common::InodeEvent::ref_from_prefix(data)
+			let (evt, rest) =
+				lib_ebpf_common::InodeEvent::ref_from_prefix(data).map_err(|_| Error::InvalidEventSize)?;
+			Ok(EbpfEvent::Inode(*evt, VarSections(rest)))
+		}
+		EVT_INODE_ATTR => {
+			let evt = lib_ebpf_common::InodeAttrEvent::ref_from_prefix(data)
 				.map_err(|_| Error::InvalidEventSize)?
 				.0;
-			Ok(EbpfEvent::Inode(*evt))
+			Ok(EbpfEvent::InodeAttr(*evt))
 		}
 		EVT_BPF_MAP => {
 			let evt = lib_ebpf_common::BpfMapEvent::ref_from_prefix(data)
@@ -249,10 +661,9 @@ fn parse_event_from_bytes(data: &[u8]) -> Result<EbpfEvent> {
 			Ok(EbpfEvent::BpfMap(*evt))
 		}
 		EVT_INODE_MUTATE => {
-			let evt = lib_ebpf_common::InodeMutationEvent::ref_from_prefix(data)
-				.map_err(|_| Error::InvalidEventSize)?
-				.0;
-			Ok(EbpfEvent::InodeMutation(*evt))
+			let (evt, rest) =
+				lib_ebpf_common::InodeMutationEvent::ref_from_prefix(data).map_err(|_| Error::InvalidEventSize)?;
+			Ok(EbpfEvent::InodeMutation(*evt, VarSections(rest)))
 		}
 
 		EVT_PTRACE_ACCESS_CHECK => {
@@ -265,7 +676,55 @@ fn parse_event_from_bytes(data: &[u8]) -> Result<EbpfEvent> {
 	}
 }
 
+/// The first `N` variable-length sections of an event.
+fn take_sections<const N: usize>(mut sections: VarSections<'_>) -> Result<[&[u8]; N]> {
+	let mut out = [&[][..]; N];
+	for section in &mut out {
+		*section = sections.next().ok_or(Error::InvalidEventSize)?;
+	}
+	Ok(out)
+}
+
+/// Splits a NUL-separated argv or environment block.
+fn split_nul(bytes: &[u8]) -> impl Iterator<Item = Arc<str>> + '_ {
+	bytes.split(|&b| b == 0).filter(|s| !s.is_empty()).map(arc_from_bytes)
+}
+
 #[inline]
+/// Reassembles a path written by the kernel's `read_dentry_path`: names leaf
+/// first, NUL separated, closed by a `/` entry at the namespace root. A path
+/// cut short in the kernel keeps its deepest names as `.../dir/name`, and one
+/// resolved through a guessed mount (`/?` entry) reads `?/dir/name`, so that
+/// neither equals the real path.
+fn path_from_dentry_names(bytes: &[u8]) -> Option<Arc<str>> {
+	let truncated = bytes.last().is_some_and(|&b| b != 0);
+	let mut names: Vec<&[u8]> = bytes.split(|&b| b == 0).take_while(|n| !n.is_empty()).collect();
+
+	let guessed = names.last() == Some(&&b"/?"[..]);
+	let rooted = guessed || names.last() == Some(&&b"/"[..]);
+	if rooted || truncated {
+		names.pop();
+	}
+	if names.is_empty() && !rooted {
+		return None;
+	}
+
+	let joined = names
+		.iter()
+		.rev()
+		.map(|n| String::from_utf8_lossy(n))
+		.collect::<Vec<_>>()
+		.join("/");
+
+	Some(Arc::from(if guessed {
+		format!("?/{joined}")
+	} else if rooted {
+		format!("/{joined}")
+	} else {
+		format!(".../{joined}")
+	}))
+}
+
 fn arc_from_bytes(bytes: &[u8]) -> Arc<str> {
 	let len = bytes.iter().position(|&b| b == 0).unwrap_or(bytes.len());
 
@@ -339,3 +798,142 @@ fn bpf_map_type_to_str(map_type: u32) -> &'static str {
 		_ => "UNKNOWN",
 	}
 }
+
+// region:    --- Tests
+
+#[cfg(test)]
+mod tests {
+	type Result<T> = core::result::Result<T, Box<dyn std::error::Error>>;
+
+	use super::*;
+
+	const HEADER_LEN: usize = size_of::<lib_ebpf_common::EventHeader>();
+
+	/// An inode record with `sections` as its variable part.
+	fn inode_record(version: u8, sections: &[u8]) -> Vec<u8> {
+		let mut out = vec![0u8; size_of::<lib_ebpf_common::InodeEvent>()];
+		out[36] = EVT_INODE;
+		out[69] = version;
+		out.extend_from_slice(sections);
+		out
+	}
+
+	fn section(bytes: &[u8]) -> Vec<u8> {
+		let mut out = (bytes.len() as u16).to_le_bytes().to_vec();
+		out.extend_from_slice(bytes);
+		out
+	}
+
+	#[test]
+	fn var_sections_cases() -> Result<()> {
+		// -- Setup & Fixtures
+		let exact = [section(b"passwd"), section(b"etc\0/\0")].concat();
+		let empty = [section(b""), section(b"name")].concat();
+		let mut overlong = section(b"passwd");
+		overlong.extend_from_slice(&8u16.to_le_bytes());
+		overlong.extend_from_slice(b"etc");
+		let mut odd = section(b"passwd");
+		odd.push(1);
+		let fx_cases = [
+			("exact fit", exact, vec![&b"passwd"[..], b"etc\0/\0"]),
+			("zero-length section", empty, vec![&b""[..], b"name"]),
+			("section longer than the record", overlong, vec![&b"passwd"[..]]),
+			("half a length prefix", odd, vec![&b"passwd"[..]]),
+		];
+
+		for (name, bytes, fx_sections) in fx_cases {
+			// -- Exec
+			let mut sections = VarSections(&bytes);
+			let got: Vec<&[u8]> = sections.by_ref().collect();
+
+			// -- Check
+			assert_eq!(got, fx_sections, "{name}");
+			assert!(sections.next().is_none(), "{name}: should stay exhausted");
+		}
+
+		Ok(())
+	}
+
+	#[test]
+	fn take_sections_missing_trailing_section_err() -> Result<()> {
+		// -- Setup & Fixtures
+		let bytes = section(b"passwd");
+
+		// -- Exec & Check
+		assert_eq!(take_sections::<1>(VarSections(&bytes))?, [b"passwd"]);
+		assert!(matches!(
+			take_sections::<2>(VarSections(&bytes)),
+			Err(Error::InvalidEventSize)
+		));
+		assert!(matches!(
+			take_sections::<1>(VarSections(&[])),
+			Err(Error::InvalidEventSize)
+		));
+
+		Ok(())
+	}
+
+	#[test]
+	fn parse_inode_record_ok() -> Result<()> {
+		// -- Setup & Fixtures
+		let record = inode_record(
+			EVENT_WIRE_VERSION,
+			&[section(b"passwd"), section(b"passwd\0etc\0/\0")].concat(),
+		);
+
+		// -- Exec
+		let EbpfEvent::Inode(_, sections) = parse_event_from_bytes(&record)? else {
+			return Err("not an inode event".into());
+		};
+		let [filename, path] = take_sections(sections)?;
+
+		// -- Check
+		assert_eq!(filename, b"passwd");
+		assert_eq!(path_from_dentry_names(path).as_deref(), Some("/etc/passwd"));
+
+		Ok(())
+	}
+
+	#[test]
+	fn parse_wrong_version_err() -> Result<()> {
+		// -- Setup & Fixtures
+		let record = inode_record(EVENT_WIRE_VERSION.wrapping_add(1), &section(b"passwd"));
+
+		// -- Exec
+		let res = parse_event_from_bytes(&record);
+
+		// -- Check
+		assert!(matches!(
+			res,
+			Err(Error::EventVersionMismatch { expected: EVENT_WIRE_VERSION, found })
+				if found == EVENT_WIRE_VERSION.wrapping_add(1)
+		));
+		assert!(matches!(
+			parse_event_from_bytes(&record[..HEADER_LEN - 1]),
+			Err(Error::InvalidEventSize)
+		));
+
+		Ok(())
+	}
+
+	#[test]
+	fn path_from_dentry_names_cases() -> Result<()> {
+		// -- Setup & Fixtures
+		let fx_cases: [(&[u8], Option<&str>); 5] = [
+			(b"passwd\0etc\0/\0", Some("/etc/passwd")),
+			(b"passwd\0etc\0/?\0", Some("?/etc/passwd")),
+			(b"passwd\0etc\0", Some(".../etc/passwd")),
+			(b"passwd\0et", Some(".../passwd")),
+			(b"", None),
+		];
+
+		for (bytes, fx_path) in fx_cases {
+			// -- Exec & Check
+			assert_eq!(path_from_dentry_names(bytes).as_deref(), fx_path);
+		}
+
+		Ok(())
+	}
+}
+
+// endregion: --- Tests
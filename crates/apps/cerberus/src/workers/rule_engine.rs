@@ -4,6 +4,7 @@ use std::{
 		Arc,
 		atomic::{AtomicU64, Ordering},
 	},
+	time::Duration,
 };
 
 use crate::{
@@ -17,8 +18,14 @@ use lib_common::event::CerberusEvent;
 
 use lib_event::unbound::{Rx, Tx};
 use lib_rules::{EngineEvent, ResponseRequest, RuleEngine};
+use tokio::task::JoinHandle;
 use tokio_util::sync::CancellationToken;
 
+const RISK_REPORT_INTERVAL: Duration = Duration::from_secs(1);
+const RISK_REPORT_LIMIT: usize = 16;
+const RISK_PRUNE_INTERVAL: Duration = Duration::from_secs(10);
+const NOVELTY_FLUSH_INTERVAL: Duration = Duration::from_secs(30);
+
 pub struct RuleEngineWorker {
 	tx: Tx<AppEvent>,
 	ringbuf_rx: Rx<CerberusEvent>,
@@ -27,6 +34,8 @@ pub struct RuleEngineWorker {
 	response_id: AtomicU64,
 	limiter: DefaultDirectRateLimiter,
 	dropped: AtomicU64,
+	/// The `first_seen` store write in flight, if any.
+	novelty_write: Option<JoinHandle<()>>,
 	token: CancellationToken,
 }
 
@@ -51,6 +60,7 @@ impl RuleEngineWorker {
 			response_tx,
 			response_id: AtomicU64::new(0),
 			dropped: AtomicU64::new(0),
+			novelty_write: None,
 			token,
 		})
 	}
@@ -87,7 +97,42 @@ impl RuleEngineWorker {
 		}
 	}
 
+	fn report_risk_scores(&self) {
+		let scores = self.rule_engine.risk_scores(RISK_REPORT_LIMIT);
+
+		if let Err(e) = self.tx.send(AppEvent::RiskScores { scores: scores.into() }) {
+			tracing::error!("Failed to send risk scores: {e}");
+		}
+	}
+
+	/// Writes the `first_seen` store on the blocking pool, skipping the round
+	/// while the previous write is still running.
+	fn flush_novelty(&mut self) {
+		if self.novelty_write.as_ref().is_some_and(|write| !write.is_finished()) {
+			return;
+		}
+
+		let Some(snapshot) = self.rule_engine.novelty_snapshot() else {
+			return;
+		};
+		self.novelty_write = Some(tokio::task::spawn_blocking(move || {
+			if let Err(e) = snapshot.write() {
+				tracing::warn!(error = %e, "Failed to persist first_seen store");
+			}
+		}));
+	}
+
+	async fn finish_novelty_write(&mut self) {
+		if let Some(write) = self.novelty_write.take() {
+			let _ = write.await;
+		}
+	}
+
 	pub async fn run(mut self, logging: bool) -> Result<()> {
+		let mut risk_report = tokio::time::interval(RISK_REPORT_INTERVAL);
+		let mut risk_prune = tokio::time::interval(RISK_PRUNE_INTERVAL);
+		let mut novelty_flush = tokio::time::interval(NOVELTY_FLUSH_INTERVAL);
+
 		loop {
 			tokio::select! {
 				biased;
@@ -97,6 +142,18 @@ impl RuleEngineWorker {
 					break;
 				}
 
+				_ = risk_report.tick() => {
+					self.report_risk_scores();
+				}
+
+				_ = risk_prune.tick() => {
+					self.rule_engine.prune_risk();
+				}
+
+				_ = novelty_flush.tick() => {
+					self.flush_novelty();
+				}
+
 				res = self.ringbuf_rx.recv() => {
 					match res {
 						Ok(evt) => {
@@ -129,6 +186,10 @@ impl RuleEngineWorker {
 				}
 			}
 		}
+		self.finish_novelty_write().await;
+		self.flush_novelty();
+		self.finish_novelty_write().await;
+
 		Ok(())
 	}
 }
@@ -3,6 +3,7 @@ use std::{path::PathBuf, sync::Arc, time::Duration};
 use crate::{
 	error::Result,
 	event::{AppEvent, RuleWatchEvent},
+	hook_registry::event::HookCommand,
 };
 
 use lib_event::unbound::{Rx, Tx, new_channel_unbounded_async};
@@ -13,6 +14,7 @@ use tokio_util::sync::CancellationToken;
 
 pub struct RuleWatchWorker {
 	tx: Tx<AppEvent>,
+	hook_tx: Tx<HookCommand>,
 	rx: Rx<RuleWatchEvent>,
 	rule_engine: Arc<RuleEngine>,
 	_debouncer: Debouncer<INotifyWatcher, NoCache>,
@@ -23,6 +25,7 @@ pub struct RuleWatchWorker {
 impl RuleWatchWorker {
 	pub fn start(
 		app_tx: Tx<AppEvent>,
+		hook_tx: Tx<HookCommand>,
 		rule_engine: Arc<RuleEngine>,
 		rule_dir: PathBuf,
 		token: CancellationToken,
@@ -39,6 +42,7 @@ impl RuleWatchWorker {
 
 		Ok(RuleWatchWorker {
 			tx: app_tx,
+			hook_tx,
 			rx,
 			rule_engine,
 			rule_dir,
@@ -63,6 +67,9 @@ impl RuleWatchWorker {
 								tracing::error!("Rule reload failed: {e}");
 								continue;
 							}
+							if let Err(e) = self.hook_tx.send(HookCommand::ApplyPolicy) {
+								tracing::error!("Failed to send event policy update: {e}");
+							}
 							let rules: Arc<[Arc<str>]> = self
 								.rule_engine
 								.snapshot()
@@ -0,0 +1,169 @@
+use std::{
+	io::ErrorKind,
+	os::unix::fs::{FileTypeExt, MetadataExt},
+	time::Duration,
+};
+
+use crate::error::{Error, Result};
+use aya::maps::{HashMap as AyaHashMap, MapData};
+use tokio_util::sync::CancellationToken;
+
+/// How often denied socket paths are looked up again.
+const UNIX_DENY_REFRESH_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Keeps `UNIX_CONNECT_DENY` pointed at the sockets currently bound at the
+/// `--deny-unix-connect` paths. The kernel matches the listener's inode, so a
+/// daemon that re-creates its socket (e.g. a restarted dockerd) needs the new
+/// inode inserted.
+pub struct UnixDenyWorker {
+	map: AyaHashMap<MapData, [u64; 2], u8>,
+	paths: Vec<DeniedPath>,
+	token: CancellationToken,
+}
+
+struct DeniedPath {
+	path: String,
+	/// Key currently inserted for the path, `None` until it first resolves.
+	key: Option<[u64; 2]>,
+	/// Whether the last lookup found a socket, so a vanished path is logged once.
+	resolves: bool,
+}
+
+impl UnixDenyWorker {
+	/// A path that does not exist yet is denied once a socket is bound there;
+	/// one that exists but is not a socket is an error.
+	pub fn start(
+		map: AyaHashMap<MapData, [u64; 2], u8>,
+		paths: impl IntoIterator<Item = String>,
+		token: CancellationToken,
+	) -> Result<Self> {
+		let mut worker = UnixDenyWorker {
+			map,
+			paths: Vec::new(),
+			token,
+		};
+		for path in paths {
+			let key = unix_socket_id(&path)?;
+			match key {
+				Some(key) => worker.map.insert(key, 1, 0)?,
+				None => tracing::warn!("[UnixDenyWorker]: '{path}' does not exist yet, denying it once it is bound"),
+			}
+			worker.paths.push(DeniedPath {
+				path,
+				key,
+				resolves: key.is_some(),
+			});
+		}
+
+		Ok(worker)
+	}
+
+	pub async fn run(mut self) -> Result<()> {
+		let mut refresh = tokio::time::interval(UNIX_DENY_REFRESH_INTERVAL);
+
+		loop {
+			tokio::select! {
+				biased;
+
+				_ = self.token.cancelled() => {
+					tracing::info!("[UnixDenyWorker]: shutting down");
+					break;
+				}
+
+				_ = refresh.tick() => self.refresh(),
+			}
+		}
+
+		Ok(())
+	}
+
+	fn refresh(&mut self) {
+		for denied in &mut self.paths {
+			let path = &denied.path;
+			let resolved = match unix_socket_id(path) {
+				Ok(resolved) => resolved,
+				Err(e) => {
+					if denied.resolves {
+						tracing::warn!("[UnixDenyWorker]: {e}");
+					}
+					denied.resolves = false;
+					continue;
+				}
+			};
+
+			match resolved {
+				Some(key) if denied.key == Some(key) => {}
+				Some(key) => {
+					if let Err(e) = self.map.insert(key, 1, 0) {
+						tracing::error!("[UnixDenyWorker]: denying '{path}' failed: {e}");
+						continue;
+					}
+					// the old inode went away with the socket that replaced it
+					if let Some(old) = denied.key.replace(key) {
+						let _ = self.map.remove(&old);
+					}
+					tracing::info!("[UnixDenyWorker]: '{path}' is bound to a new socket, deny updated");
+				}
+				// the last key stays: the listener may still be reachable under another name
+				None if denied.resolves => {
+					tracing::warn!("[UnixDenyWorker]: '{path}' no longer resolves to a socket");
+				}
+				None => {}
+			}
+			denied.resolves = resolved.is_some();
+		}
+	}
+}
+
+/// `[s_dev, i_ino]` of the socket bound at `path`, as the kernel keys it, or
+/// `None` if nothing exists there.
+fn unix_socket_id(path: &str) -> Result<Option<[u64; 2]>> {
+	let meta = match std::fs::metadata(path) {
+		Ok(meta) => meta,
+		Err(e) if e.kind() == ErrorKind::NotFound => return Ok(None),
+		Err(_) => return Err(Error::InvalidUnixPath(path.into())),
+	};
+	if !meta.file_type().is_socket() {
+		return Err(Error::InvalidUnixPath(path.into()));
+	}
+
+	// st_dev is the userspace encoding, s_dev is MKDEV(major, minor)
+	let dev = (u64::from(libc::major(meta.dev())) << 20) | u64::from(libc::minor(meta.dev()));
+	Ok(Some([dev, meta.ino()]))
+}
+
+// region:    --- Tests
+
+#[cfg(test)]
+mod tests {
+	type Result<T> = core::result::Result<T, Box<dyn std::error::Error>>;
+
+	use std::os::unix::net::UnixListener;
+
+	use super::*;
+
+	#[test]
+	fn unix_socket_id_cases() -> Result<()> {
+		// -- Setup & Fixtures
+		let fx_dir = std::env::temp_dir().join(format!("cerberus-unix-deny-{}", std::process::id()));
+		std::fs::create_dir_all(&fx_dir)?;
+		let fx_sock = fx_dir.join("api.sock");
+		let fx_file = fx_dir.join("plain");
+		std::fs::write(&fx_file, b"")?;
+
+		// -- Exec & Check
+		assert_eq!(unix_socket_id(&fx_sock.to_string_lossy())?, None);
+
+		let _listener = UnixListener::bind(&fx_sock)?;
+		let key = unix_socket_id(&fx_sock.to_string_lossy())?.ok_or("bound socket")?;
+		assert_eq!(key[1], std::fs::metadata(&fx_sock)?.ino());
+
+		assert!(unix_socket_id(&fx_file.to_string_lossy()).is_err());
+
+		std::fs::remove_dir_all(&fx_dir)?;
+
+		Ok(())
+	}
+}
+
+// endregion: --- Tests
@@ -1,6 +1,6 @@
 use lib_container::container::ContainerInfo;
 use lib_event_schema::{Field, FieldValue};
-use std::sync::Arc;
+use std::{net::IpAddr, sync::Arc};
 use strum::EnumCount;
 
 use derive_more::From;
@@ -16,16 +16,50 @@ pub enum CerberusEvent {
 	#[from]
 	Socket(SocketEvent),
 	#[from]
+	UnixSocket(UnixSocketEvent),
+	#[from]
+	SocketCreate(SocketCreateEvent),
+	#[from]
+	UdpSend(UdpSendEvent),
+	#[from]
+	InetAccept(InetAcceptEvent),
+	#[from]
+	Dns(DnsEvent),
+	#[from]
 	Module(ModuleEvent),
 	#[from]
 	Bprm(BprmSecurityEvent),
 	#[from]
+	ProcessFork(ProcessForkEvent),
+	#[from]
+	ProcessExec(ProcessExecEvent),
+	#[from]
+	ProcessExit(ProcessExitEvent),
+	#[from]
+	CredChange(CredChangeEvent),
+	#[from]
+	IoUring(IoUringEvent),
+	#[from]
+	MemProt(MemProtEvent),
+	#[from]
+	Capable(CapableEvent),
+	#[from]
+	Capset(CapsetEvent),
+	#[from]
+	Mount(MountEvent),
+	#[from]
+	Namespace(NamespaceEvent),
+	#[from]
+	File(FileEvent),
+	#[from]
 	BpfProgLoad(BpfProgLoadEvent),
 	#[from]
 	BpfMap(BpfMapEvent),
 	#[from]
 	InodeMutation(InodeMutationEvent),
 	#[from]
+	InodeAttr(InodeAttrEvent),
+	#[from]
 	PtraceAccessCheck(PtraceAccessCheckEvent),
 	#[from]
 	Tamper(TamperEvent),
@@ -80,6 +114,9 @@ pub struct InodeMutationEvent {
 	pub new_filename_len: u32,
 	pub old_filename_len: u32,
 	pub mutation: u8,
+	/// Full source path; for a symlink, the link target as given.
+	pub old_path: Option<Arc<str>>,
+	pub new_path: Option<Arc<str>>,
 }
 
 #[derive(Debug, Clone)]
@@ -88,8 +125,8 @@ pub struct InetSockEvent {
 	pub old_state: Arc<str>,
 	pub new_state: Arc<str>,
 	pub protocol: Arc<str>,
-	pub saddr: u32,
-	pub daddr: u32,
+	pub saddr: IpAddr,
+	pub daddr: IpAddr,
 	pub sport: u16,
 	pub dport: u16,
 }
@@ -97,12 +134,69 @@ pub struct InetSockEvent {
 #[derive(Debug, Clone)]
 pub struct SocketEvent {
 	pub header: EventHeader,
-	pub addr: u32,
+	pub addr: IpAddr,
 	pub port: u16,
 	pub family: u16,
 	pub op: u8,
 }
 
+/// An AF_UNIX `connect(2)`, reported once per process and path per minute.
+#[derive(Debug, Clone)]
+pub struct UnixSocketEvent {
+	pub header: EventHeader,
+	pub op: u8,
+	/// Path as given to connect(2); abstract names are shown as `@name`.
+	pub path: Arc<str>,
+	pub abstract_ns: bool,
+	/// Refused because the path is on the deny list.
+	pub denied: bool,
+}
+
+/// First datagram of a UDP flow, per socket and destination.
+#[derive(Debug, Clone)]
+pub struct UdpSendEvent {
+	pub header: EventHeader,
+	pub saddr: IpAddr,
+	pub daddr: IpAddr,
+	pub sport: u16,
+	pub dport: u16,
+}
+
+/// An inbound TCP connection returned by accept(2) to the listening process.
+#[derive(Debug, Clone)]
+pub struct InetAcceptEvent {
+	pub header: EventHeader,
+	pub protocol: Arc<str>,
+	/// Local (listening) side.
+	pub saddr: IpAddr,
+	/// Remote peer.
+	pub daddr: IpAddr,
+	pub sport: u16,
+	pub dport: u16,
+}
+
+/// A DNS lookup, reported once its response arrives or it times out.
+#[derive(Debug, Clone)]
+pub struct DnsEvent {
+	pub header: EventHeader,
+	pub server: IpAddr,
+	pub qname: Arc<str>,
+	pub qtype: u16,
+	/// `None` when no response was seen.
+	pub rcode: Option<u8>,
+	pub answers: Vec<IpAddr>,
+}
+
+/// A `socket(2)` call, once per process, family, type and protocol per minute.
+/// Kernel-internal sockets are not reported.
+#[derive(Debug, Clone)]
+pub struct SocketCreateEvent {
+	pub header: EventHeader,
+	pub family: u16,
+	pub sock_type: u32,
+	pub protocol: u32,
+}
+
 #[derive(Debug, Clone)]
 pub struct BpfMapEvent {
 	pub header: EventHeader,
@@ -125,6 +219,10 @@ pub struct ModuleEvent {
 	pub header: EventHeader,
 	pub module_name: Arc<str>,
 	pub op: u8,
+	/// Loads only: `1` = init_module (image from memory), `2` = finit_module.
+	pub source: u8,
+	/// File passed to finit_module.
+	pub path: Option<Arc<str>>,
 }
 
 #[derive(Debug, Clone)]
@@ -134,12 +232,492 @@ pub struct BprmSecurityEvent {
 	pub path_len: u32,
 }
 
+/// The header describes the parent.
+#[derive(Debug, Clone)]
+pub struct ProcessForkEvent {
+	pub header: EventHeader,
+	pub child_pid: u32,
+	pub child_tgid: u32,
+}
+
+#[derive(Debug, Clone)]
+pub struct ProcessExecEvent {
+	pub header: EventHeader,
+	/// Path as passed to execve; empty for failed execs.
+	pub filename: Arc<str>,
+	pub args: Vec<Arc<str>>,
+	/// `args` joined by spaces.
+	pub cmdline: Arc<str>,
+	/// Loader variables (`LD_PRELOAD=...`) found in the environment.
+	pub env: Vec<Arc<str>>,
+	/// The environment was not fully scanned or a loader variable was cut, so
+	/// `env` may be incomplete.
+	pub env_truncated: bool,
+	pub old_pid: u32,
+	pub success: bool,
+	/// Positive errno of a failed exec, `0` on success.
+	pub errno: i32,
+}
+
+#[derive(Debug, Clone)]
+pub struct ProcessExitEvent {
+	pub header: EventHeader,
+	pub exit_code: u8,
+	/// Terminating signal, `0` for a normal exit.
+	pub signal: u8,
+}
+
+#[derive(Debug, Clone)]
+pub struct CredChangeEvent {
+	pub header: EventHeader,
+	pub old_uid: u32,
+	pub new_uid: u32,
+	pub old_euid: u32,
+	pub new_euid: u32,
+	pub old_gid: u32,
+	pub new_gid: u32,
+	pub old_egid: u32,
+	pub new_egid: u32,
+	pub old_cap_effective: u64,
+	pub new_cap_effective: u64,
+}
+
+impl CredChangeEvent {
+	/// Capability numbers effective after the change but not before.
+	pub fn caps_gained(&self) -> Vec<i64> {
+		let gained = self.new_cap_effective & !self.old_cap_effective;
+		(0..64).filter(|bit| gained & (1 << bit) != 0).collect()
+	}
+}
+
+/// `CAP_*` names indexed by capability number.
+pub const CAP_NAMES: [&str; 41] = [
+	"CAP_CHOWN",
+	"CAP_DAC_OVERRIDE",
+	"CAP_DAC_READ_SEARCH",
+	"CAP_FOWNER",
+	"CAP_FSETID",
+	"CAP_KILL",
+	"CAP_SETGID",
+	"CAP_SETUID",
+	"CAP_SETPCAP",
+	"CAP_LINUX_IMMUTABLE",
+	"CAP_NET_BIND_SERVICE",
+	"CAP_NET_BROADCAST",
+	"CAP_NET_ADMIN",
+	"CAP_NET_RAW",
+	"CAP_IPC_LOCK",
+	"CAP_IPC_OWNER",
+	"CAP_SYS_MODULE",
+	"CAP_SYS_RAWIO",
+	"CAP_SYS_CHROOT",
+	"CAP_SYS_PTRACE",
+	"CAP_SYS_PACCT",
+	"CAP_SYS_ADMIN",
+	"CAP_SYS_BOOT",
+	"CAP_SYS_NICE",
+	"CAP_SYS_RESOURCE",
+	"CAP_SYS_TIME",
+	"CAP_SYS_TTY_CONFIG",
+	"CAP_MKNOD",
+	"CAP_LEASE",
+	"CAP_AUDIT_WRITE",
+	"CAP_AUDIT_CONTROL",
+	"CAP_SETFCAP",
+	"CAP_MAC_OVERRIDE",
+	"CAP_MAC_ADMIN",
+	"CAP_SYSLOG",
+	"CAP_WAKE_ALARM",
+	"CAP_BLOCK_SUSPEND",
+	"CAP_AUDIT_READ",
+	"CAP_PERFMON",
+	"CAP_BPF",
+	"CAP_CHECKPOINT_RESTORE",
+];
+
+pub fn cap_name(cap: u32) -> &'static str {
+	CAP_NAMES.get(cap as usize).copied().unwrap_or("CAP_UNKNOWN")
+}
+
+/// Capability number of a `CAP_*` name; the prefix and case are optional.
+pub fn cap_from_name(name: &str) -> Option<u32> {
+	let name = name.to_ascii_uppercase();
+	let name = name.strip_prefix("CAP_").unwrap_or(&name);
+	CAP_NAMES.iter().position(|n| n[4..] == *name).map(|cap| cap as u32)
+}
+
+fn cap_names(set: u64) -> Vec<&'static str> {
+	(0..CAP_NAMES.len())
+		.filter(|bit| set & (1 << bit) != 0)
+		.map(|bit| CAP_NAMES[bit])
+		.collect()
+}
+
+/// A capability check, reported once per process and capability per minute.
+#[derive(Debug, Clone)]
+pub struct CapableEvent {
+	pub header: EventHeader,
+	pub cap: u32,
+	/// False for `*_noaudit` checks, which only probe for the capability.
+	pub audit: bool,
+	/// Refused because the capability is on the deny list.
+	pub denied: bool,
+	/// Checked against the initial user namespace rather than a child one.
+	pub init_userns: bool,
+}
+
+impl CapableEvent {
+	pub fn cap_name(&self) -> &'static str {
+		cap_name(self.cap)
+	}
+}
+
+/// A process changing its own capability sets with capset(2).
+#[derive(Debug, Clone)]
+pub struct CapsetEvent {
+	pub header: EventHeader,
+	pub effective: u64,
+	pub permitted: u64,
+	pub old_effective: u64,
+	pub old_permitted: u64,
+	pub init_userns: bool,
+}
+
+impl CapsetEvent {
+	/// Capabilities added to the effective or permitted set.
+	pub fn gained(&self) -> Vec<&'static str> {
+		cap_names((self.effective & !self.old_effective) | (self.permitted & !self.old_permitted))
+	}
+
+	pub fn effective_names(&self) -> Vec<&'static str> {
+		cap_names(self.effective)
+	}
+}
+
+/// A filesystem mounted with mount(2), bind mounts and remounts included, or
+/// an existing mount moved with move_mount(2).
+#[derive(Debug, Clone)]
+pub struct MountEvent {
+	pub header: EventHeader,
+	pub op: u8,
+	/// `MS_*` flags.
+	pub flags: u64,
+	/// Device or source path as given to mount(2); the resolved path of the
+	/// moved mount for move_mount(2).
+	pub source: Option<Arc<str>>,
+	pub target: Option<Arc<str>>,
+	pub fstype: Option<Arc<str>>,
+	/// The mounting task is in the host's mount namespace.
+	pub host_ns: bool,
+}
+
+impl MountEvent {
+	pub fn op_name(&self) -> &'static str {
+		match self.op {
+			0 => "mount",
+			1 => "move",
+			_ => "unknown",
+		}
+	}
+
+	/// `MS_*` names of the set flags, lowercased without the prefix, e.g. `["bind", "rec"]`.
+	pub fn flag_names(&self) -> Vec<&'static str> {
+		const FLAGS: [&str; 26] = [
+			"rdonly",
+			"nosuid",
+			"nodev",
+			"noexec",
+			"synchronous",
+			"remount",
+			"mandlock",
+			"dirsync",
+			"nosymfollow",
+			"",
+			"noatime",
+			"nodiratime",
+			"bind",
+			"move",
+			"rec",
+			"silent",
+			"posixacl",
+			"unbindable",
+			"private",
+			"slave",
+			"shared",
+			"relatime",
+			"kernmount",
+			"i_version",
+			"strictatime",
+			"lazytime",
+		];
+		FLAGS
+			.iter()
+			.enumerate()
+			.filter(|(bit, name)| !name.is_empty() && self.flags & (1 << bit) != 0)
+			.map(|(_, name)| *name)
+			.collect()
+	}
+}
+
+/// A task switching namespaces with setns(2) or unshare(2). The header is taken
+/// after the call, so `header.mnt_ns` is the mount namespace the task ended up in.
+#[derive(Debug, Clone)]
+pub struct NamespaceEvent {
+	pub header: EventHeader,
+	pub op: u8,
+	/// `CLONE_NEW*` flags passed to the call; 0 for a setns(2) that accepts any type.
+	pub flags: u32,
+	/// `CLONE_NEW*` bits of the namespaces that actually changed.
+	pub changed: u32,
+	pub old_mnt_ns: u32,
+	pub fd: i32,
+	/// Mount namespace of PID 1 as seen by the agent, 0 if unknown.
+	pub host_mnt_ns: u32,
+}
+
+impl NamespaceEvent {
+	pub fn op_name(&self) -> &'static str {
+		match self.op {
+			0 => "setns",
+			1 => "unshare",
+			_ => "unknown",
+		}
+	}
+
+	/// Namespace kinds named by `CLONE_NEW*` bits, e.g. `["mnt", "net"]`.
+	pub fn ns_names(flags: u32) -> Vec<&'static str> {
+		const KINDS: [(u32, &str); 8] = [
+			(0x0002_0000, "mnt"),
+			(0x0400_0000, "uts"),
+			(0x0800_0000, "ipc"),
+			(0x2000_0000, "pid"),
+			(0x4000_0000, "net"),
+			(0x0200_0000, "cgroup"),
+			(0x1000_0000, "user"),
+			(0x0000_0080, "time"),
+		];
+		KINDS
+			.iter()
+			.filter(|(bit, _)| flags & bit != 0)
+			.map(|(_, name)| *name)
+			.collect()
+	}
+
+	/// Moved from another mount namespace into the host's, the typical
+	/// container escape through `/proc/1/ns/mnt`.
+	pub fn joins_host(&self) -> bool {
+		self.host_mnt_ns != 0 && self.old_mnt_ns != self.host_mnt_ns && self.header.mnt_ns == self.host_mnt_ns
+	}
+}
+
+/// An executable mapping, or a mapping made executable by mprotect.
+#[derive(Debug, Clone)]
+pub struct MemProtEvent {
+	pub header: EventHeader,
+	/// `0` = mmap, `1` = mprotect.
+	pub op: u8,
+	/// PROT_* bits.
+	pub prot: u32,
+	/// Protection before an mprotect.
+	pub old_prot: Option<u32>,
+	/// MAP_* flags of an mmap.
+	pub flags: u32,
+	pub anonymous: bool,
+	pub path: Option<Arc<str>>,
+}
+
+impl MemProtEvent {
+	pub fn op_name(&self) -> &'static str {
+		match self.op {
+			0 => "mmap",
+			1 => "mprotect",
+			_ => "unknown",
+		}
+	}
+
+	/// `r`/`w`/`x` letters of the set PROT_* bits, e.g. `rx`.
+	pub fn prot_str(prot: u32) -> &'static str {
+		const PROTS: [&str; 8] = ["", "r", "w", "rw", "x", "rx", "wx", "rwx"];
+		PROTS[(prot & 7) as usize]
+	}
+
+	/// `rw->rx` for an mprotect.
+	pub fn transition(&self) -> Option<String> {
+		self.old_prot
+			.map(|old| format!("{}->{}", Self::prot_str(old), Self::prot_str(self.prot)))
+	}
+}
+
+/// An open of a watched path, or the first write through a watched file opened
+/// for writing.
+#[derive(Debug, Clone)]
+pub struct FileEvent {
+	pub header: EventHeader,
+	/// `0` = open, `1` = write.
+	pub op: u8,
+	/// Read/write/append/truncate bits derived from the open flags.
+	pub access: u8,
+	/// `f_flags` of the open file.
+	pub flags: u32,
+	pub path: Arc<str>,
+}
+
+impl FileEvent {
+	pub fn op_name(&self) -> &'static str {
+		match self.op {
+			0 => "open",
+			1 => "write",
+			_ => "unknown",
+		}
+	}
+
+	/// Names of the set access bits, e.g. `["write", "truncate"]`.
+	pub fn access_names(&self) -> Vec<&'static str> {
+		const ACCESS: [&str; 4] = ["read", "write", "append", "truncate"];
+		ACCESS
+			.iter()
+			.enumerate()
+			.filter(|(bit, _)| self.access & (1 << bit) != 0)
+			.map(|(_, name)| *name)
+			.collect()
+	}
+}
+
+/// An io_uring ring being created, or a file/network request submitted to one.
+#[derive(Debug, Clone)]
+pub struct IoUringEvent {
+	pub header: EventHeader,
+	pub opcode: u8,
+	pub setup: bool,
+	pub fd: i32,
+	pub path: Option<Arc<str>>,
+	pub addr: Option<IpAddr>,
+	pub port: u16,
+	pub sqpoll: bool,
+}
+
+impl IoUringEvent {
+	/// `IORING_OP_*` name without the prefix, lowercased; `setup` for ring creation.
+	pub fn op_name(&self) -> &'static str {
+		if self.setup {
+			return "setup";
+		}
+		const OPS: [&str; 58] = [
+			"nop",
+			"readv",
+			"writev",
+			"fsync",
+			"read_fixed",
+			"write_fixed",
+			"poll_add",
+			"poll_remove",
+			"sync_file_range",
+			"sendmsg",
+			"recvmsg",
+			"timeout",
+			"timeout_remove",
+			"accept",
+			"async_cancel",
+			"link_timeout",
+			"connect",
+			"fallocate",
+			"openat",
+			"close",
+			"files_update",
+			"statx",
+			"read",
+			"write",
+			"fadvise",
+			"madvise",
+			"send",
+			"recv",
+			"openat2",
+			"epoll_ctl",
+			"splice",
+			"provide_buffers",
+			"remove_buffers",
+			"tee",
+			"shutdown",
+			"renameat",
+			"unlinkat",
+			"mkdirat",
+			"symlinkat",
+			"linkat",
+			"msg_ring",
+			"fsetxattr",
+			"setxattr",
+			"fgetxattr",
+			"getxattr",
+			"socket",
+			"uring_cmd",
+			"send_zc",
+			"sendmsg_zc",
+			"read_multishot",
+			"waitid",
+			"futex_wait",
+			"futex_wake",
+			"futex_waitv",
+			"fixed_fd_install",
+			"ftruncate",
+			"bind",
+			"listen",
+		];
+		OPS.get(self.opcode as usize).copied().unwrap_or("unknown")
+	}
+}
+
 #[derive(Debug, Clone)]
 pub struct InodeEvent {
 	pub header: EventHeader,
 	pub filename: Arc<str>,
 	pub filename_len: u32,
 	pub op: u8,
+	pub path: Option<Arc<str>>,
+}
+
+/// A regular file being created, or its mode, ownership or timestamps being
+/// changed (chmod, chown, `touch -t`).
+#[derive(Debug, Clone)]
+pub struct InodeAttrEvent {
+	pub header: EventHeader,
+	pub filename: Arc<str>,
+	/// `0` = create, `1` = setattr.
+	pub op: u8,
+	/// `ATTR_*` bits of the change; only `ATTR_MODE` on create.
+	pub valid: u32,
+	/// Permission bits, set on create and when the mode changes.
+	pub mode: Option<u32>,
+	pub uid: Option<u32>,
+	pub gid: Option<u32>,
+	/// Explicitly set access time, in seconds.
+	pub atime: Option<i64>,
+	/// Explicitly set modification time, in seconds.
+	pub mtime: Option<i64>,
+	pub path: Option<Arc<str>>,
+}
+
+impl InodeAttrEvent {
+	pub fn op_name(&self) -> &'static str {
+		match self.op {
+			0 => "create",
+			1 => "setattr",
+			_ => "unknown",
+		}
+	}
+
+	/// Names of the changed attributes, e.g. `["mode"]` for a chmod.
+	pub fn changes(&self) -> Vec<&'static str> {
+		[
+			(self.mode.is_some(), "mode"),
+			(self.uid.is_some(), "uid"),
+			(self.gid.is_some(), "gid"),
+			(self.atime.is_some(), "atime"),
+			(self.mtime.is_some(), "mtime"),
+		]
+		.into_iter()
+		.filter_map(|(changed, name)| changed.then_some(name))
+		.collect()
+	}
 }
 
 #[derive(Debug, Clone)]
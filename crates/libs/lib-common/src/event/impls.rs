@@ -4,10 +4,22 @@ use lib_event_schema::{Field, FieldValue};
 use strum::EnumCount;
 
 use crate::event::{
-	BpfMapEvent, BpfProgLoadEvent, BprmSecurityEvent, CerberusEvent, Event, EventHeader, InetSockEvent, InodeEvent,
-	InodeMutationEvent, ModuleEvent, PtraceAccessCheckEvent, RingBufEvent, SocketEvent, TamperEvent,
+	BpfMapEvent, BpfProgLoadEvent, BprmSecurityEvent, CapableEvent, CapsetEvent, CerberusEvent, CredChangeEvent,
+	DnsEvent, Event, EventHeader, FileEvent, InetAcceptEvent, InetSockEvent, InodeAttrEvent, InodeEvent,
+	InodeMutationEvent, IoUringEvent, MemProtEvent, ModuleEvent, MountEvent, NamespaceEvent, ProcessExecEvent,
+	ProcessExitEvent, ProcessForkEvent, PtraceAccessCheckEvent, RingBufEvent, SocketCreateEvent, SocketEvent,
+	TamperEvent, UdpSendEvent, UnixSocketEvent,
 };
 
+/// Directory part of a full path, `/` for entries in the root.
+fn parent_dir(path: &str) -> &str {
+	match path.rsplit_once('/') {
+		Some(("", _)) => "/",
+		Some((dir, _)) => dir,
+		None => "",
+	}
+}
+
 impl Event for RingBufEvent {
 	fn header(&self) -> &EventHeader {
 		&self.header
@@ -64,6 +76,10 @@ impl Event for ModuleEvent {
 		f[Field::ProcessParentComm.index()] = Some(FieldValue::String(self.header.parent_comm.clone()));
 		f[Field::ModuleName.index()] = Some(FieldValue::String(self.module_name.clone()));
 		f[Field::ModuleOp.index()] = Some(FieldValue::Int(self.op as i64));
+		f[Field::ModuleSource.index()] = Some(FieldValue::Int(self.source as i64));
+		if let Some(path) = &self.path {
+			f[Field::ModulePath.index()] = Some(FieldValue::String(path.clone()));
+		}
 		f
 	}
 }
@@ -88,6 +104,333 @@ impl Event for BprmSecurityEvent {
 	}
 }
 
+impl Event for ProcessForkEvent {
+	fn header(&self) -> &EventHeader {
+		&self.header
+	}
+	fn header_mut(&mut self) -> &mut EventHeader {
+		&mut self.header
+	}
+	fn to_fields(&self) -> [Option<FieldValue>; Field::COUNT] {
+		let mut f = [const { None }; Field::COUNT];
+
+		f[Field::ProcessUid.index()] = Some(FieldValue::Int(self.header.uid as i64));
+		f[Field::ProcessPid.index()] = Some(FieldValue::Int(self.header.pid as i64));
+		f[Field::ProcessTgid.index()] = Some(FieldValue::Int(self.header.tgid as i64));
+		f[Field::ProcessComm.index()] = Some(FieldValue::String(self.header.comm.clone()));
+		f[Field::ProcessParentComm.index()] = Some(FieldValue::String(self.header.parent_comm.clone()));
+		f[Field::ProcessChildPid.index()] = Some(FieldValue::Int(self.child_pid as i64));
+		f[Field::ProcessChildTgid.index()] = Some(FieldValue::Int(self.child_tgid as i64));
+		f
+	}
+}
+
+impl Event for ProcessExecEvent {
+	fn header(&self) -> &EventHeader {
+		&self.header
+	}
+	fn header_mut(&mut self) -> &mut EventHeader {
+		&mut self.header
+	}
+	fn to_fields(&self) -> [Option<FieldValue>; Field::COUNT] {
+		let mut f = [const { None }; Field::COUNT];
+
+		f[Field::ProcessUid.index()] = Some(FieldValue::Int(self.header.uid as i64));
+		f[Field::ProcessPid.index()] = Some(FieldValue::Int(self.header.pid as i64));
+		f[Field::ProcessTgid.index()] = Some(FieldValue::Int(self.header.tgid as i64));
+		f[Field::ProcessComm.index()] = Some(FieldValue::String(self.header.comm.clone()));
+		f[Field::ProcessParentComm.index()] = Some(FieldValue::String(self.header.parent_comm.clone()));
+		f[Field::ProcessExecFilename.index()] = Some(FieldValue::String(self.filename.clone()));
+		f[Field::ProcessArgs.index()] = Some(FieldValue::StringList(self.args.clone()));
+		f[Field::ProcessCmdline.index()] = Some(FieldValue::String(self.cmdline.clone()));
+		f[Field::ProcessEnv.index()] = Some(FieldValue::StringList(self.env.clone()));
+		f[Field::ProcessEnvTruncated.index()] = Some(FieldValue::Bool(self.env_truncated));
+		f[Field::ProcessExecSuccess.index()] = Some(FieldValue::Bool(self.success));
+		f[Field::ProcessExecErrno.index()] = Some(FieldValue::Int(self.errno as i64));
+		f
+	}
+}
+
+impl Event for ProcessExitEvent {
+	fn header(&self) -> &EventHeader {
+		&self.header
+	}
+	fn header_mut(&mut self) -> &mut EventHeader {
+		&mut self.header
+	}
+	fn to_fields(&self) -> [Option<FieldValue>; Field::COUNT] {
+		let mut f = [const { None }; Field::COUNT];
+
+		f[Field::ProcessUid.index()] = Some(FieldValue::Int(self.header.uid as i64));
+		f[Field::ProcessPid.index()] = Some(FieldValue::Int(self.header.pid as i64));
+		f[Field::ProcessTgid.index()] = Some(FieldValue::Int(self.header.tgid as i64));
+		f[Field::ProcessComm.index()] = Some(FieldValue::String(self.header.comm.clone()));
+		f[Field::ProcessParentComm.index()] = Some(FieldValue::String(self.header.parent_comm.clone()));
+		f[Field::ProcessExitCode.index()] = Some(FieldValue::Int(self.exit_code as i64));
+		f[Field::ProcessExitSignal.index()] = Some(FieldValue::Int(self.signal as i64));
+		f
+	}
+}
+
+impl Event for CredChangeEvent {
+	fn header(&self) -> &EventHeader {
+		&self.header
+	}
+	fn header_mut(&mut self) -> &mut EventHeader {
+		&mut self.header
+	}
+	fn to_fields(&self) -> [Option<FieldValue>; Field::COUNT] {
+		let mut f = [const { None }; Field::COUNT];
+
+		f[Field::ProcessUid.index()] = Some(FieldValue::Int(self.header.uid as i64));
+		f[Field::ProcessPid.index()] = Some(FieldValue::Int(self.header.pid as i64));
+		f[Field::ProcessTgid.index()] = Some(FieldValue::Int(self.header.tgid as i64));
+		f[Field::ProcessComm.index()] = Some(FieldValue::String(self.header.comm.clone()));
+		f[Field::ProcessParentComm.index()] = Some(FieldValue::String(self.header.parent_comm.clone()));
+		f[Field::CredOldUid.index()] = Some(FieldValue::Int(self.old_uid as i64));
+		f[Field::CredNewUid.index()] = Some(FieldValue::Int(self.new_uid as i64));
+		f[Field::CredOldEuid.index()] = Some(FieldValue::Int(self.old_euid as i64));
+		f[Field::CredNewEuid.index()] = Some(FieldValue::Int(self.new_euid as i64));
+		f[Field::CredOldGid.index()] = Some(FieldValue::Int(self.old_gid as i64));
+		f[Field::CredNewGid.index()] = Some(FieldValue::Int(self.new_gid as i64));
+		f[Field::CredCapsGained.index()] = Some(FieldValue::IntList(self.caps_gained()));
+		f
+	}
+}
+
+impl Event for IoUringEvent {
+	fn header(&self) -> &EventHeader {
+		&self.header
+	}
+	fn header_mut(&mut self) -> &mut EventHeader {
+		&mut self.header
+	}
+	fn to_fields(&self) -> [Option<FieldValue>; Field::COUNT] {
+		let mut f = [const { None }; Field::COUNT];
+
+		f[Field::ProcessUid.index()] = Some(FieldValue::Int(self.header.uid as i64));
+		f[Field::ProcessPid.index()] = Some(FieldValue::Int(self.header.pid as i64));
+		f[Field::ProcessTgid.index()] = Some(FieldValue::Int(self.header.tgid as i64));
+		f[Field::ProcessComm.index()] = Some(FieldValue::String(self.header.comm.clone()));
+		f[Field::ProcessParentComm.index()] = Some(FieldValue::String(self.header.parent_comm.clone()));
+		f[Field::IoUringOp.index()] = Some(FieldValue::String(Arc::from(self.op_name())));
+		f[Field::IoUringFd.index()] = Some(FieldValue::Int(self.fd as i64));
+		f[Field::IoUringSqpoll.index()] = Some(FieldValue::Bool(self.sqpoll));
+		if let Some(path) = &self.path {
+			f[Field::IoUringPath.index()] = Some(FieldValue::String(path.clone()));
+		}
+		if let Some(addr) = self.addr {
+			f[Field::IoUringAddr.index()] = Some(FieldValue::Ip(addr));
+			f[Field::IoUringPort.index()] = Some(FieldValue::Int(self.port as i64));
+		}
+		f
+	}
+}
+
+impl Event for CapableEvent {
+	fn header(&self) -> &EventHeader {
+		&self.header
+	}
+	fn header_mut(&mut self) -> &mut EventHeader {
+		&mut self.header
+	}
+	fn to_fields(&self) -> [Option<FieldValue>; Field::COUNT] {
+		let mut f = [const { None }; Field::COUNT];
+
+		f[Field::ProcessUid.index()] = Some(FieldValue::Int(self.header.uid as i64));
+		f[Field::ProcessPid.index()] = Some(FieldValue::Int(self.header.pid as i64));
+		f[Field::ProcessTgid.index()] = Some(FieldValue::Int(self.header.tgid as i64));
+		f[Field::ProcessComm.index()] = Some(FieldValue::String(self.header.comm.clone()));
+		f[Field::ProcessParentComm.index()] = Some(FieldValue::String(self.header.parent_comm.clone()));
+		f[Field::CapId.index()] = Some(FieldValue::Int(self.cap as i64));
+		f[Field::CapName.index()] = Some(FieldValue::String(Arc::from(self.cap_name())));
+		f[Field::CapAudit.index()] = Some(FieldValue::Bool(self.audit));
+		f[Field::CapDenied.index()] = Some(FieldValue::Bool(self.denied));
+		f[Field::CapInitUserns.index()] = Some(FieldValue::Bool(self.init_userns));
+		f
+	}
+}
+
+impl Event for CapsetEvent {
+	fn header(&self) -> &EventHeader {
+		&self.header
+	}
+	fn header_mut(&mut self) -> &mut EventHeader {
+		&mut self.header
+	}
+	fn to_fields(&self) -> [Option<FieldValue>; Field::COUNT] {
+		let mut f = [const { None }; Field::COUNT];
+
+		f[Field::ProcessUid.index()] = Some(FieldValue::Int(self.header.uid as i64));
+		f[Field::ProcessPid.index()] = Some(FieldValue::Int(self.header.pid as i64));
+		f[Field::ProcessTgid.index()] = Some(FieldValue::Int(self.header.tgid as i64));
+		f[Field::ProcessComm.index()] = Some(FieldValue::String(self.header.comm.clone()));
+		f[Field::ProcessParentComm.index()] = Some(FieldValue::String(self.header.parent_comm.clone()));
+		f[Field::CapGained.index()] = Some(FieldValue::StringList(
+			self.gained().into_iter().map(Arc::from).collect(),
+		));
+		f[Field::CapEffective.index()] = Some(FieldValue::StringList(
+			self.effective_names().into_iter().map(Arc::from).collect(),
+		));
+		f[Field::CapInitUserns.index()] = Some(FieldValue::Bool(self.init_userns));
+		f
+	}
+}
+
+impl Event for MountEvent {
+	fn header(&self) -> &EventHeader {
+		&self.header
+	}
+	fn header_mut(&mut self) -> &mut EventHeader {
+		&mut self.header
+	}
+	fn to_fields(&self) -> [Option<FieldValue>; Field::COUNT] {
+		let mut f = [const { None }; Field::COUNT];
+
+		f[Field::ProcessUid.index()] = Some(FieldValue::Int(self.header.uid as i64));
+		f[Field::ProcessPid.index()] = Some(FieldValue::Int(self.header.pid as i64));
+		f[Field::ProcessTgid.index()] = Some(FieldValue::Int(self.header.tgid as i64));
+		f[Field::ProcessComm.index()] = Some(FieldValue::String(self.header.comm.clone()));
+		f[Field::ProcessParentComm.index()] = Some(FieldValue::String(self.header.parent_comm.clone()));
+		f[Field::MountOp.index()] = Some(FieldValue::String(Arc::from(self.op_name())));
+		f[Field::MountSource.index()] = self.source.clone().map(FieldValue::String);
+		f[Field::MountTarget.index()] = self.target.clone().map(FieldValue::String);
+		f[Field::MountFstype.index()] = self.fstype.clone().map(FieldValue::String);
+		f[Field::MountFlags.index()] = Some(FieldValue::StringList(
+			self.flag_names().into_iter().map(Arc::from).collect(),
+		));
+		f[Field::MountHostNs.index()] = Some(FieldValue::Bool(self.host_ns));
+		f
+	}
+}
+
+impl Event for NamespaceEvent {
+	fn header(&self) -> &EventHeader {
+		&self.header
+	}
+	fn header_mut(&mut self) -> &mut EventHeader {
+		&mut self.header
+	}
+	fn to_fields(&self) -> [Option<FieldValue>; Field::COUNT] {
+		let mut f = [const { None }; Field::COUNT];
+
+		f[Field::ProcessUid.index()] = Some(FieldValue::Int(self.header.uid as i64));
+		f[Field::ProcessPid.index()] = Some(FieldValue::Int(self.header.pid as i64));
+		f[Field::ProcessTgid.index()] = Some(FieldValue::Int(self.header.tgid as i64));
+		f[Field::ProcessComm.index()] = Some(FieldValue::String(self.header.comm.clone()));
+		f[Field::ProcessParentComm.index()] = Some(FieldValue::String(self.header.parent_comm.clone()));
+		f[Field::NsOp.index()] = Some(FieldValue::String(Arc::from(self.op_name())));
+		f[Field::NsRequested.index()] = Some(FieldValue::StringList(
+			NamespaceEvent::ns_names(self.flags).into_iter().map(Arc::from).collect(),
+		));
+		f[Field::NsChanged.index()] = Some(FieldValue::StringList(
+			NamespaceEvent::ns_names(self.changed).into_iter().map(Arc::from).collect(),
+		));
+		f[Field::NsOldMnt.index()] = Some(FieldValue::Int(self.old_mnt_ns as i64));
+		f[Field::NsNewMnt.index()] = Some(FieldValue::Int(self.header.mnt_ns as i64));
+		f[Field::NsJoinsHost.index()] = Some(FieldValue::Bool(self.joins_host()));
+		f
+	}
+}
+
+impl Event for MemProtEvent {
+	fn header(&self) -> &EventHeader {
+		&self.header
+	}
+	fn header_mut(&mut self) -> &mut EventHeader {
+		&mut self.header
+	}
+	fn to_fields(&self) -> [Option<FieldValue>; Field::COUNT] {
+		let mut f = [const { None }; Field::COUNT];
+
+		f[Field::ProcessUid.index()] = Some(FieldValue::Int(self.header.uid as i64));
+		f[Field::ProcessPid.index()] = Some(FieldValue::Int(self.header.pid as i64));
+		f[Field::ProcessTgid.index()] = Some(FieldValue::Int(self.header.tgid as i64));
+		f[Field::ProcessComm.index()] = Some(FieldValue::String(self.header.comm.clone()));
+		f[Field::ProcessParentComm.index()] = Some(FieldValue::String(self.header.parent_comm.clone()));
+		f[Field::MemOp.index()] = Some(FieldValue::String(Arc::from(self.op_name())));
+		f[Field::MemProt.index()] = Some(FieldValue::String(Arc::from(Self::prot_str(self.prot))));
+		f[Field::MemAnonymous.index()] = Some(FieldValue::Bool(self.anonymous));
+		if let Some(old) = self.old_prot {
+			f[Field::MemOldProt.index()] = Some(FieldValue::String(Arc::from(Self::prot_str(old))));
+		}
+		if let Some(transition) = self.transition() {
+			f[Field::MemTransition.index()] = Some(FieldValue::String(Arc::from(transition)));
+		}
+		if let Some(path) = &self.path {
+			f[Field::MemPath.index()] = Some(FieldValue::String(path.clone()));
+		}
+		f
+	}
+}
+
+impl Event for FileEvent {
+	fn header(&self) -> &EventHeader {
+		&self.header
+	}
+	fn header_mut(&mut self) -> &mut EventHeader {
+		&mut self.header
+	}
+	fn to_fields(&self) -> [Option<FieldValue>; Field::COUNT] {
+		let mut f = [const { None }; Field::COUNT];
+
+		f[Field::ProcessUid.index()] = Some(FieldValue::Int(self.header.uid as i64));
+		f[Field::ProcessPid.index()] = Some(FieldValue::Int(self.header.pid as i64));
+		f[Field::ProcessTgid.index()] = Some(FieldValue::Int(self.header.tgid as i64));
+		f[Field::ProcessComm.index()] = Some(FieldValue::String(self.header.comm.clone()));
+		f[Field::ProcessParentComm.index()] = Some(FieldValue::String(self.header.parent_comm.clone()));
+		f[Field::FilePath.index()] = Some(FieldValue::String(self.path.clone()));
+		f[Field::FileOp.index()] = Some(FieldValue::String(Arc::from(self.op_name())));
+		f[Field::FileAccess.index()] = Some(FieldValue::StringList(
+			self.access_names().into_iter().map(Arc::from).collect(),
+		));
+		f
+	}
+}
+
+impl Event for InodeAttrEvent {
+	fn header(&self) -> &EventHeader {
+		&self.header
+	}
+	fn header_mut(&mut self) -> &mut EventHeader {
+		&mut self.header
+	}
+	fn to_fields(&self) -> [Option<FieldValue>; Field::COUNT] {
+		let mut f = [const { None }; Field::COUNT];
+
+		f[Field::ProcessUid.index()] = Some(FieldValue::Int(self.header.uid as i64));
+		f[Field::ProcessPid.index()] = Some(FieldValue::Int(self.header.pid as i64));
+		f[Field::ProcessTgid.index()] = Some(FieldValue::Int(self.header.tgid as i64));
+		f[Field::ProcessComm.index()] = Some(FieldValue::String(self.header.comm.clone()));
+		f[Field::ProcessParentComm.index()] = Some(FieldValue::String(self.header.parent_comm.clone()));
+		f[Field::InodeFilename.index()] = Some(FieldValue::String(self.filename.clone()));
+		f[Field::InodeAttrOp.index()] = Some(FieldValue::String(Arc::from(self.op_name())));
+		f[Field::InodeAttrChanges.index()] = Some(FieldValue::StringList(
+			self.changes().into_iter().map(Arc::from).collect(),
+		));
+		f[Field::InodeAttrTimesSet.index()] = Some(FieldValue::Bool(self.atime.is_some() || self.mtime.is_some()));
+		if let Some(mode) = self.mode {
+			f[Field::InodeAttrMode.index()] = Some(FieldValue::Int(mode as i64));
+			f[Field::InodeAttrExec.index()] = Some(FieldValue::Bool(mode & 0o111 != 0));
+			f[Field::InodeAttrSuid.index()] = Some(FieldValue::Bool(mode & 0o6000 != 0));
+		}
+		if let Some(uid) = self.uid {
+			f[Field::InodeAttrUid.index()] = Some(FieldValue::Int(uid as i64));
+		}
+		if let Some(gid) = self.gid {
+			f[Field::InodeAttrGid.index()] = Some(FieldValue::Int(gid as i64));
+		}
+		if let Some(mtime) = self.mtime {
+			f[Field::InodeAttrMtime.index()] = Some(FieldValue::Int(mtime));
+		}
+		if let Some(path) = &self.path {
+			f[Field::InodePath.index()] = Some(FieldValue::String(path.clone()));
+			f[Field::InodeDir.index()] = Some(FieldValue::String(Arc::from(parent_dir(path))));
+		}
+		f
+	}
+}
+
 impl Event for InodeMutationEvent {
 	fn header(&self) -> &EventHeader {
 		&self.header
@@ -105,6 +448,16 @@ impl Event for InodeMutationEvent {
 		f[Field::InodeNewFilename.index()] = Some(FieldValue::String(self.new_filename.clone()));
 		f[Field::InodeOldFilename.index()] = Some(FieldValue::String(self.old_filename.clone()));
 		f[Field::InodeMutationType.index()] = Some(FieldValue::Int(self.mutation as i64));
+		if let Some(path) = &self.old_path {
+			f[Field::InodePath.index()] = Some(FieldValue::String(path.clone()));
+			// a symlink target is not a resolved path, its directory means nothing
+			if self.mutation != 2 {
+				f[Field::InodeDir.index()] = Some(FieldValue::String(Arc::from(parent_dir(path))));
+			}
+		}
+		if let Some(path) = &self.new_path {
+			f[Field::InodeNewPath.index()] = Some(FieldValue::String(path.clone()));
+		}
 		f
 	}
 }
@@ -150,6 +503,10 @@ impl Event for InodeEvent {
 		f[Field::ProcessParentComm.index()] = Some(FieldValue::String(self.header.parent_comm.clone()));
 		f[Field::InodeFilename.index()] = Some(FieldValue::String(self.filename.clone()));
 		f[Field::InodeOp.index()] = Some(FieldValue::Int(self.op as i64));
+		if let Some(path) = &self.path {
+			f[Field::InodePath.index()] = Some(FieldValue::String(path.clone()));
+			f[Field::InodeDir.index()] = Some(FieldValue::String(Arc::from(parent_dir(path))));
+		}
 		f
 	}
 }
@@ -195,12 +552,128 @@ impl Event for SocketEvent {
 		f[Field::ProcessComm.index()] = Some(FieldValue::String(self.header.comm.clone()));
 		f[Field::ProcessParentComm.index()] = Some(FieldValue::String(self.header.parent_comm.clone()));
 		f[Field::SocketPort.index()] = Some(FieldValue::Int(self.port as i64));
+		f[Field::SocketAddr.index()] = Some(FieldValue::Ip(self.addr));
 		f[Field::SocketFamily.index()] = Some(FieldValue::Int(self.family as i64));
 		f[Field::SocketOp.index()] = Some(FieldValue::Int(self.op as i64));
 		f
 	}
 }
 
+impl Event for UnixSocketEvent {
+	fn header(&self) -> &EventHeader {
+		&self.header
+	}
+	fn header_mut(&mut self) -> &mut EventHeader {
+		&mut self.header
+	}
+	fn to_fields(&self) -> [Option<FieldValue>; Field::COUNT] {
+		let mut f = [const { None }; Field::COUNT];
+		f[Field::ProcessUid.index()] = Some(FieldValue::Int(self.header.uid as i64));
+		f[Field::ProcessPid.index()] = Some(FieldValue::Int(self.header.pid as i64));
+		f[Field::ProcessTgid.index()] = Some(FieldValue::Int(self.header.tgid as i64));
+		f[Field::ProcessComm.index()] = Some(FieldValue::String(self.header.comm.clone()));
+		f[Field::ProcessParentComm.index()] = Some(FieldValue::String(self.header.parent_comm.clone()));
+		f[Field::SocketFamily.index()] = Some(FieldValue::Int(1));
+		f[Field::SocketOp.index()] = Some(FieldValue::Int(self.op as i64));
+		f[Field::SocketUnixPath.index()] = Some(FieldValue::String(self.path.clone()));
+		f[Field::SocketUnixAbstract.index()] = Some(FieldValue::Bool(self.abstract_ns));
+		f[Field::SocketDenied.index()] = Some(FieldValue::Bool(self.denied));
+		f
+	}
+}
+
+impl Event for SocketCreateEvent {
+	fn header(&self) -> &EventHeader {
+		&self.header
+	}
+	fn header_mut(&mut self) -> &mut EventHeader {
+		&mut self.header
+	}
+	fn to_fields(&self) -> [Option<FieldValue>; Field::COUNT] {
+		let mut f = [const { None }; Field::COUNT];
+		f[Field::ProcessUid.index()] = Some(FieldValue::Int(self.header.uid as i64));
+		f[Field::ProcessPid.index()] = Some(FieldValue::Int(self.header.pid as i64));
+		f[Field::ProcessTgid.index()] = Some(FieldValue::Int(self.header.tgid as i64));
+		f[Field::ProcessComm.index()] = Some(FieldValue::String(self.header.comm.clone()));
+		f[Field::ProcessParentComm.index()] = Some(FieldValue::String(self.header.parent_comm.clone()));
+		f[Field::SocketFamily.index()] = Some(FieldValue::Int(self.family as i64));
+		f[Field::SocketType.index()] = Some(FieldValue::Int(self.sock_type as i64));
+		f[Field::SocketProtocol.index()] = Some(FieldValue::Int(self.protocol as i64));
+		f
+	}
+}
+
+impl Event for UdpSendEvent {
+	fn header(&self) -> &EventHeader {
+		&self.header
+	}
+	fn header_mut(&mut self) -> &mut EventHeader {
+		&mut self.header
+	}
+	fn to_fields(&self) -> [Option<FieldValue>; Field::COUNT] {
+		let mut f = [const { None }; Field::COUNT];
+		f[Field::ProcessUid.index()] = Some(FieldValue::Int(self.header.uid as i64));
+		f[Field::ProcessPid.index()] = Some(FieldValue::Int(self.header.pid as i64));
+		f[Field::ProcessTgid.index()] = Some(FieldValue::Int(self.header.tgid as i64));
+		f[Field::ProcessComm.index()] = Some(FieldValue::String(self.header.comm.clone()));
+		f[Field::ProcessParentComm.index()] = Some(FieldValue::String(self.header.parent_comm.clone()));
+		f[Field::NetworkSport.index()] = Some(FieldValue::Int(self.sport as i64));
+		f[Field::NetworkDport.index()] = Some(FieldValue::Int(self.dport as i64));
+		f[Field::NetworkSaddr.index()] = Some(FieldValue::Ip(self.saddr));
+		f[Field::NetworkDaddr.index()] = Some(FieldValue::Ip(self.daddr));
+		f[Field::NetworkProtocol.index()] = Some(FieldValue::String(Arc::from("UDP")));
+		f
+	}
+}
+
+impl Event for InetAcceptEvent {
+	fn header(&self) -> &EventHeader {
+		&self.header
+	}
+	fn header_mut(&mut self) -> &mut EventHeader {
+		&mut self.header
+	}
+	fn to_fields(&self) -> [Option<FieldValue>; Field::COUNT] {
+		let mut f = [const { None }; Field::COUNT];
+		f[Field::ProcessUid.index()] = Some(FieldValue::Int(self.header.uid as i64));
+		f[Field::ProcessPid.index()] = Some(FieldValue::Int(self.header.pid as i64));
+		f[Field::ProcessTgid.index()] = Some(FieldValue::Int(self.header.tgid as i64));
+		f[Field::ProcessComm.index()] = Some(FieldValue::String(self.header.comm.clone()));
+		f[Field::ProcessParentComm.index()] = Some(FieldValue::String(self.header.parent_comm.clone()));
+		f[Field::NetworkSport.index()] = Some(FieldValue::Int(self.sport as i64));
+		f[Field::NetworkDport.index()] = Some(FieldValue::Int(self.dport as i64));
+		f[Field::NetworkSaddr.index()] = Some(FieldValue::Ip(self.saddr));
+		f[Field::NetworkDaddr.index()] = Some(FieldValue::Ip(self.daddr));
+		f[Field::NetworkProtocol.index()] = Some(FieldValue::String(self.protocol.clone()));
+		f[Field::NetworkDirection.index()] = Some(FieldValue::String(Arc::from("inbound")));
+		f
+	}
+}
+
+impl Event for DnsEvent {
+	fn header(&self) -> &EventHeader {
+		&self.header
+	}
+	fn header_mut(&mut self) -> &mut EventHeader {
+		&mut self.header
+	}
+	fn to_fields(&self) -> [Option<FieldValue>; Field::COUNT] {
+		let mut f = [const { None }; Field::COUNT];
+		f[Field::ProcessUid.index()] = Some(FieldValue::Int(self.header.uid as i64));
+		f[Field::ProcessPid.index()] = Some(FieldValue::Int(self.header.pid as i64));
+		f[Field::ProcessTgid.index()] = Some(FieldValue::Int(self.header.tgid as i64));
+		f[Field::ProcessComm.index()] = Some(FieldValue::String(self.header.comm.clone()));
+		f[Field::ProcessParentComm.index()] = Some(FieldValue::String(self.header.parent_comm.clone()));
+		f[Field::DnsQname.index()] = Some(FieldValue::String(self.qname.clone()));
+		f[Field::DnsQtype.index()] = Some(FieldValue::Int(self.qtype as i64));
+		if let Some(rcode) = self.rcode {
+			f[Field::DnsRcode.index()] = Some(FieldValue::Int(rcode as i64));
+			f[Field::DnsAnswerIp.index()] = Some(FieldValue::IpList(self.answers.clone()));
+		}
+		f
+	}
+}
+
 impl Event for BpfProgLoadEvent {
 	fn header(&self) -> &EventHeader {
 		&self.header
@@ -285,9 +758,26 @@ impl Event for CerberusEvent {
 			CerberusEvent::Inode(e) => e.header(),
 			CerberusEvent::InetSock(e) => e.header(),
 			CerberusEvent::Socket(e) => e.header(),
+			CerberusEvent::UnixSocket(e) => e.header(),
+			CerberusEvent::SocketCreate(e) => e.header(),
+			CerberusEvent::UdpSend(e) => e.header(),
+			CerberusEvent::InetAccept(e) => e.header(),
+			CerberusEvent::Dns(e) => e.header(),
+			CerberusEvent::ProcessFork(e) => e.header(),
+			CerberusEvent::ProcessExec(e) => e.header(),
+			CerberusEvent::ProcessExit(e) => e.header(),
+			CerberusEvent::CredChange(e) => e.header(),
+			CerberusEvent::IoUring(e) => e.header(),
+			CerberusEvent::MemProt(e) => e.header(),
+			CerberusEvent::Capable(e) => e.header(),
+			CerberusEvent::Capset(e) => e.header(),
+			CerberusEvent::Mount(e) => e.header(),
+			CerberusEvent::Namespace(e) => e.header(),
+			CerberusEvent::File(e) => e.header(),
 			CerberusEvent::BpfProgLoad(e) => e.header(),
 			CerberusEvent::BpfMap(e) => e.header(),
 			CerberusEvent::InodeMutation(e) => e.header(),
+			CerberusEvent::InodeAttr(e) => e.header(),
 			CerberusEvent::PtraceAccessCheck(e) => e.header(),
 			CerberusEvent::Tamper(e) => e.header(),
 		}
@@ -301,9 +791,26 @@ impl Event for CerberusEvent {
 			CerberusEvent::Inode(e) => e.header_mut(),
 			CerberusEvent::InetSock(e) => e.header_mut(),
 			CerberusEvent::Socket(e) => e.header_mut(),
+			CerberusEvent::UnixSocket(e) => e.header_mut(),
+			CerberusEvent::SocketCreate(e) => e.header_mut(),
+			CerberusEvent::UdpSend(e) => e.header_mut(),
+			CerberusEvent::InetAccept(e) => e.header_mut(),
+			CerberusEvent::Dns(e) => e.header_mut(),
+			CerberusEvent::ProcessFork(e) => e.header_mut(),
+			CerberusEvent::ProcessExec(e) => e.header_mut(),
+			CerberusEvent::ProcessExit(e) => e.header_mut(),
+			CerberusEvent::CredChange(e) => e.header_mut(),
+			CerberusEvent::IoUring(e) => e.header_mut(),
+			CerberusEvent::MemProt(e) => e.header_mut(),
+			CerberusEvent::Capable(e) => e.header_mut(),
+			CerberusEvent::Capset(e) => e.header_mut(),
+			CerberusEvent::Mount(e) => e.header_mut(),
+			CerberusEvent::Namespace(e) => e.header_mut(),
+			CerberusEvent::File(e) => e.header_mut(),
 			CerberusEvent::BpfProgLoad(e) => e.header_mut(),
 			CerberusEvent::BpfMap(e) => e.header_mut(),
 			CerberusEvent::InodeMutation(e) => e.header_mut(),
+			CerberusEvent::InodeAttr(e) => e.header_mut(),
 			CerberusEvent::PtraceAccessCheck(e) => e.header_mut(),
 			CerberusEvent::Tamper(e) => e.header_mut(),
 		}
@@ -317,9 +824,26 @@ impl Event for CerberusEvent {
 			CerberusEvent::Inode(e) => e.to_fields(),
 			CerberusEvent::InetSock(e) => e.to_fields(),
 			CerberusEvent::Socket(e) => e.to_fields(),
+			CerberusEvent::UnixSocket(e) => e.to_fields(),
+			CerberusEvent::SocketCreate(e) => e.to_fields(),
+			CerberusEvent::UdpSend(e) => e.to_fields(),
+			CerberusEvent::InetAccept(e) => e.to_fields(),
+			CerberusEvent::Dns(e) => e.to_fields(),
+			CerberusEvent::ProcessFork(e) => e.to_fields(),
+			CerberusEvent::ProcessExec(e) => e.to_fields(),
+			CerberusEvent::ProcessExit(e) => e.to_fields(),
+			CerberusEvent::CredChange(e) => e.to_fields(),
+			CerberusEvent::IoUring(e) => e.to_fields(),
+			CerberusEvent::MemProt(e) => e.to_fields(),
+			CerberusEvent::Capable(e) => e.to_fields(),
+			CerberusEvent::Capset(e) => e.to_fields(),
+			CerberusEvent::Mount(e) => e.to_fields(),
+			CerberusEvent::Namespace(e) => e.to_fields(),
+			CerberusEvent::File(e) => e.to_fields(),
 			CerberusEvent::BpfProgLoad(e) => e.to_fields(),
 			CerberusEvent::BpfMap(e) => e.to_fields(),
 			CerberusEvent::InodeMutation(e) => e.to_fields(),
+			CerberusEvent::InodeAttr(e) => e.to_fields(),
 			CerberusEvent::PtraceAccessCheck(e) => e.to_fields(),
 			CerberusEvent::Tamper(e) => e.to_fields(),
 		}
@@ -1,5 +1,24 @@
 pub const FILE_PATH_LEN: usize = 128;
 pub const FILE_NAME_LEN: usize = 64;
+// sun_path of struct sockaddr_un
+pub const UNIX_PATH_LEN: usize = 108;
+pub const DNS_PAYLOAD_LEN: usize = 512;
+pub const EXEC_ARGS_LEN: usize = 1024;
+// one 256 byte slot per loader variable (LD_PRELOAD, LD_LIBRARY_PATH, LD_AUDIT)
+pub const EXEC_ENV_LEN: usize = 768;
+
+// =========================
+// Wire Format
+// =========================
+
+// EventHeader.version; bump on any change to an event layout so userspace
+// refuses eBPF objects built against another one
+pub const EVENT_WIRE_VERSION: u8 = 1;
+// caps of the length-prefixed sections that follow variable-length events
+pub const VAR_NAME_LEN: usize = 256; // NAME_MAX + NUL
+pub const VAR_PATH_LEN: usize = 1024;
+// largest variable-length record: the fixed part and all of its sections
+pub const VAR_EVENT_LEN: usize = 4096;
 // =========================
 // Event Types
 // =========================
@@ -17,6 +36,34 @@ pub const EVT_INODE: u8 = 10;
 pub const EVT_BPF_MAP: u8 = 11;
 pub const EVT_INODE_MUTATE: u8 = 12;
 pub const EVT_PTRACE_ACCESS_CHECK: u8 = 13;
+pub const EVT_SOCKET_CREATE: u8 = 14;
+pub const EVT_UDP_SEND: u8 = 15;
+pub const EVT_DNS: u8 = 16;
+pub const EVT_PROCESS_FORK: u8 = 17;
+pub const EVT_PROCESS_EXEC: u8 = 18;
+pub const EVT_PROCESS_EXIT: u8 = 19;
+pub const EVT_MEM_PROT: u8 = 20;
+pub const EVT_FILE: u8 = 21;
+pub const EVT_INODE_ATTR: u8 = 22;
+pub const EVT_CAP: u8 = 23;
+pub const EVT_MOUNT: u8 = 24;
+pub const EVT_NS: u8 = 25;
+pub const EVT_UNIX_SOCKET: u8 = 26;
+pub const EVT_INET_ACCEPT: u8 = 27;
+
+// =========================
+// Pre-filter Policy
+// =========================
+
+// Event type codes are bit positions in the POLICY_COMM/UID/CGROUP masks, so
+// they have to stay below this.
+pub const EVT_TYPE_COUNT: u32 = 32;
+
+// values of the EVT_POLICY map, indexed by event type
+pub const POLICY_DROP: u32 = 1 << 0;
+pub const POLICY_COMM_ALLOW: u32 = 1 << 1;
+pub const POLICY_UID_ALLOW: u32 = 1 << 2;
+pub const POLICY_CGROUP_ALLOW: u32 = 1 << 3;
 
 // =========================
 // Generic Event Meta Types
@@ -33,6 +80,18 @@ pub const MODULE_OP_INIT: u8 = 0;
 pub const MODULE_OP_DELETE: u8 = 1;
 pub const MODULE_OP_REQUEST: u8 = 2;
 
+pub const MODULE_SOURCE_UNKNOWN: u8 = 0;
+pub const MODULE_SOURCE_INIT_MODULE: u8 = 1;
+pub const MODULE_SOURCE_FINIT_MODULE: u8 = 2;
+
+// =========================
+// Address Families
+// =========================
+
+pub const AF_UNIX: u16 = 1;
+pub const AF_INET: u16 = 2;
+pub const AF_INET6: u16 = 10;
+
 // =========================
 // Socket Operations
 // =========================
@@ -40,6 +99,13 @@ pub const MODULE_OP_REQUEST: u8 = 2;
 pub const SOCKET_OP_BIND: u8 = 0;
 pub const SOCKET_OP_CONNECT: u8 = 1;
 
+// =========================
+// DNS Directions
+// =========================
+
+pub const DNS_DIR_QUERY: u8 = 0;
+pub const DNS_DIR_RESPONSE: u8 = 1;
+
 // =========================
 // Inode Operations
 // =========================
@@ -56,6 +122,101 @@ pub const INODE_MUTATION_RENAME: u8 = 0;
 pub const INODE_MUTATION_LINK: u8 = 1;
 pub const INODE_MUTATION_SYMLINK: u8 = 2;
 
+// dentry levels walked for inode paths, mountpoints included
+pub const DENTRY_PATH_MAX_DEPTH: u32 = 32;
+
+pub const INODE_ATTR_OP_CREATE: u8 = 0;
+pub const INODE_ATTR_OP_SETATTR: u8 = 1;
+
+// struct iattr ia_valid bits
+pub const ATTR_MODE: u32 = 1 << 0;
+pub const ATTR_UID: u32 = 1 << 1;
+pub const ATTR_GID: u32 = 1 << 2;
+pub const ATTR_SIZE: u32 = 1 << 3;
+pub const ATTR_ATIME: u32 = 1 << 4;
+pub const ATTR_MTIME: u32 = 1 << 5;
+pub const ATTR_ATIME_SET: u32 = 1 << 7;
+pub const ATTR_MTIME_SET: u32 = 1 << 8;
+
+// =========================
+// Capabilities
+// =========================
+
+pub const CAP_OP_CAPABLE: u8 = 0;
+pub const CAP_OP_CAPSET: u8 = 1;
+
+// security_capable() opts
+pub const CAP_OPT_NOAUDIT: u32 = 1 << 1;
+
+// =========================
+// Mounts & Namespaces
+// =========================
+
+pub const MOUNT_OP_MOUNT: u8 = 0;
+pub const MOUNT_OP_MOVE: u8 = 1;
+
+pub const FS_TYPE_LEN: usize = 16;
+
+pub const NS_OP_SETNS: u8 = 0;
+pub const NS_OP_UNSHARE: u8 = 1;
+
+// namespace kinds, as the CLONE_NEW* flags of setns(2)/unshare(2)
+pub const CLONE_NEWTIME: u32 = 0x0000_0080;
+pub const CLONE_NEWNS: u32 = 0x0002_0000;
+pub const CLONE_NEWCGROUP: u32 = 0x0200_0000;
+pub const CLONE_NEWUTS: u32 = 0x0400_0000;
+pub const CLONE_NEWIPC: u32 = 0x0800_0000;
+pub const CLONE_NEWUSER: u32 = 0x1000_0000;
+pub const CLONE_NEWPID: u32 = 0x2000_0000;
+pub const CLONE_NEWNET: u32 = 0x4000_0000;
+
+// =========================
+// File Access
+// =========================
+
+pub const FILE_OP_OPEN: u8 = 0;
+pub const FILE_OP_WRITE: u8 = 1;
+
+pub const FILE_ACCESS_READ: u8 = 1 << 0;
+pub const FILE_ACCESS_WRITE: u8 = 1 << 1;
+pub const FILE_ACCESS_APPEND: u8 = 1 << 2;
+pub const FILE_ACCESS_TRUNCATE: u8 = 1 << 3;
+
+// values of the FILE_WATCH / FILE_WATCH_NAMES maps
+pub const FILE_WATCH_WRITES: u8 = 1 << 0;
+pub const FILE_WATCH_READS: u8 = 1 << 1;
+
+// =========================
+// Memory Protection
+// =========================
+
+pub const MEM_OP_MMAP: u8 = 0;
+pub const MEM_OP_MPROTECT: u8 = 1;
+
+// PROT_* and the low VM_* bits share values
+pub const PROT_READ: u32 = 1;
+pub const PROT_WRITE: u32 = 2;
+pub const PROT_EXEC: u32 = 4;
+
+// =========================
+// io_uring
+// =========================
+
+pub const IORING_SETUP_SQPOLL: u32 = 1 << 1;
+
+pub const IORING_OP_ACCEPT: u8 = 13;
+pub const IORING_OP_CONNECT: u8 = 16;
+pub const IORING_OP_OPENAT: u8 = 18;
+pub const IORING_OP_OPENAT2: u8 = 28;
+pub const IORING_OP_RENAMEAT: u8 = 35;
+pub const IORING_OP_UNLINKAT: u8 = 36;
+pub const IORING_OP_MKDIRAT: u8 = 37;
+pub const IORING_OP_SYMLINKAT: u8 = 38;
+pub const IORING_OP_LINKAT: u8 = 39;
+pub const IORING_OP_SOCKET: u8 = 45;
+pub const IORING_OP_BIND: u8 = 56;
+pub const IORING_OP_LISTEN: u8 = 57;
+
 // =========================
 // Ptrace Stages
 // =========================
@@ -1,6 +1,6 @@
 use zerocopy_derive::{FromBytes, Immutable, KnownLayout};
 
-use crate::FILE_PATH_LEN;
+use crate::{DNS_PAYLOAD_LEN, EXEC_ARGS_LEN, EXEC_ENV_LEN, FILE_PATH_LEN, FS_TYPE_LEN, UNIX_PATH_LEN};
 
 // EVT_TYPE
 // 1 => KILL,
@@ -16,6 +16,20 @@ use crate::FILE_PATH_LEN;
 // 11 => BPF_MAP
 // 12 => INODE_MUTATE
 // 13 => PTRACE_ACCESS_CHECK
+// 14 => SOCKET_CREATE
+// 15 => UDP_SEND
+// 16 => DNS
+// 17 => PROCESS_FORK
+// 18 => PROCESS_EXEC
+// 19 => PROCESS_EXIT
+// 20 => MEM_PROT
+// 21 => FILE
+// 22 => INODE_ATTR
+// 23 => CAP
+// 24 => MOUNT
+// 25 => NS
+// 26 => UNIX_SOCKET
+// 27 => INET_ACCEPT
 
 #[repr(C)]
 #[derive(Clone, Copy, Debug, FromBytes, Immutable, KnownLayout)]
@@ -30,7 +44,8 @@ pub struct EventHeader {
 	pub event_type: u8,        // 36..37
 	pub comm: [u8; 16],        // 37..53
 	pub parent_comm: [u8; 16], // 53..69
-	pub _pad0: [u8; 3],        // 69..72
+	pub version: u8,           // 69..70
+	pub _pad0: [u8; 2],        // 70..72
 }
 
 // META_TYPE
@@ -46,6 +61,107 @@ pub struct GenericEvent {
 	pub _pad0: [u8; 2],
 }
 
+/// New process; the header describes the parent. Threads are not reported.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, FromBytes, Immutable, KnownLayout)]
+pub struct ProcessForkEvent {
+	pub header: EventHeader,
+	pub child_pid: u32,
+	pub child_tgid: u32,
+}
+
+/// Exec outcome. On success the header describes the new image, `args` holds
+/// the start of its NUL-separated argv and `env` its loader variables; on
+/// failure they are empty and `ret` holds the negative errno.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, FromBytes, Immutable, KnownLayout)]
+pub struct ProcessExecEvent {
+	pub header: EventHeader,
+	pub filename: [u8; FILE_PATH_LEN],
+	pub args: [u8; EXEC_ARGS_LEN],
+	pub env: [u8; EXEC_ENV_LEN],
+	pub filename_len: u32,
+	pub args_len: u32,
+	pub env_len: u32,
+	pub old_pid: u32,
+	pub ret: i32,
+	pub success: u8,
+	pub env_truncated: u8, // environment not fully scanned or a loader variable cut
+	pub _pad0: [u8; 2],
+}
+
+/// Last thread of a process exited.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, FromBytes, Immutable, KnownLayout)]
+pub struct ProcessExitEvent {
+	pub header: EventHeader,
+	pub exit_code: u32, // wait(2) status: code << 8 | signal
+	pub _pad0: [u8; 4],
+}
+
+/// Credentials committed by `commit_creds` that differ from the task's
+/// current ones in an id or by gaining effective capabilities.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, FromBytes, Immutable, KnownLayout)]
+pub struct CredChangeEvent {
+	pub header: EventHeader,
+	pub old_uid: u32,
+	pub new_uid: u32,
+	pub old_euid: u32,
+	pub new_euid: u32,
+	pub old_gid: u32,
+	pub new_gid: u32,
+	pub old_egid: u32,
+	pub new_egid: u32,
+	pub old_cap_effective: u64,
+	pub new_cap_effective: u64,
+}
+
+/// An io_uring ring being set up (`setup == 1`) or a request submitted to one.
+/// `path` is filled for path-based ops, `addr`/`port` for connect.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, FromBytes, Immutable, KnownLayout)]
+pub struct IoUringEvent {
+	pub header: EventHeader,
+	pub path: [u8; FILE_PATH_LEN],
+	pub addr: [u8; 16], // v4 in the first 4 bytes when family == AF_INET
+	pub opcode: u8,
+	pub setup: u8,
+	pub family: u16,
+	pub port: u16, // network byte order
+	pub _pad0: [u8; 2],
+	pub fd: i32,
+	pub ring_flags: u32, // IORING_SETUP_*
+}
+
+/// An open of a watched path, or the first write through a watched file opened
+/// for writing.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, FromBytes, Immutable, KnownLayout)]
+pub struct FileEvent {
+	pub header: EventHeader,
+	pub path: [u8; FILE_PATH_LEN],
+	pub flags: u32, // f_flags
+	pub op: u8,     // FILE_OP_*
+	pub access: u8, // FILE_ACCESS_* bits
+	pub _pad0: [u8; 2],
+}
+
+/// An executable mapping from `mmap_file`, or `file_mprotect` making a mapping
+/// executable. `old_prot` is only meaningful for mprotect.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, FromBytes, Immutable, KnownLayout)]
+pub struct MemProtEvent {
+	pub header: EventHeader,
+	pub path: [u8; FILE_PATH_LEN],
+	pub prot: u32,     // PROT_*
+	pub old_prot: u32, // PROT_* view of the vma's VM_* flags
+	pub flags: u32,    // MAP_*, mmap only
+	pub op: u8,        // MEM_OP_*
+	pub anonymous: u8,
+	pub _pad0: [u8; 2],
+}
+
 #[repr(C)]
 #[derive(Clone, Copy, Debug, FromBytes, Immutable, KnownLayout)]
 pub struct PtraceAccessCheckEvent {
@@ -64,17 +180,17 @@ pub struct PtraceAccessCheckEvent {
 pub struct ModuleEvent {
 	pub header: EventHeader,
 	pub module_name: [u8; 56],
-	pub op: u8, // 0 = init, 1 = delete, etc.
-	pub _pad0: [u8; 7],
+	pub path: [u8; FILE_PATH_LEN], // finit_module source file, loads only
+	pub op: u8,                    // 0 = init, 1 = delete, 2 = request
+	pub source: u8,                // MODULE_SOURCE_*, loads only
+	pub _pad0: [u8; 6],
 }
 
 #[repr(C)]
 #[derive(Clone, Copy, Debug, FromBytes, Immutable, KnownLayout)]
 pub struct BprmSecurityCheckEvent {
 	pub header: EventHeader,
-	pub filepath: [u8; FILE_PATH_LEN],
-	pub path_len: u32,
-	pub _pad0: [u8; 4],
+	// sections: filepath
 }
 
 #[repr(C)]
@@ -92,54 +208,168 @@ pub struct InetSockSetStateEvent {
 	pub header: EventHeader,
 	pub oldstate: i32,
 	pub newstate: i32,
-	pub saddr: u32,
-	pub daddr: u32,
+	pub saddr: [u8; 16], // v4 in the first 4 bytes when family == AF_INET
+	pub daddr: [u8; 16],
 	pub sport: u16,
 	pub dport: u16,
 	pub protocol: u16,
-	pub _pad0: [u8; 2],
+	pub family: u16,
 }
 
 #[repr(C)]
 #[derive(Clone, Copy, Debug, FromBytes, Immutable, KnownLayout)]
-pub struct SocketConnectEvent {
+pub struct SocketEvent {
 	pub header: EventHeader,
-	pub addr: u32,
+	pub addr: [u8; 16], // v4 in the first 4 bytes when family == AF_INET
 	pub port: u16,
 	pub family: u16,
+	pub op: u8, // 0 = bind, 1 = connect, etc.
+	pub _pad0: [u8; 3],
 }
 
 #[repr(C)]
 #[derive(Clone, Copy, Debug, FromBytes, Immutable, KnownLayout)]
-pub struct SocketEvent {
+pub struct UnixSocketEvent {
 	pub header: EventHeader,
-	pub addr: u32,
-	pub port: u16,
+	pub path: [u8; UNIX_PATH_LEN], // sun_path as given; abstract names start with a NUL
+	pub path_len: u16,
+	pub op: u8,          // SOCKET_OP_*
+	pub abstract_ns: u8, // 1 = abstract namespace name
+	pub denied: u8,      // refused through UNIX_CONNECT_DENY
+	pub _pad0: [u8; 3],
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, FromBytes, Immutable, KnownLayout)]
+pub struct UdpSendEvent {
+	pub header: EventHeader,
+	pub saddr: [u8; 16], // v4 in the first 4 bytes when family == AF_INET
+	pub daddr: [u8; 16],
+	pub sport: u16, // host byte order
+	pub dport: u16,
 	pub family: u16,
-	pub op: u8, // 0 = bind, 1 = connect, etc.
+	pub _pad0: [u8; 2],
+}
+
+/// An inbound connection handed to a process by accept(2).
+#[repr(C)]
+#[derive(Clone, Copy, Debug, FromBytes, Immutable, KnownLayout)]
+pub struct InetAcceptEvent {
+	pub header: EventHeader,
+	pub saddr: [u8; 16], // local; v4 in the first 4 bytes when family == AF_INET
+	pub daddr: [u8; 16], // peer
+	pub sport: u16,      // host byte order
+	pub dport: u16,      // host byte order
+	pub family: u16,
+	pub protocol: u16,
+}
+
+/// Raw DNS message on port 53. Queries come from `socket_sendmsg` with the
+/// sending task in the header; responses come from XDP and carry no task.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, FromBytes, Immutable, KnownLayout)]
+pub struct DnsEvent {
+	pub header: EventHeader,
+	pub saddr: [u8; 16], // v4 in the first 4 bytes when family == AF_INET
+	pub daddr: [u8; 16],
+	pub sport: u16, // host byte order
+	pub dport: u16,
+	pub family: u16,
+	pub payload_len: u16,
+	pub direction: u8, // 0 = query, 1 = response
+	pub _pad0: [u8; 7],
+	pub payload: [u8; DNS_PAYLOAD_LEN],
+}
+
+/// A `socket(2)` call by a task. Sockets the kernel creates for itself (`kern`
+/// set in `security_socket_create`) are not reported, so there is no kern flag.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, FromBytes, Immutable, KnownLayout)]
+pub struct SocketCreateEvent {
+	pub header: EventHeader,
+	pub family: u16,
+	pub _pad0: [u8; 2],
+	pub sock_type: u32, // SOCK_STREAM, SOCK_DGRAM, SOCK_RAW, ...
+	pub protocol: u32,
+	pub _pad1: [u8; 4],
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, FromBytes, Immutable, KnownLayout)]
+pub struct CapEvent {
+	pub header: EventHeader,
+	pub effective: u64,     // capset: requested effective set
+	pub permitted: u64,     // capset: requested permitted set
+	pub old_effective: u64, // capset
+	pub old_permitted: u64, // capset
+	pub cap: u32,           // capable
+	pub opts: u32,          // capable: CAP_OPT_* bits
+	pub op: u8,             // 0 = capable, 1 = capset
+	pub denied: u8,         // capable: refused through CAP_DENY
+	pub init_userns: u8,    // checked against the initial user namespace
+	pub _pad0: [u8; 5],
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, FromBytes, Immutable, KnownLayout)]
+pub struct MountEvent {
+	pub header: EventHeader,
+	pub flags: u64,       // MS_* flags
+	pub op: u8,           // 0 = mount, 1 = move_mount
+	pub source_names: u8, // 1 = source holds dentry names like target, 0 = the device string as given
+	pub _pad0: [u8; 6],
+	pub source: [u8; FILE_PATH_LEN],
+	pub target: [u8; FILE_PATH_LEN], // dentry path names, leaf first
+	pub fstype: [u8; FS_TYPE_LEN],
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, FromBytes, Immutable, KnownLayout)]
+pub struct NsEvent {
+	pub header: EventHeader, // taken after the call, mnt_ns is the new one
+	pub flags: u32,          // CLONE_NEW* flags passed to the call
+	pub changed: u32,        // CLONE_NEW* bits of the namespaces that actually changed
+	pub old_mnt_ns: u32,
+	pub fd: i32, // setns only
+	pub op: u8,  // 0 = setns, 1 = unshare
 	pub _pad0: [u8; 7],
 }
 
 #[repr(C)]
 #[derive(Clone, Copy, Debug, FromBytes, Immutable, KnownLayout)]
 pub struct InodeEvent {
+	pub header: EventHeader,
+	pub op: u8, // 0 = unlink, 1 = mkdir, 2 = rmdir
+	pub _pad0: [u8; 7],
+	// sections: filename, path (dentry path names, leaf first, see read_dentry_path)
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, FromBytes, Immutable, KnownLayout)]
+pub struct InodeAttrEvent {
 	pub header: EventHeader,
 	pub filename: [u8; 64],
 	pub filename_len: u32,
-	pub op: u8, // 0 = unlink, 1 = mkdir, 2 = rmdir
+	pub mode: u32,  // permission bits (create, or setattr with ATTR_MODE)
+	pub uid: u32,   // setattr with ATTR_UID
+	pub gid: u32,   // setattr with ATTR_GID
+	pub valid: u32, // ATTR_* bits; ATTR_MODE only on create
+	pub op: u8,     // 0 = create, 1 = setattr
 	pub _pad0: [u8; 3],
+	pub atime: i64,                // seconds, setattr with ATTR_ATIME_SET
+	pub mtime: i64,                // seconds, setattr with ATTR_MTIME_SET
+	pub path: [u8; FILE_PATH_LEN], // dentry path names, leaf first
 }
 
 #[repr(C)]
 #[derive(Clone, Copy, Debug, FromBytes, Immutable, KnownLayout)]
 pub struct InodeMutationEvent {
 	pub header: EventHeader,
-	pub new_filename: [u8; 64],
-	pub old_filename: [u8; 64],
-	pub new_filename_len: u32,
-	pub old_filename_len: u32,
 	pub mutation: u8, // 0 = rename, 1 = link, 2 = symlink
 	pub _pad0: [u8; 7],
+	// sections: old_filename, new_filename,
+	// old_path (dentry path names, leaf first; symlink: the target as given),
+	// new_path (dentry path names, leaf first)
 }
 
 #[repr(C)]
@@ -153,16 +383,56 @@ pub struct BpfProgLoadEvent {
 	pub _pad0: [u8; 4],
 }
 
+/// Variable-length sections that follow the fixed part of an event, each a
+/// little-endian `u16` length and that many bytes. Ends early on a section
+/// running past the record.
+#[derive(Clone, Copy, Debug)]
+pub struct VarSections<'a>(pub &'a [u8]);
+
+impl<'a> Iterator for VarSections<'a> {
+	type Item = &'a [u8];
+
+	fn next(&mut self) -> Option<Self::Item> {
+		let (len, rest) = self.0.split_first_chunk::<2>()?;
+		let len = u16::from_le_bytes(*len) as usize;
+		if len > rest.len() {
+			self.0 = &[];
+			return None;
+		}
+		let (section, rest) = rest.split_at(len);
+		self.0 = rest;
+		Some(section)
+	}
+}
+
+// only lives between the ring buffer and the conversion to CerberusEvent
+#[allow(clippy::large_enum_variant)]
 #[derive(Clone, Copy, Debug)]
-pub enum EbpfEvent {
+pub enum EbpfEvent<'a> {
 	Generic(GenericEvent),
 	InetSock(InetSockSetStateEvent),
 	Socket(SocketEvent),
-	Inode(InodeEvent),
-	InodeMutation(InodeMutationEvent),
+	UnixSocket(UnixSocketEvent),
+	SocketCreate(SocketCreateEvent),
+	UdpSend(UdpSendEvent),
+	InetAccept(InetAcceptEvent),
+	Dns(DnsEvent),
+	ProcessFork(ProcessForkEvent),
+	ProcessExec(ProcessExecEvent),
+	ProcessExit(ProcessExitEvent),
+	CredChange(CredChangeEvent),
+	IoUring(IoUringEvent),
+	MemProt(MemProtEvent),
+	File(FileEvent),
+	Inode(InodeEvent, VarSections<'a>),
+	InodeMutation(InodeMutationEvent, VarSections<'a>),
+	InodeAttr(InodeAttrEvent),
+	Cap(CapEvent),
+	Mount(MountEvent),
+	Ns(NsEvent),
 	PtraceAccessCheck(PtraceAccessCheckEvent),
 	Module(ModuleEvent),
-	BprmSecurityCheck(BprmSecurityCheckEvent),
+	BprmSecurityCheck(BprmSecurityCheckEvent, VarSections<'a>),
 	BpfProgLoad(BpfProgLoadEvent),
 	BpfMap(BpfMapEvent),
 }
@@ -1,36 +1,20 @@
-use aya_ebpf::{
-	helpers::{
-		bpf_get_current_comm, bpf_get_current_pid_tgid, bpf_get_current_uid_gid, bpf_probe_read_kernel,
-		generated::{bpf_get_current_cgroup_id, bpf_ktime_get_ns},
-	},
-	programs::LsmContext,
-};
+use aya_ebpf::{helpers::bpf_probe_read_kernel, programs::LsmContext};
 // use aya_log_ebpf::error;
 use lib_ebpf_common::{
-	BpfMapEvent, BpfProgLoadEvent, EVT_BPF_MAP, EVT_BPF_PROG_LOAD, EventHeader, FLAG_GPL, FLAG_JITED, FLAG_KPROBE_OVR,
+	BpfMapEvent, BpfProgLoadEvent, EVT_BPF_MAP, EVT_BPF_PROG_LOAD, FLAG_GPL, FLAG_JITED, FLAG_KPROBE_OVR,
 	FLAG_SLEEPABLE,
 };
 
 use crate::{
-	EVT_MAP,
-	utils::{get_mnt_ns, get_parent_comm, get_ppid},
+	utils::{emit, event_header},
 	vmlinux::{bpf_map, bpf_prog},
 };
 
 // LSM_HOOK(int, 0, bpf_prog_load, struct bpf_prog *prog, union bpf_attr *attr, struct bpf_token *token, bool kernel)
 pub fn try_bpf_prog_load(ctx: LsmContext) -> Result<i32, i32> {
-	let uid = bpf_get_current_uid_gid() as u32;
-	let pid = bpf_get_current_pid_tgid() as u32;
-	let tgid = (bpf_get_current_pid_tgid() >> 32) as u32;
-	let ts = unsafe { bpf_ktime_get_ns() };
 	// let task = unsafe { bpf_get_current_task() as *const task_struct };
 	// let parent = unsafe { bpf_probe_read_kernel(&(*task).real_parent).map_err(|e| e as i32)? };
 	// let raw_ppid: i32 = unsafe { bpf_probe_read_kernel(&(*parent).pid).map_err(|e| e as i32)? };
-	let parent_comm = unsafe { get_parent_comm() };
-	let comm = bpf_get_current_comm().unwrap_or([0u8; 16]);
-	let cgroup_id = unsafe { bpf_get_current_cgroup_id() };
-	let mnt_ns = unsafe { get_mnt_ns() };
-	let ppid = unsafe { get_ppid() };
 	let prog: *const bpf_prog = ctx.arg(0);
 
 	if prog.is_null() {
@@ -59,19 +43,7 @@ pub fn try_bpf_prog_load(ctx: LsmContext) -> Result<i32, i32> {
 	}
 
 	let event = BpfProgLoadEvent {
-		header: EventHeader {
-			ts,
-			event_type: EVT_BPF_PROG_LOAD,
-			cgroup_id,
-			mnt_ns,
-			pid,
-			ppid: ppid as u32,
-			uid,
-			tgid,
-			comm,
-			parent_comm,
-			_pad0: [0u8; 3],
-		},
+		header: event_header(EVT_BPF_PROG_LOAD),
 		attach_type,
 		prog_type,
 		tag,
@@ -82,24 +54,15 @@ pub fn try_bpf_prog_load(ctx: LsmContext) -> Result<i32, i32> {
 	// if let Err(e) = EVT_MAP.output::<BpfProgLoadEvent>(&event, 0) {
 	// 	error!(&ctx, "ringbuf write failed: {}", e);
 	// }
-	let _ = EVT_MAP.output::<BpfProgLoadEvent>(&event, 0);
+	let _ = emit(&event);
 	Ok(0)
 }
 
 pub fn try_bpf_map(ctx: LsmContext) -> Result<i32, i32> {
-	let uid = bpf_get_current_uid_gid() as u32;
-	let pid = bpf_get_current_pid_tgid() as u32;
-	let tgid = (bpf_get_current_pid_tgid() >> 32) as u32;
-	let ts = unsafe { bpf_ktime_get_ns() };
 	// let task = unsafe { bpf_get_current_task() as *const task_struct };
 	// let parent = unsafe { bpf_probe_read_kernel(&(*task).real_parent).map_err(|e| e as i32)? };
 	// let raw_ppid: i32 = unsafe { bpf_probe_read_kernel(&(*parent).pid).map_err(|e| e as i32)? };
-	let parent_comm = unsafe { get_parent_comm() };
 
-	let comm = bpf_get_current_comm().unwrap_or([0u8; 16]);
-	let cgroup_id = unsafe { bpf_get_current_cgroup_id() };
-	let mnt_ns = unsafe { get_mnt_ns() };
-	let ppid = unsafe { get_ppid() };
 	let map: *const bpf_map = ctx.arg(0);
 
 	if map.is_null() {
@@ -122,25 +85,13 @@ pub fn try_bpf_map(ctx: LsmContext) -> Result<i32, i32> {
 	}
 
 	let event = BpfMapEvent {
-		header: EventHeader {
-			ts,
-			event_type: EVT_BPF_MAP,
-			cgroup_id,
-			mnt_ns,
-			pid,
-			ppid: ppid as u32,
-			uid,
-			tgid,
-			comm,
-			parent_comm,
-			_pad0: [0u8; 3],
-		},
+		header: event_header(EVT_BPF_MAP),
 		map_id,
 		map_type,
 		map_name,
 	};
 
-	let _ = EVT_MAP.output::<BpfMapEvent>(&event, 0);
+	let _ = emit(&event);
 
 	Ok(0)
 }
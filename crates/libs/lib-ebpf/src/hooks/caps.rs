@@ -0,0 +1,140 @@
+use aya_ebpf::{
+	helpers::{bpf_get_current_pid_tgid, bpf_probe_read_kernel, generated::bpf_ktime_get_ns},
+	macros::map,
+	maps::LruHashMap,
+	programs::LsmContext,
+};
+use lib_ebpf_common::{CAP_OP_CAPABLE, CAP_OP_CAPSET, CapEvent, EVT_CAP};
+
+use crate::{
+	AGENT_TGID, CAP_DENY,
+	utils::{emit, event_header},
+	vmlinux::{cred, kernel_cap_t, user_namespace},
+};
+
+const EPERM: i32 = 1;
+
+/// A (tgid, cap, denied) triple is reported at most once per window.
+const CAP_REPORT_INTERVAL_NS: u64 = 60 * 1_000_000_000;
+
+/// Last report per `tgid << 32 | denied << 16 | cap`. Capability checks run on
+/// every privileged syscall, so each process only reports the first use of a
+/// capability and then once per interval.
+#[map]
+static CAP_SEEN: LruHashMap<u64, u64> = LruHashMap::with_max_entries(8192, 0);
+
+fn empty_event(op: u8) -> CapEvent {
+	CapEvent {
+		header: event_header(EVT_CAP),
+		effective: 0,
+		permitted: 0,
+		old_effective: 0,
+		old_permitted: 0,
+		cap: 0,
+		opts: 0,
+		op,
+		denied: 0,
+		init_userns: 0,
+		_pad0: [0u8; 5],
+	}
+}
+
+unsafe fn is_init_userns(ns: *const user_namespace) -> bool {
+	!ns.is_null() && matches!(unsafe { bpf_probe_read_kernel(&(*ns).level) }, Ok(0))
+}
+
+/// Whether `cap` is refused to the current task, by a host-wide entry or one
+/// for its process that hasn't expired yet.
+fn cap_denied(cap: u32) -> bool {
+	let tgid = bpf_get_current_pid_tgid() >> 32;
+	let agent = unsafe { core::ptr::read_volatile(&AGENT_TGID) } as u64;
+	if tgid == 1 || tgid == agent {
+		return false;
+	}
+
+	let now = unsafe { bpf_ktime_get_ns() };
+	for key in [cap as u64, tgid << 32 | cap as u64] {
+		let Some(&expiry) = (unsafe { CAP_DENY.get(key) }) else {
+			continue;
+		};
+		if expiry == 0 || now < expiry {
+			return true;
+		}
+		let _ = CAP_DENY.remove(key);
+	}
+	false
+}
+
+fn should_report(cap: u32, denied: bool) -> bool {
+	let tgid = bpf_get_current_pid_tgid() >> 32;
+	let key = tgid << 32 | (denied as u64) << 16 | cap as u64;
+	let now = unsafe { bpf_ktime_get_ns() };
+
+	if let Some(last) = unsafe { CAP_SEEN.get(key) }
+		&& now.saturating_sub(*last) < CAP_REPORT_INTERVAL_NS
+	{
+		return false;
+	}
+	let _ = CAP_SEEN.insert(key, now, 0);
+	true
+}
+
+// LSM_HOOK(int, 0, capable, const struct cred *cred, struct user_namespace *ns, int cap, unsigned int opts)
+pub fn try_capable(ctx: LsmContext) -> Result<i32, i32> {
+	let ns: *const user_namespace = ctx.arg(1);
+	let cap: u32 = ctx.arg(2);
+	let opts: u32 = ctx.arg(3);
+	let ret: i32 = ctx.arg(4);
+
+	if ret != 0 {
+		return Ok(ret);
+	}
+
+	let denied = cap_denied(cap);
+
+	if should_report(cap, denied) {
+		let mut event = empty_event(CAP_OP_CAPABLE);
+		event.cap = cap;
+		event.opts = opts;
+		event.denied = denied as u8;
+		event.init_userns = unsafe { is_init_userns(ns) } as u8;
+
+		let _ = emit(&event);
+	}
+
+	if denied {
+		return Ok(-EPERM);
+	}
+
+	Ok(0)
+}
+
+// LSM_HOOK(int, 0, capset, struct cred *new, const struct cred *old, const kernel_cap_t *effective,
+//          const kernel_cap_t *inheritable, const kernel_cap_t *permitted)
+pub fn try_capset(ctx: LsmContext) -> Result<i32, i32> {
+	let old: *const cred = ctx.arg(1);
+	let effective: *const kernel_cap_t = ctx.arg(2);
+	let permitted: *const kernel_cap_t = ctx.arg(4);
+	let ret: i32 = ctx.arg(5);
+
+	if ret != 0 {
+		return Ok(ret);
+	}
+	if old.is_null() || effective.is_null() || permitted.is_null() {
+		return Ok(0);
+	}
+
+	let mut event = empty_event(CAP_OP_CAPSET);
+	unsafe {
+		event.effective = bpf_probe_read_kernel(&(*effective).val).map_err(|_| 0)?;
+		event.permitted = bpf_probe_read_kernel(&(*permitted).val).map_err(|_| 0)?;
+		event.old_effective = bpf_probe_read_kernel(&(*old).cap_effective.val).map_err(|_| 0)?;
+		event.old_permitted = bpf_probe_read_kernel(&(*old).cap_permitted.val).map_err(|_| 0)?;
+		let ns = bpf_probe_read_kernel(&(*old).user_ns).unwrap_or(core::ptr::null_mut());
+		event.init_userns = is_init_userns(ns) as u8;
+	}
+
+	let _ = emit(&event);
+
+	Ok(0)
+}
@@ -0,0 +1,75 @@
+use aya_ebpf::{
+	helpers::{bpf_probe_read_kernel, generated::bpf_get_current_task},
+	programs::ProbeContext,
+};
+use lib_ebpf_common::{CredChangeEvent, EVT_COMMIT_CREDS};
+
+use crate::{
+	utils::{emit, event_header},
+	vmlinux::{cred, task_struct},
+};
+
+struct CredIds {
+	uid: u32,
+	euid: u32,
+	gid: u32,
+	egid: u32,
+	cap_effective: u64,
+}
+
+unsafe fn read_cred(c: *const cred) -> Result<CredIds, i64> {
+	unsafe {
+		Ok(CredIds {
+			uid: bpf_probe_read_kernel(&(*c).uid.val)?,
+			euid: bpf_probe_read_kernel(&(*c).euid.val)?,
+			gid: bpf_probe_read_kernel(&(*c).gid.val)?,
+			egid: bpf_probe_read_kernel(&(*c).egid.val)?,
+			cap_effective: bpf_probe_read_kernel(&(*c).cap_effective.val)?,
+		})
+	}
+}
+
+/// `commit_creds` runs on every exec, so only transitions that change an id or
+/// add an effective capability are reported.
+pub fn try_commit_creds(ctx: ProbeContext) -> Result<u32, i64> {
+	let new: *const cred = ctx.arg(0).ok_or(1)?;
+	if new.is_null() {
+		return Err(1);
+	}
+
+	let task = unsafe { bpf_get_current_task() } as *const task_struct;
+	let old: *const cred = unsafe { bpf_probe_read_kernel(&(*task).real_cred)? };
+	if old.is_null() {
+		return Err(1);
+	}
+
+	let old_ids = unsafe { read_cred(old)? };
+	let new_ids = unsafe { read_cred(new)? };
+
+	let ids_changed = old_ids.uid != new_ids.uid
+		|| old_ids.euid != new_ids.euid
+		|| old_ids.gid != new_ids.gid
+		|| old_ids.egid != new_ids.egid;
+	let caps_gained = new_ids.cap_effective & !old_ids.cap_effective != 0;
+	if !ids_changed && !caps_gained {
+		return Ok(0);
+	}
+
+	let event = CredChangeEvent {
+		header: event_header(EVT_COMMIT_CREDS),
+		old_uid: old_ids.uid,
+		new_uid: new_ids.uid,
+		old_euid: old_ids.euid,
+		new_euid: new_ids.euid,
+		old_gid: old_ids.gid,
+		new_gid: new_ids.gid,
+		old_egid: old_ids.egid,
+		new_egid: new_ids.egid,
+		old_cap_effective: old_ids.cap_effective,
+		new_cap_effective: new_ids.cap_effective,
+	};
+
+	emit(&event)?;
+
+	Ok(0)
+}
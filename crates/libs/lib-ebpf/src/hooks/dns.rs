@@ -0,0 +1,147 @@
+use aya_ebpf::{
+	helpers::{
+		bpf_probe_read_kernel, bpf_probe_read_user_buf,
+		generated::{bpf_ktime_get_ns, bpf_xdp_load_bytes},
+	},
+	macros::map,
+	maps::PerCpuArray,
+	programs::XdpContext,
+};
+use lib_ebpf_common::{DNS_DIR_QUERY, DNS_DIR_RESPONSE, DNS_PAYLOAD_LEN, DnsEvent, EVENT_WIRE_VERSION, EVT_DNS};
+use network_types::udp::UdpHdr;
+
+use crate::{
+	EVT_MAP,
+	utils::{count_drop, emit, event_header, policy_drops_type, ptr_at},
+	vmlinux::{iovec, msghdr},
+};
+
+pub const DNS_PORT: u16 = 53;
+
+const ITER_UBUF: u8 = 0;
+const ITER_IOVEC: u8 = 1;
+
+// DnsEvent is too large for the BPF stack
+#[map(name = "DNS_SCRATCH")]
+static DNS_SCRATCH: PerCpuArray<DnsEvent> = PerCpuArray::with_max_entries(1, 0);
+
+/// Copies the first segment of an outgoing datagram to userspace. Queries fit
+/// in one segment in practice, so scattered payloads are not stitched together.
+pub fn emit_dns_query(
+	msg: *const msghdr,
+	saddr: &[u8; 16],
+	daddr: &[u8; 16],
+	sport: u16,
+	dport: u16,
+	family: u16,
+) -> Result<(), i32> {
+	let evt = DNS_SCRATCH.get_ptr_mut(0).ok_or(0)?;
+	let evt = unsafe { &mut *evt };
+
+	let iter = unsafe { bpf_probe_read_kernel(&(*msg).msg_iter) }?;
+	let (base, len) = match iter.iter_type {
+		ITER_UBUF => {
+			let iov = unsafe { iter.__bindgen_anon_1.__ubuf_iovec };
+			(iov.iov_base as *const u8, iov.iov_len as usize)
+		}
+		ITER_IOVEC => {
+			let iov: iovec =
+				unsafe { bpf_probe_read_kernel(iter.__bindgen_anon_1.__bindgen_anon_1.__bindgen_anon_1.__iov) }?;
+			(iov.iov_base as *const u8, iov.iov_len as usize)
+		}
+		_ => return Ok(()),
+	};
+	if base.is_null() || iter.iov_offset >= len {
+		return Ok(());
+	}
+
+	let mut len = len - iter.iov_offset;
+	if len > DNS_PAYLOAD_LEN {
+		len = DNS_PAYLOAD_LEN;
+	}
+	if len == 0 {
+		return Ok(());
+	}
+	unsafe { bpf_probe_read_user_buf(base.add(iter.iov_offset), &mut evt.payload[..len]) }?;
+
+	evt.header = event_header(EVT_DNS);
+	evt.saddr = *saddr;
+	evt.daddr = *daddr;
+	evt.sport = sport;
+	evt.dport = dport;
+	evt.family = family;
+	evt.payload_len = len as u16;
+	evt.direction = DNS_DIR_QUERY;
+
+	let _ = emit(evt);
+
+	Ok(())
+}
+
+/// Copies a DNS response seen on ingress. XDP runs outside any task, so the
+/// header only carries the timestamp; userspace pairs it with its query.
+pub fn emit_dns_response(
+	ctx: &XdpContext,
+	udp_off: usize,
+	saddr: &[u8; 16],
+	daddr: &[u8; 16],
+	family: u16,
+) -> Result<(), ()> {
+	// no task to match allowlists against; only a dropped type applies
+	if policy_drops_type(EVT_DNS) {
+		return Ok(());
+	}
+
+	let udphdr: *const UdpHdr = unsafe { ptr_at(ctx, udp_off)? };
+	let sport = u16::from_be_bytes(unsafe { (*udphdr).src });
+	if sport != DNS_PORT {
+		return Ok(());
+	}
+	let dport = u16::from_be_bytes(unsafe { (*udphdr).dst });
+
+	let payload_off = udp_off + UdpHdr::LEN;
+	let pkt_len = ctx.data_end() - ctx.data();
+	if payload_off >= pkt_len {
+		return Ok(());
+	}
+	let mut len = pkt_len - payload_off;
+	if len > DNS_PAYLOAD_LEN {
+		len = DNS_PAYLOAD_LEN;
+	}
+	if len == 0 {
+		return Ok(());
+	}
+
+	let evt = DNS_SCRATCH.get_ptr_mut(0).ok_or(())?;
+	let evt = unsafe { &mut *evt };
+
+	let ret = unsafe {
+		bpf_xdp_load_bytes(
+			ctx.ctx,
+			payload_off as u32,
+			evt.payload.as_mut_ptr() as *mut _,
+			len as u32,
+		)
+	};
+	if ret != 0 {
+		return Ok(());
+	}
+
+	evt.header = unsafe { core::mem::zeroed() };
+	evt.header.ts = unsafe { bpf_ktime_get_ns() };
+	evt.header.event_type = EVT_DNS;
+	evt.header.version = EVENT_WIRE_VERSION;
+	evt.saddr = *saddr;
+	evt.daddr = *daddr;
+	evt.sport = sport;
+	evt.dport = dport;
+	evt.family = family;
+	evt.payload_len = len as u16;
+	evt.direction = DNS_DIR_RESPONSE;
+
+	if EVT_MAP.output::<DnsEvent>(evt, 0).is_err() {
+		count_drop(EVT_DNS);
+	}
+
+	Ok(())
+}
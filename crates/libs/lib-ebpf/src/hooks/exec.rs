@@ -1,74 +1,233 @@
 use aya_ebpf::{
 	helpers::{
-		bpf_get_current_comm, bpf_get_current_pid_tgid, bpf_get_current_uid_gid,
-		generated::{bpf_get_current_cgroup_id, bpf_ktime_get_ns},
+		bpf_probe_read_kernel, bpf_probe_read_kernel_str_bytes, bpf_probe_read_user, bpf_probe_read_user_buf,
+		bpf_probe_read_user_str_bytes, generated::bpf_get_current_task,
 	},
 	macros::map,
 	maps::PerCpuArray,
-	programs::LsmContext,
+	programs::{BtfTracePointContext, LsmContext, TracePointContext},
 };
 // use aya_log_ebpf::error;
-use lib_ebpf_common::{BprmSecurityCheckEvent, EVT_BPRM_CHECK_SEC, EventHeader, FILE_PATH_LEN};
+use lib_ebpf_common::{
+	BprmSecurityCheckEvent, EVT_BPRM_CHECK_SEC, EVT_PROCESS_EXEC, EXEC_ARGS_LEN, EXEC_ENV_LEN, FILE_PATH_LEN,
+	ProcessExecEvent,
+};
 
 use crate::{
-	EVT_MAP, LSM_EXEC_DENY,
-	utils::{get_mnt_ns, get_parent_comm, get_ppid, resolve_file_path},
-	vmlinux::linux_binprm,
+	LSM_EXEC_DENY,
+	utils::{VarEvent, emit, event_header},
+	vmlinux::{linux_binprm, task_struct},
 };
 
+const EPERM: i32 = 1;
+
+/// Environment entries looked at per exec; later ones are not scanned.
+const EXEC_ENV_SCAN_MAX: usize = 256;
+/// Room for one loader variable, NUL included. `EXEC_ENV_LEN` holds one per key.
+const EXEC_ENV_VAR_LEN: usize = 256;
+/// Environment variables that change what a binary loads, the only ones shipped.
+const EXEC_ENV_KEYS: [&[u8]; 3] = [b"LD_PRELOAD=", b"LD_LIBRARY_PATH=", b"LD_AUDIT="];
+/// Head of an env entry read to match it against `EXEC_ENV_KEYS`; the read
+/// NUL-terminates, so the longest key needs one byte more than its length.
+const EXEC_ENV_PREFIX_LEN: usize = longest_env_key() + 1;
+const _: () = assert!(EXEC_ENV_PREFIX_LEN == b"LD_LIBRARY_PATH=".len() + 1);
+
+const fn longest_env_key() -> usize {
+	let mut longest = 0;
+	let mut i = 0;
+	while i < EXEC_ENV_KEYS.len() {
+		if EXEC_ENV_KEYS[i].len() > longest {
+			longest = EXEC_ENV_KEYS[i].len();
+		}
+		i += 1;
+	}
+	longest
+}
+
 #[map(name = "FPATH")]
 static FPATH: PerCpuArray<[u8; FILE_PATH_LEN]> = PerCpuArray::with_max_entries(1, 0);
 
 pub fn try_bprm_check_security(ctx: LsmContext) -> Result<i32, i32> {
-	let uid = bpf_get_current_uid_gid() as u32;
-	let pid = bpf_get_current_pid_tgid() as u32;
-	let tgid = (bpf_get_current_pid_tgid() >> 32) as u32;
-	let comm = bpf_get_current_comm().unwrap_or([0u8; 16]);
-	let parent_comm = unsafe { get_parent_comm() };
-
-	let ts = unsafe { bpf_ktime_get_ns() };
-	let ppid = unsafe { get_ppid() };
-	let cgroup_id = unsafe { bpf_get_current_cgroup_id() };
-	let mnt_ns = unsafe { get_mnt_ns() };
 	let bprm: *const linux_binprm = ctx.arg(0);
 
 	if bprm.is_null() {
 		return Ok(0);
 	}
 
-	let buf = FPATH.get_ptr_mut(0).ok_or(0)?;
-	let ret = unsafe { resolve_file_path((*bprm).file, buf) };
-	if ret == 0 {
+	let event = BprmSecurityCheckEvent {
+		header: event_header(EVT_BPRM_CHECK_SEC),
+	};
+	let mut event = VarEvent::new(&event).ok_or(0)?;
+	if !event.push_file_path(unsafe { (*bprm).file }) {
 		return Ok(0);
 	}
 
-	let event = BprmSecurityCheckEvent {
-		header: EventHeader {
-			ts,
-			event_type: EVT_BPRM_CHECK_SEC,
-			cgroup_id,
-			mnt_ns,
-			pid,
-			ppid: ppid as u32,
-			uid,
-			tgid,
-			comm,
-			parent_comm,
-			_pad0: [0u8; 3],
-		},
-		filepath: unsafe { *buf },
-		path_len: ret,
-		_pad0: [0u8; 4],
-	};
+	// deny list keys are fixed-size, so longer paths can't be listed
+	let key = unsafe { &mut *FPATH.get_ptr_mut(0).ok_or(0)? };
+	let listed = event.last_section_key(key) && unsafe { LSM_EXEC_DENY.get(key) }.is_some();
 
 	// if let Err(e) = EVT_MAP.output::<BprmSecurityCheckEvent>(&event, 0) {
 	// 	error!(&ctx, "ringbuf write failed: {}", e);
 	// }
-	let _ = EVT_MAP.output::<BprmSecurityCheckEvent>(&event, 0);
+	let _ = event.emit();
+
+	if listed {
+		return Ok(-EPERM);
+	}
+
+	Ok(0)
+}
+
+#[map(name = "EXEC_SCRATCH")]
+static EXEC_SCRATCH: PerCpuArray<ProcessExecEvent> = PerCpuArray::with_max_entries(1, 0);
+
+/// Fires once the new image is committed, so the header already carries the
+/// new comm.
+pub fn try_sched_process_exec(ctx: BtfTracePointContext) -> Result<u32, u32> {
+	let old_pid: i32 = ctx.arg(1);
+	let bprm: *const linux_binprm = ctx.arg(2);
+	if bprm.is_null() {
+		return Ok(0);
+	}
+
+	let evt = EXEC_SCRATCH.get_ptr_mut(0).ok_or(0u32)?;
+	let evt = unsafe { &mut *evt };
+
+	let filename = unsafe { bpf_probe_read_kernel(&(*bprm).filename) }.map_err(|_| 1u32)?;
+	let filename_len = match unsafe { bpf_probe_read_kernel_str_bytes(filename as *const u8, &mut evt.filename) } {
+		Ok(name) => name.len() as u32,
+		Err(_) => 0,
+	};
+
+	let (args_len, start_stack) = read_args(evt);
+	let (env_len, env_truncated) = match start_stack {
+		Some(start_stack) => read_loader_env(bprm, start_stack, &mut evt.env),
+		None => (0, false),
+	};
+
+	evt.header = event_header(EVT_PROCESS_EXEC);
+	evt.filename_len = filename_len;
+	evt.args_len = args_len;
+	evt.env_len = env_len;
+	evt.env_truncated = env_truncated as u8;
+	evt.old_pid = old_pid as u32;
+	evt.ret = 0;
+	evt.success = 1;
+
+	let _ = emit(evt);
 
-	if unsafe { LSM_EXEC_DENY.get(&event.filepath) }.is_some() {
-		return Err(-1);
+	Ok(0)
+}
+
+/// Shared by `sys_exit_execve` and `sys_exit_execveat`. Successful execs are
+/// reported by `sched_process_exec`, so only failures are emitted here.
+pub fn try_sys_exit_execve(ctx: TracePointContext) -> Result<u32, u32> {
+	let ret: i64 = unsafe { tp_try_read!(ctx, 16) };
+	if ret >= 0 {
+		return Ok(0);
 	}
 
+	let evt = EXEC_SCRATCH.get_ptr_mut(0).ok_or(0u32)?;
+	let evt = unsafe { &mut *evt };
+
+	evt.header = event_header(EVT_PROCESS_EXEC);
+	evt.filename = [0u8; FILE_PATH_LEN];
+	evt.filename_len = 0;
+	evt.args_len = 0;
+	evt.env_len = 0;
+	evt.env_truncated = 0;
+	evt.old_pid = evt.header.pid;
+	evt.ret = ret as i32;
+	evt.success = 0;
+
+	let _ = emit(evt);
+
 	Ok(0)
 }
+
+/// Copies the start of argv from the new image's stack, NUL-separated and cut
+/// off at the buffer. Also returns the stack start, where envp is found.
+fn read_args(evt: &mut ProcessExecEvent) -> (u32, Option<u64>) {
+	let task = unsafe { bpf_get_current_task() } as *const task_struct;
+	let Ok(mm) = (unsafe { bpf_probe_read_kernel(&(*task).mm) }) else {
+		return (0, None);
+	};
+	if mm.is_null() {
+		return (0, None);
+	}
+	let layout = unsafe { &(*mm).__bindgen_anon_1 };
+	let bounds = unsafe {
+		(
+			bpf_probe_read_kernel(&layout.arg_start),
+			bpf_probe_read_kernel(&layout.arg_end),
+			bpf_probe_read_kernel(&layout.start_stack),
+		)
+	};
+	let (arg_start, arg_end, start_stack) = match bounds {
+		(Ok(a), Ok(b), Ok(c)) => (a, b, c),
+		_ => return (0, None),
+	};
+
+	let mut args_len = arg_end.saturating_sub(arg_start) as usize;
+	if args_len > EXEC_ARGS_LEN {
+		args_len = EXEC_ARGS_LEN;
+	}
+	if unsafe { bpf_probe_read_user_buf(arg_start as *const u8, &mut evt.args[..args_len]) }.is_err() {
+		args_len = 0;
+	}
+
+	(args_len as u32, Some(start_stack))
+}
+
+/// Copies the first occurrence of each `EXEC_ENV_KEYS` variable into `env`,
+/// NUL-separated. envp is walked entry by entry, so padding the environment
+/// does not push them out of view. Also returns whether anything was left
+/// out: entries past `EXEC_ENV_SCAN_MAX` or a value cut at `EXEC_ENV_VAR_LEN`.
+fn read_loader_env(bprm: *const linux_binprm, start_stack: u64, env: &mut [u8; EXEC_ENV_LEN]) -> (u32, bool) {
+	let counts = unsafe {
+		(
+			bpf_probe_read_kernel(&(*bprm).argc),
+			bpf_probe_read_kernel(&(*bprm).envc),
+		)
+	};
+	let (Ok(argc), Ok(envc)) = counts else {
+		return (0, false);
+	};
+	let envc = envc.max(0) as usize;
+
+	// start_stack holds argc, then argv and envp, each NULL-terminated
+	let envp = start_stack + 8 * (argc.max(0) as u64 + 2);
+	let mut truncated = envc > EXEC_ENV_SCAN_MAX;
+	let mut found = 0u8;
+	let mut len = 0usize;
+
+	for i in 0..EXEC_ENV_SCAN_MAX {
+		if i >= envc || len + EXEC_ENV_VAR_LEN > EXEC_ENV_LEN {
+			break;
+		}
+
+		let Ok(var) = (unsafe { bpf_probe_read_user((envp + 8 * i as u64) as *const u64) }) else {
+			break;
+		};
+		let mut prefix = [0u8; EXEC_ENV_PREFIX_LEN];
+		let Ok(head) = (unsafe { bpf_probe_read_user_str_bytes(var as *const u8, &mut prefix) }) else {
+			continue;
+		};
+		let Some(key) = EXEC_ENV_KEYS.iter().position(|key| head.starts_with(key)) else {
+			continue;
+		};
+		if found & (1 << key) != 0 {
+			continue;
+		}
+		found |= 1 << key;
+
+		if let Ok(value) =
+			unsafe { bpf_probe_read_user_str_bytes(var as *const u8, &mut env[len..len + EXEC_ENV_VAR_LEN]) }
+		{
+			truncated |= value.len() + 1 >= EXEC_ENV_VAR_LEN;
+			len += value.len() + 1;
+		}
+	}
+
+	(len as u32, truncated)
+}
@@ -0,0 +1,175 @@
+use core::ptr::addr_of_mut;
+
+use aya_ebpf::{
+	helpers::bpf_probe_read_kernel,
+	macros::map,
+	maps::{LruHashMap, PerCpuArray, lpm_trie::Key},
+	programs::LsmContext,
+};
+use lib_ebpf_common::{
+	EVT_FILE, FILE_ACCESS_APPEND, FILE_ACCESS_READ, FILE_ACCESS_TRUNCATE, FILE_ACCESS_WRITE, FILE_NAME_LEN,
+	FILE_OP_OPEN, FILE_OP_WRITE, FILE_PATH_LEN, FILE_WATCH_READS, FILE_WATCH_WRITES, FileEvent,
+};
+
+use crate::{
+	FILE_WATCH, FILE_WATCH_NAMES,
+	utils::{event_header, read_dentry_name, resolve_file_path},
+	vmlinux::file,
+};
+
+const O_ACCMODE: u32 = 0o3;
+const O_WRONLY: u32 = 0o1;
+const O_RDWR: u32 = 0o2;
+const O_TRUNC: u32 = 0o1000;
+const O_APPEND: u32 = 0o2000;
+
+const MAY_WRITE: u32 = 0x2;
+const MAY_APPEND: u32 = 0x8;
+
+/// Watched files opened for writing that have not been written through yet,
+/// keyed by `struct file *`. `file_permission` runs on every read and write,
+/// so it only pays for a hash lookup unless the file is in here. Entries go
+/// on the first write or in `file_free_security`, before the pointer can be
+/// reused for another file.
+#[map]
+static FILE_PENDING_WRITES: LruHashMap<u64, u8> = LruHashMap::with_max_entries(4096, 0);
+
+// LPM key for FILE_WATCH; d_path writes straight into it
+#[map]
+static FILE_WATCH_KEY: PerCpuArray<Key<[u8; FILE_PATH_LEN]>> = PerCpuArray::with_max_entries(1, 0);
+
+fn access_of(flags: u32) -> u8 {
+	let mut access = match flags & O_ACCMODE {
+		O_WRONLY => FILE_ACCESS_WRITE,
+		O_RDWR => FILE_ACCESS_READ | FILE_ACCESS_WRITE,
+		_ => FILE_ACCESS_READ,
+	};
+	if flags & O_APPEND != 0 {
+		access |= FILE_ACCESS_APPEND;
+	}
+	if flags & O_TRUNC != 0 {
+		access |= FILE_ACCESS_TRUNCATE;
+	}
+	access
+}
+
+fn writes(access: u8) -> bool {
+	access & (FILE_ACCESS_WRITE | FILE_ACCESS_APPEND | FILE_ACCESS_TRUNCATE) != 0
+}
+
+/// Resolves the path of `file` into the per-CPU LPM key. Returns the watch
+/// flags of the file's basename or longest matching prefix, 0 if unwatched.
+unsafe fn watch_flags(file: *mut file, key: *mut Key<[u8; FILE_PATH_LEN]>) -> u8 {
+	let data = unsafe { addr_of_mut!((*key).data) };
+	unsafe { *data = [0u8; FILE_PATH_LEN] };
+	if unsafe { resolve_file_path(file, data) } == 0 {
+		return 0;
+	}
+
+	let mut name = [0u8; FILE_NAME_LEN];
+	let dentry = unsafe { bpf_probe_read_kernel(&(*file).__bindgen_anon_1.f_path.dentry) };
+	if let Ok(dentry) = dentry
+		&& read_dentry_name(dentry, &mut name).is_some()
+		&& let Some(flags) = unsafe { FILE_WATCH_NAMES.get(name) }
+	{
+		return *flags;
+	}
+
+	unsafe { (*key).prefix_len = (FILE_PATH_LEN * 8) as u32 };
+	match FILE_WATCH.get(unsafe { &*key }) {
+		Some(flags) => *flags,
+		None => 0,
+	}
+}
+
+fn emit(path: &[u8; FILE_PATH_LEN], flags: u32, op: u8, access: u8) {
+	let event = FileEvent {
+		header: event_header(EVT_FILE),
+		path: *path,
+		flags,
+		op,
+		access,
+		_pad0: [0u8; 2],
+	};
+
+	let _ = crate::utils::emit(&event);
+}
+
+// LSM_HOOK(int, 0, file_open, struct file *file)
+pub fn try_file_open(ctx: LsmContext) -> Result<i32, i32> {
+	let file: *mut file = ctx.arg(0);
+	let ret: i32 = ctx.arg(1);
+
+	if ret != 0 {
+		return Ok(ret);
+	}
+	if file.is_null() {
+		return Ok(0);
+	}
+
+	let flags = unsafe { bpf_probe_read_kernel(&(*file).f_flags) }.map_err(|_| 0)?;
+	let access = access_of(flags);
+
+	let key = FILE_WATCH_KEY.get_ptr_mut(0).ok_or(0)?;
+	let watch = unsafe { watch_flags(file, key) };
+	let wanted = if writes(access) {
+		FILE_WATCH_WRITES
+	} else {
+		FILE_WATCH_READS
+	};
+	if watch & wanted == 0 {
+		return Ok(0);
+	}
+
+	if writes(access) {
+		let _ = FILE_PENDING_WRITES.insert(file as u64, 0, 0);
+	}
+
+	emit(unsafe { &(*key).data }, flags, FILE_OP_OPEN, access);
+
+	Ok(0)
+}
+
+// LSM_HOOK(int, 0, file_permission, struct file *file, int mask)
+pub fn try_file_permission(ctx: LsmContext) -> Result<i32, i32> {
+	let file: *mut file = ctx.arg(0);
+	let mask: u32 = ctx.arg(1);
+	let ret: i32 = ctx.arg(2);
+
+	if ret != 0 {
+		return Ok(ret);
+	}
+	if mask & (MAY_WRITE | MAY_APPEND) == 0 || file.is_null() {
+		return Ok(0);
+	}
+	if unsafe { FILE_PENDING_WRITES.get(file as u64) }.is_none() {
+		return Ok(0);
+	}
+	let _ = FILE_PENDING_WRITES.remove(file as u64);
+
+	let flags = unsafe { bpf_probe_read_kernel(&(*file).f_flags) }.map_err(|_| 0)?;
+	let key = FILE_WATCH_KEY.get_ptr_mut(0).ok_or(0)?;
+	let data = unsafe { addr_of_mut!((*key).data) };
+	unsafe { *data = [0u8; FILE_PATH_LEN] };
+	if unsafe { resolve_file_path(file, data) } == 0 {
+		return Ok(0);
+	}
+
+	emit(
+		unsafe { &*data },
+		flags,
+		FILE_OP_WRITE,
+		access_of(flags) & !FILE_ACCESS_READ,
+	);
+
+	Ok(0)
+}
+
+// LSM_HOOK(void, LSM_RET_VOID, file_free_security, struct file *file)
+pub fn try_file_free_security(ctx: LsmContext) -> Result<i32, i32> {
+	let file: *mut file = ctx.arg(0);
+
+	let _ = FILE_PENDING_WRITES.remove(file as u64);
+
+	Ok(0)
+}
@@ -1,319 +1,214 @@
 use core::ffi::c_char;
 
-use aya_ebpf::{
-	helpers::{
-		bpf_get_current_comm, bpf_get_current_pid_tgid, bpf_get_current_uid_gid, bpf_probe_read_kernel_str_bytes,
-		generated::{bpf_get_current_cgroup_id, bpf_ktime_get_ns},
-	},
-	programs::LsmContext,
-};
+use aya_ebpf::{helpers::bpf_probe_read_kernel, macros::map, maps::PerCpuArray, programs::LsmContext};
 use lib_ebpf_common::{
-	EVT_INODE, EVT_INODE_MUTATE, EventHeader, FILE_NAME_LEN, INODE_MUTATION_LINK, INODE_MUTATION_RENAME,
-	INODE_MUTATION_SYMLINK, INODE_OP_MKDIR, INODE_OP_RMDIR, INODE_OP_UNLINK, InodeEvent, InodeMutationEvent,
+	ATTR_ATIME_SET, ATTR_GID, ATTR_MODE, ATTR_MTIME_SET, ATTR_UID, EVT_INODE, EVT_INODE_ATTR, EVT_INODE_MUTATE,
+	FILE_NAME_LEN, FILE_PATH_LEN, INODE_ATTR_OP_CREATE, INODE_ATTR_OP_SETATTR, INODE_MUTATION_LINK,
+	INODE_MUTATION_RENAME, INODE_MUTATION_SYMLINK, INODE_OP_MKDIR, INODE_OP_RMDIR, INODE_OP_UNLINK, InodeAttrEvent,
+	InodeEvent, InodeMutationEvent, VAR_NAME_LEN, VAR_PATH_LEN,
 };
 
 use crate::{
-	EVT_MAP,
-	utils::{get_mnt_ns, get_parent_comm, get_ppid, read_dentry_name},
-	vmlinux::dentry,
+	utils::{VarEvent, emit, event_header, read_dentry_name, read_dentry_path},
+	vmlinux::{dentry, iattr},
 };
 
-// LSM_HOOK(int, 0, inode_unlink, struct inode *dir, struct dentry *dentry)
-pub fn try_inode_unlink(ctx: LsmContext) -> Result<i32, i32> {
-	let uid = bpf_get_current_uid_gid() as u32;
-	let pid = bpf_get_current_pid_tgid() as u32;
-	let tgid = (bpf_get_current_pid_tgid() >> 32) as u32;
-	let comm_raw = bpf_get_current_comm().unwrap_or([0u8; 16]);
-	let ts = unsafe { bpf_ktime_get_ns() };
-	let ppid = unsafe { get_ppid() };
-	let parent_comm = unsafe { get_parent_comm() };
-
-	let cgroup_id = unsafe { bpf_get_current_cgroup_id() };
-	let mnt_ns = unsafe { get_mnt_ns() };
-	let mut filename = [0u8; FILE_NAME_LEN];
-
-	let dentry: *const dentry = ctx.arg(1);
-
-	let len = match read_dentry_name(dentry, &mut filename) {
-		Some(l) => l,
-		None => return Ok(0),
-	};
+// the event carries a full path and no longer fits on the BPF stack
+#[map]
+static INODE_ATTR_SCRATCH: PerCpuArray<InodeAttrEvent> = PerCpuArray::with_max_entries(1, 0);
 
+fn emit_inode(op: u8, dentry: *const dentry) -> Result<i32, i32> {
 	let event = InodeEvent {
-		header: EventHeader {
-			ts,
-			event_type: EVT_INODE,
-			cgroup_id,
-			mnt_ns,
-			pid,
-			ppid: ppid as u32,
-			uid,
-			tgid,
-			comm: comm_raw,
-			parent_comm,
-			_pad0: [0u8; 3],
-		},
-		filename,
-		filename_len: len,
-		op: INODE_OP_UNLINK,
-		_pad0: [0u8; 3],
+		header: event_header(EVT_INODE),
+		op,
+		_pad0: [0u8; 7],
 	};
+	let mut event = VarEvent::new(&event).ok_or(0)?;
+	if !event.push_dentry_name(dentry) {
+		return Ok(0);
+	}
+	event.push_dentry_path(dentry);
+
+	let _ = event.emit();
 
-	// if let Err(e) = EVT_MAP.output::<InodeEvent>(&event, 0) {
-	// 	error!(&ctx, "ringbuf write failed: {}", e);
-	// }
-	let _ = EVT_MAP.output::<InodeEvent>(&event, 0);
 	Ok(0)
 }
 
-pub fn try_inode_rename(ctx: LsmContext) -> Result<i32, i32> {
-	let uid = bpf_get_current_uid_gid() as u32;
-	let pid = bpf_get_current_pid_tgid() as u32;
-	let tgid = (bpf_get_current_pid_tgid() >> 32) as u32;
-	let comm = bpf_get_current_comm().unwrap_or([0u8; 16]);
-	let ts = unsafe { bpf_ktime_get_ns() };
-	let ppid = unsafe { get_ppid() };
-	let parent_comm = unsafe { get_parent_comm() };
-
-	let cgroup_id = unsafe { bpf_get_current_cgroup_id() };
-	let mnt_ns = unsafe { get_mnt_ns() };
-	let mut old_filename = [0u8; FILE_NAME_LEN];
-	let mut new_filename = [0u8; FILE_NAME_LEN];
-
-	let old_dentry: *const dentry = ctx.arg(1);
-	let new_dentry: *const dentry = ctx.arg(3);
-
-	let old_filename_len = match read_dentry_name(old_dentry, &mut old_filename) {
-		Some(l) => l,
-		None => return Ok(0),
-	};
-
-	let new_filename_len = match read_dentry_name(new_dentry, &mut new_filename) {
-		Some(l) => l,
-		None => return Ok(0),
-	};
-
+/// `old_name` replaces `old_dentry` for symlinks, whose source is the target
+/// string rather than an existing file.
+fn emit_mutation(
+	mutation: u8,
+	old_dentry: *const dentry,
+	old_name: *const c_char,
+	new_dentry: *const dentry,
+) -> Result<i32, i32> {
 	let event = InodeMutationEvent {
-		header: EventHeader {
-			ts,
-			event_type: EVT_INODE_MUTATE,
-			cgroup_id,
-			mnt_ns,
-			pid,
-			ppid: ppid as u32,
-			uid,
-			tgid,
-			comm,
-			parent_comm,
-			_pad0: [0u8; 3],
-		},
-		new_filename,
-		old_filename,
-		new_filename_len,
-		old_filename_len,
-		mutation: INODE_MUTATION_RENAME,
+		header: event_header(EVT_INODE_MUTATE),
+		mutation,
 		_pad0: [0u8; 7],
 	};
+	let mut event = VarEvent::new(&event).ok_or(0)?;
 
-	let _ = EVT_MAP.output::<InodeMutationEvent>(&event, 0);
+	// sections: old_filename, new_filename, old_path, new_path
+	if old_name.is_null() {
+		if !event.push_dentry_name(old_dentry) {
+			return Ok(0);
+		}
+	} else if !event.push_str::<VAR_NAME_LEN>(old_name as *const u8) {
+		return Ok(0);
+	}
+	if !event.push_dentry_name(new_dentry) {
+		return Ok(0);
+	}
+	if old_name.is_null() {
+		event.push_dentry_path(old_dentry);
+	} else {
+		event.push_str::<VAR_PATH_LEN>(old_name as *const u8);
+	}
+	event.push_dentry_path(new_dentry);
+
+	let _ = event.emit();
 
 	Ok(0)
 }
 
+// LSM_HOOK(int, 0, inode_unlink, struct inode *dir, struct dentry *dentry)
+pub fn try_inode_unlink(ctx: LsmContext) -> Result<i32, i32> {
+	let dentry: *const dentry = ctx.arg(1);
+
+	emit_inode(INODE_OP_UNLINK, dentry)
+}
+
+// LSM_HOOK(int, 0, inode_mkdir, struct inode *dir, struct dentry *dentry, umode_t mode)
 pub fn try_inode_mkdir(ctx: LsmContext) -> Result<i32, i32> {
-	let uid = bpf_get_current_uid_gid() as u32;
-	let pid = bpf_get_current_pid_tgid() as u32;
-	let tgid = (bpf_get_current_pid_tgid() >> 32) as u32;
-	let comm = bpf_get_current_comm().unwrap_or([0u8; 16]);
-	let ts = unsafe { bpf_ktime_get_ns() };
-	let ppid = unsafe { get_ppid() };
-	let parent_comm = unsafe { get_parent_comm() };
-
-	let cgroup_id = unsafe { bpf_get_current_cgroup_id() };
-	let mnt_ns = unsafe { get_mnt_ns() };
-	let mut filename = [0u8; FILE_NAME_LEN];
+	let dentry: *const dentry = ctx.arg(1);
 
+	emit_inode(INODE_OP_MKDIR, dentry)
+}
+
+// LSM_HOOK(int, 0, inode_rmdir, struct inode *dir, struct dentry *dentry)
+pub fn try_inode_rmdir(ctx: LsmContext) -> Result<i32, i32> {
 	let dentry: *const dentry = ctx.arg(1);
-	let len = match read_dentry_name(dentry, &mut filename) {
-		Some(l) => l,
-		None => return Ok(0),
-	};
 
-	let event = InodeEvent {
-		header: EventHeader {
-			ts,
-			event_type: EVT_INODE,
-			cgroup_id,
-			mnt_ns,
-			pid,
-			ppid: ppid as u32,
-			uid,
-			tgid,
-			comm,
-			parent_comm,
-			_pad0: [0u8; 3],
-		},
-		filename,
-		filename_len: len,
-		op: INODE_OP_MKDIR,
-		_pad0: [0u8; 3],
-	};
+	emit_inode(INODE_OP_RMDIR, dentry)
+}
 
-	let _ = EVT_MAP.output::<InodeEvent>(&event, 0);
+// LSM_HOOK(int, 0, inode_rename, struct inode *old_dir, struct dentry *old_dentry,
+//          struct inode *new_dir, struct dentry *new_dentry)
+pub fn try_inode_rename(ctx: LsmContext) -> Result<i32, i32> {
+	let old_dentry: *const dentry = ctx.arg(1);
+	let new_dentry: *const dentry = ctx.arg(3);
 
-	Ok(0)
+	emit_mutation(INODE_MUTATION_RENAME, old_dentry, core::ptr::null(), new_dentry)
 }
 
+// LSM_HOOK(int, 0, inode_link, struct dentry *old_dentry, struct inode *dir, struct dentry *new_dentry)
 pub fn try_inode_link(ctx: LsmContext) -> Result<i32, i32> {
-	let uid = bpf_get_current_uid_gid() as u32;
-	let pid = bpf_get_current_pid_tgid() as u32;
-	let tgid = (bpf_get_current_pid_tgid() >> 32) as u32;
-	let comm = bpf_get_current_comm().unwrap_or([0u8; 16]);
-	let ts = unsafe { bpf_ktime_get_ns() };
-	let ppid = unsafe { get_ppid() };
-	let cgroup_id = unsafe { bpf_get_current_cgroup_id() };
-	let mnt_ns = unsafe { get_mnt_ns() };
-	let parent_comm = unsafe { get_parent_comm() };
-
-	let mut old_filename = [0u8; FILE_NAME_LEN];
-	let mut new_filename = [0u8; FILE_NAME_LEN];
-
 	let old_dentry: *const dentry = ctx.arg(0);
 	let new_dentry: *const dentry = ctx.arg(2);
 
-	let old_filename_len = match read_dentry_name(old_dentry, &mut old_filename) {
-		Some(l) => l,
-		None => return Ok(0),
-	};
+	emit_mutation(INODE_MUTATION_LINK, old_dentry, core::ptr::null(), new_dentry)
+}
 
-	let new_filename_len = match read_dentry_name(new_dentry, &mut new_filename) {
-		Some(l) => l,
-		None => return Ok(0),
-	};
+// LSM_HOOK(int, 0, inode_symlink, struct inode *dir, struct dentry *dentry, const char *old_name)
+pub fn try_inode_symlink(ctx: LsmContext) -> Result<i32, i32> {
+	let new_dentry: *const dentry = ctx.arg(1);
+	let old_name: *const c_char = ctx.arg(2);
 
-	let event = InodeMutationEvent {
-		header: EventHeader {
-			ts,
-			event_type: EVT_INODE_MUTATE,
-			cgroup_id,
-			mnt_ns,
-			pid,
-			ppid: ppid as u32,
-			uid,
-			tgid,
-			comm,
-			parent_comm,
-			_pad0: [0u8; 3],
-		},
-		new_filename,
-		old_filename,
-		new_filename_len,
-		old_filename_len,
-		mutation: INODE_MUTATION_LINK,
-		_pad0: [0u8; 7],
-	};
+	if old_name.is_null() {
+		return Ok(0);
+	}
 
-	let _ = EVT_MAP.output::<InodeMutationEvent>(&event, 0);
+	emit_mutation(INODE_MUTATION_SYMLINK, core::ptr::null(), old_name, new_dentry)
+}
 
-	Ok(0)
+/// setattr changes worth an event: ownership, permissions and explicit
+/// timestamps (`touch -t`, `utimensat` with given times). Size changes and
+/// "set to now" timestamp updates are routine and left out.
+const ATTR_REPORTED: u32 = ATTR_MODE | ATTR_UID | ATTR_GID | ATTR_ATIME_SET | ATTR_MTIME_SET;
+
+fn inode_attr_event(op: u8, dentry: *const dentry) -> Option<&'static mut InodeAttrEvent> {
+	let event = unsafe { &mut *INODE_ATTR_SCRATCH.get_ptr_mut(0)? };
+
+	event.filename = [0u8; FILE_NAME_LEN];
+	event.filename_len = read_dentry_name(dentry, &mut event.filename)?;
+	event.header = event_header(EVT_INODE_ATTR);
+	event.mode = 0;
+	event.uid = 0;
+	event.gid = 0;
+	event.valid = 0;
+	event.op = op;
+	event._pad0 = [0u8; 3];
+	event.atime = 0;
+	event.mtime = 0;
+	event.path = [0u8; FILE_PATH_LEN];
+	unsafe { read_dentry_path(dentry, core::ptr::null(), &mut event.path) };
+
+	Some(event)
 }
 
-pub fn try_inode_symlink(ctx: LsmContext) -> Result<i32, i32> {
-	let uid = bpf_get_current_uid_gid() as u32;
-	let pid = bpf_get_current_pid_tgid() as u32;
-	let tgid = (bpf_get_current_pid_tgid() >> 32) as u32;
-	let comm = bpf_get_current_comm().unwrap_or([0u8; 16]);
-	let ts = unsafe { bpf_ktime_get_ns() };
-	let ppid = unsafe { get_ppid() };
-	let cgroup_id = unsafe { bpf_get_current_cgroup_id() };
-	let mnt_ns = unsafe { get_mnt_ns() };
-	let parent_comm = unsafe { get_parent_comm() };
-
-	let mut old_filename = [0u8; FILE_NAME_LEN];
-	let mut new_filename = [0u8; FILE_NAME_LEN];
+// LSM_HOOK(int, 0, inode_create, struct inode *dir, struct dentry *dentry, umode_t mode)
+pub fn try_inode_create(ctx: LsmContext) -> Result<i32, i32> {
+	let dentry: *const dentry = ctx.arg(1);
+	let mode: u16 = ctx.arg(2);
+	let ret: i32 = ctx.arg(3);
 
-	let new_dentry: *const dentry = ctx.arg(1);
-	let old_name: *const c_char = ctx.arg(2);
+	if ret != 0 {
+		return Ok(ret);
+	}
 
-	let new_filename_len = match read_dentry_name(new_dentry, &mut new_filename) {
-		Some(l) => l,
+	let event = match inode_attr_event(INODE_ATTR_OP_CREATE, dentry) {
+		Some(e) => e,
 		None => return Ok(0),
 	};
+	event.mode = mode as u32 & 0o7777;
+	event.valid = ATTR_MODE;
 
-	let old_filename_len = unsafe {
-		match bpf_probe_read_kernel_str_bytes(old_name as *const u8, &mut old_filename) {
-			Ok(s) => s.len() as u32,
-			Err(_) => return Ok(0),
-		}
-	};
-
-	let event = InodeMutationEvent {
-		header: EventHeader {
-			ts,
-			event_type: EVT_INODE_MUTATE,
-			cgroup_id,
-			mnt_ns,
-			pid,
-			ppid: ppid as u32,
-			uid,
-			tgid,
-			comm,
-			parent_comm,
-			_pad0: [0u8; 3],
-		},
-		new_filename,
-		old_filename,
-		new_filename_len,
-		old_filename_len,
-		mutation: INODE_MUTATION_SYMLINK,
-		_pad0: [0u8; 7],
-	};
-
-	let _ = EVT_MAP.output::<InodeMutationEvent>(&event, 0);
+	let _ = emit(event);
 
 	Ok(0)
 }
 
-pub fn try_inode_rmdir(ctx: LsmContext) -> Result<i32, i32> {
-	let uid = bpf_get_current_uid_gid() as u32;
-	let pid = bpf_get_current_pid_tgid() as u32;
-	let tgid = (bpf_get_current_pid_tgid() >> 32) as u32;
-	let comm = bpf_get_current_comm().unwrap_or([0u8; 16]);
-	let ts = unsafe { bpf_ktime_get_ns() };
-	let ppid = unsafe { get_ppid() };
-	let parent_comm = unsafe { get_parent_comm() };
-
-	let cgroup_id = unsafe { bpf_get_current_cgroup_id() };
-	let mnt_ns = unsafe { get_mnt_ns() };
-	let mut filename = [0u8; FILE_NAME_LEN];
-
+// LSM_HOOK(int, 0, inode_setattr, struct mnt_idmap *idmap, struct dentry *dentry, struct iattr *attr)
+pub fn try_inode_setattr(ctx: LsmContext) -> Result<i32, i32> {
 	let dentry: *const dentry = ctx.arg(1);
-	let len = match read_dentry_name(dentry, &mut filename) {
-		Some(l) => l,
+	let attr: *const iattr = ctx.arg(2);
+	let ret: i32 = ctx.arg(3);
+
+	if ret != 0 {
+		return Ok(ret);
+	}
+	if attr.is_null() {
+		return Ok(0);
+	}
+
+	let attr = unsafe { bpf_probe_read_kernel(attr) }.map_err(|_| 0)?;
+	if attr.ia_valid & ATTR_REPORTED == 0 {
+		return Ok(0);
+	}
+
+	let event = match inode_attr_event(INODE_ATTR_OP_SETATTR, dentry) {
+		Some(e) => e,
 		None => return Ok(0),
 	};
-
-	let event = InodeEvent {
-		header: EventHeader {
-			ts,
-			event_type: EVT_INODE,
-			cgroup_id,
-			mnt_ns,
-			pid,
-			ppid: ppid as u32,
-			uid,
-			tgid,
-			comm,
-			parent_comm,
-			_pad0: [0u8; 3],
-		},
-		filename,
-		filename_len: len,
-		op: INODE_OP_RMDIR,
-		_pad0: [0u8; 3],
-	};
-
-	let _ = EVT_MAP.output::<InodeEvent>(&event, 0);
+	event.valid = attr.ia_valid;
+	if attr.ia_valid & ATTR_MODE != 0 {
+		event.mode = attr.ia_mode as u32 & 0o7777;
+	}
+	if attr.ia_valid & ATTR_UID != 0 {
+		event.uid = unsafe { attr.__bindgen_anon_1.ia_uid.val };
+	}
+	if attr.ia_valid & ATTR_GID != 0 {
+		event.gid = unsafe { attr.__bindgen_anon_2.ia_gid.val };
+	}
+	if attr.ia_valid & ATTR_ATIME_SET != 0 {
+		event.atime = attr.ia_atime.tv_sec;
+	}
+	if attr.ia_valid & ATTR_MTIME_SET != 0 {
+		event.mtime = attr.ia_mtime.tv_sec;
+	}
+
+	let _ = emit(event);
 
 	Ok(0)
 }
@@ -0,0 +1,135 @@
+use aya_ebpf::{
+	helpers::{bpf_probe_read_kernel, bpf_probe_read_kernel_str_bytes, bpf_probe_read_user},
+	programs::BtfTracePointContext,
+};
+use lib_ebpf_common::{
+	AF_INET, AF_INET6, EVT_IO_URING, FILE_PATH_LEN, IORING_OP_ACCEPT, IORING_OP_BIND, IORING_OP_CONNECT,
+	IORING_OP_LINKAT, IORING_OP_LISTEN, IORING_OP_MKDIRAT, IORING_OP_OPENAT, IORING_OP_OPENAT2, IORING_OP_RENAMEAT,
+	IORING_OP_SOCKET, IORING_OP_SYMLINKAT, IORING_OP_UNLINKAT, IoUringEvent,
+};
+
+use crate::{
+	utils::{emit, event_header},
+	vmlinux::{io_kiocb, sockaddr, sockaddr_in, sockaddr_in6},
+};
+
+fn empty_event(opcode: u8, setup: u8, fd: i32, ring_flags: u32) -> IoUringEvent {
+	IoUringEvent {
+		header: event_header(EVT_IO_URING),
+		path: [0u8; FILE_PATH_LEN],
+		addr: [0u8; 16],
+		opcode,
+		setup,
+		family: 0,
+		port: 0,
+		_pad0: [0u8; 2],
+		fd,
+		ring_flags,
+	}
+}
+
+/// Ops that open files or set up connections. Data transfer ops are left out:
+/// they only act on descriptors one of these (or a syscall) produced, and one
+/// event per read/write would swamp the ring buffer.
+fn reported(opcode: u8) -> bool {
+	matches!(
+		opcode,
+		IORING_OP_ACCEPT
+			| IORING_OP_CONNECT
+			| IORING_OP_OPENAT
+			| IORING_OP_OPENAT2
+			| IORING_OP_RENAMEAT
+			| IORING_OP_UNLINKAT
+			| IORING_OP_MKDIRAT
+			| IORING_OP_SYMLINKAT
+			| IORING_OP_LINKAT
+			| IORING_OP_SOCKET
+			| IORING_OP_BIND
+			| IORING_OP_LISTEN
+	)
+}
+
+/// Same as the LSM sockaddr reader, but the SQE's address is still a user
+/// pointer when `io_uring_submit_req` fires.
+unsafe fn read_user_sockaddr(addr: *const sockaddr, evt: &mut IoUringEvent) {
+	match unsafe { bpf_probe_read_user(&(*addr).sa_family) } {
+		Ok(AF_INET) => {
+			if let Ok(addr_in) = unsafe { bpf_probe_read_user(addr as *const sockaddr_in) } {
+				evt.addr[..4].copy_from_slice(&addr_in.sin_addr.s_addr.to_ne_bytes());
+				evt.family = AF_INET;
+				evt.port = addr_in.sin_port;
+			}
+		}
+		Ok(AF_INET6) => {
+			if let Ok(addr_in6) = unsafe { bpf_probe_read_user(addr as *const sockaddr_in6) } {
+				evt.addr = unsafe { addr_in6.sin6_addr.in6_u.u6_addr8 };
+				evt.family = AF_INET6;
+				evt.port = addr_in6.sin6_port;
+			}
+		}
+		_ => {}
+	}
+}
+
+/// Fires for every SQE after prep, from the submitting task or the ring's
+/// SQPOLL thread, which belongs to the same thread group. Op-specific state
+/// lives in `req->cmd.data`: the path-based ops keep a `struct filename *` at
+/// offset 8, connect keeps the user sockaddr pointer at offset 0.
+pub fn try_io_uring_submit_req(ctx: BtfTracePointContext) -> Result<u32, u32> {
+	let req: *const io_kiocb = ctx.arg(0);
+	if req.is_null() {
+		return Ok(0);
+	}
+
+	let opcode = unsafe { bpf_probe_read_kernel(&(*req).opcode) }.map_err(|_| 0u32)?;
+	if !reported(opcode) {
+		return Ok(0);
+	}
+
+	let fd = unsafe { bpf_probe_read_kernel(&(*req).cqe.__bindgen_anon_1.fd) }.unwrap_or(-1);
+	let ring_flags = unsafe {
+		bpf_probe_read_kernel(&(*req).ctx)
+			.and_then(|ring| bpf_probe_read_kernel(&(*ring).__bindgen_anon_1.flags))
+			.unwrap_or(0)
+	};
+
+	let mut evt = empty_event(opcode, 0, fd, ring_flags);
+	let data = unsafe { &(*req).__bindgen_anon_1.cmd.data } as *const u8;
+
+	match opcode {
+		IORING_OP_CONNECT => {
+			let addr = unsafe { bpf_probe_read_kernel(data as *const *const sockaddr) }.unwrap_or(core::ptr::null());
+			if !addr.is_null() {
+				unsafe { read_user_sockaddr(addr, &mut evt) };
+			}
+		}
+		IORING_OP_OPENAT | IORING_OP_OPENAT2 | IORING_OP_RENAMEAT | IORING_OP_UNLINKAT | IORING_OP_MKDIRAT
+		| IORING_OP_SYMLINKAT | IORING_OP_LINKAT => {
+			// struct filename starts with `const char *name`
+			let filename =
+				unsafe { bpf_probe_read_kernel(data.add(8) as *const *const *const u8) }.unwrap_or(core::ptr::null());
+			if !filename.is_null() {
+				let name = unsafe { bpf_probe_read_kernel(filename) }.unwrap_or(core::ptr::null());
+				if !name.is_null() {
+					let _ = unsafe { bpf_probe_read_kernel_str_bytes(name, &mut evt.path) };
+				}
+			}
+		}
+		_ => {}
+	}
+
+	let _ = emit(&evt);
+
+	Ok(0)
+}
+
+/// `io_uring_create(int fd, void *ctx, u32 sq_entries, u32 cq_entries, u32 flags)`
+pub fn try_io_uring_create(ctx: BtfTracePointContext) -> Result<u32, u32> {
+	let fd: i32 = ctx.arg(0);
+	let flags: u32 = ctx.arg(4);
+
+	let evt = empty_event(0, 1, fd, flags);
+	let _ = emit(&evt);
+
+	Ok(0)
+}
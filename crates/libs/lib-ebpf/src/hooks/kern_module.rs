@@ -1,18 +1,53 @@
 use aya_ebpf::{
 	helpers::{
-		bpf_get_current_comm, bpf_get_current_pid_tgid, bpf_get_current_uid_gid, bpf_probe_read_kernel,
-		generated::{bpf_get_current_cgroup_id, bpf_ktime_get_ns},
+		bpf_get_current_pid_tgid, bpf_probe_read_kernel, bpf_probe_read_kernel_str_bytes, bpf_probe_read_user_str_bytes,
 	},
-	programs::ProbeContext,
+	macros::map,
+	maps::{LruHashMap, PerCpuArray},
+	programs::{LsmContext, ProbeContext, TracePointContext},
+};
+use lib_ebpf_common::{
+	EVT_MODULE, FILE_PATH_LEN, MODULE_OP_DELETE, MODULE_OP_INIT, MODULE_OP_REQUEST, MODULE_SOURCE_FINIT_MODULE,
+	MODULE_SOURCE_INIT_MODULE, MODULE_SOURCE_UNKNOWN, ModuleEvent,
 };
-use lib_ebpf_common::{EVT_MODULE, EventHeader, MODULE_OP_INIT, ModuleEvent};
 
 use crate::{
-	EVT_MAP,
-	utils::{get_mnt_ns, get_parent_comm, get_ppid},
-	vmlinux::module,
+	utils::{emit, event_header, resolve_file_path},
+	vmlinux::{file, module},
 };
 
+// enum kernel_read_file_id / enum kernel_load_data_id
+const READING_MODULE: u32 = 2;
+const LOADING_MODULE: u32 = 2;
+
+#[repr(C)]
+#[derive(Clone, Copy)]
+struct ModuleSource {
+	path: [u8; FILE_PATH_LEN],
+	source: u8,
+	_pad0: [u8; 7],
+}
+
+/// Where the image handed to `do_init_module` came from, keyed by thread id.
+/// Filled by the kernel_read_file/kernel_load_data LSM hooks, which run in the
+/// same thread before the module is parsed and its name is known.
+#[map]
+static MODULE_SOURCES: LruHashMap<u32, ModuleSource> = LruHashMap::with_max_entries(1024, 0);
+
+#[map]
+static MODULE_SOURCE_SCRATCH: PerCpuArray<ModuleSource> = PerCpuArray::with_max_entries(1, 0);
+
+fn empty_event(op: u8) -> ModuleEvent {
+	ModuleEvent {
+		header: event_header(EVT_MODULE),
+		module_name: [0u8; 56],
+		path: [0u8; FILE_PATH_LEN],
+		op,
+		source: MODULE_SOURCE_UNKNOWN,
+		_pad0: [0u8; 6],
+	}
+}
+
 pub fn try_do_init_module(ctx: ProbeContext) -> Result<u32, i64> {
 	let module: *const module = ctx.arg(0).ok_or(1)?;
 
@@ -20,40 +55,103 @@ pub fn try_do_init_module(ctx: ProbeContext) -> Result<u32, i64> {
 		return Err(1);
 	}
 
-	let uid = bpf_get_current_uid_gid() as u32;
-	let pid = bpf_get_current_pid_tgid() as u32;
-	let tgid = (bpf_get_current_pid_tgid() >> 32) as u32;
-	let comm = bpf_get_current_comm().unwrap_or([0u8; 16]);
-	let ts = unsafe { bpf_ktime_get_ns() };
-	let ppid = unsafe { get_ppid() };
-	let parent_comm = unsafe { get_parent_comm() };
-
-	let cgroup_id = unsafe { bpf_get_current_cgroup_id() };
-	let mnt_ns = unsafe { get_mnt_ns() };
 	let name_i8 = unsafe { bpf_probe_read_kernel(&(*module).name)? };
 
-	let module_name: [u8; 56] = unsafe { core::mem::transmute(name_i8) };
-
-	let event = ModuleEvent {
-		header: EventHeader {
-			ts,
-			event_type: EVT_MODULE,
-			cgroup_id,
-			mnt_ns,
-			pid,
-			ppid: ppid as u32,
-			uid,
-			tgid,
-			comm,
-			parent_comm,
-			_pad0: [0u8; 3],
-		},
-		module_name,
-		op: MODULE_OP_INIT,
-		_pad0: [0u8; 7],
-	};
-
-	EVT_MAP.output::<ModuleEvent>(&event, 0)?;
+	let mut event = empty_event(MODULE_OP_INIT);
+	event.module_name = unsafe { core::mem::transmute::<[i8; 56], [u8; 56]>(name_i8) };
+
+	let tid = bpf_get_current_pid_tgid() as u32;
+	if let Some(src) = unsafe { MODULE_SOURCES.get(tid) } {
+		event.path = src.path;
+		event.source = src.source;
+		let _ = MODULE_SOURCES.remove(tid);
+	}
+
+	emit(&event)?;
+
+	Ok(0)
+}
+
+/// `finit_module(2)`: the image is read from `file`.
+pub fn try_kernel_read_file(ctx: LsmContext) -> Result<i32, i32> {
+	let file: *mut file = ctx.arg(0);
+	let id: u32 = ctx.arg(1);
+	let ret: i32 = ctx.arg(3);
+
+	if ret != 0 {
+		return Ok(ret);
+	}
+	if id != READING_MODULE || file.is_null() {
+		return Ok(0);
+	}
+
+	let src = unsafe { &mut *MODULE_SOURCE_SCRATCH.get_ptr_mut(0).ok_or(0)? };
+	src.path = [0u8; FILE_PATH_LEN];
+	unsafe { resolve_file_path(file, &mut src.path) };
+	src.source = MODULE_SOURCE_FINIT_MODULE;
+	let _ = MODULE_SOURCES.insert(bpf_get_current_pid_tgid() as u32, src, 0);
+
+	Ok(0)
+}
+
+/// `init_module(2)`: the image is copied from user memory, so there is no path.
+pub fn try_kernel_load_data(ctx: LsmContext) -> Result<i32, i32> {
+	let id: u32 = ctx.arg(0);
+	let ret: i32 = ctx.arg(2);
+
+	if ret != 0 {
+		return Ok(ret);
+	}
+	if id != LOADING_MODULE {
+		return Ok(0);
+	}
+
+	let src = unsafe { &mut *MODULE_SOURCE_SCRATCH.get_ptr_mut(0).ok_or(0)? };
+	src.path = [0u8; FILE_PATH_LEN];
+	src.source = MODULE_SOURCE_INIT_MODULE;
+	let _ = MODULE_SOURCES.insert(bpf_get_current_pid_tgid() as u32, src, 0);
+
+	Ok(0)
+}
+
+/// Unload attempts, reported on entry so a refused unload of a security module
+/// is still visible.
+pub fn try_sys_enter_delete_module(ctx: TracePointContext) -> Result<u32, i64> {
+	// sys_enter_delete_module: const char __user *name_user at offset 16
+	let name: *const u8 = unsafe { ctx.read_at(16)? };
+	if name.is_null() {
+		return Ok(0);
+	}
+
+	let mut event = empty_event(MODULE_OP_DELETE);
+	if unsafe { bpf_probe_read_user_str_bytes(name, &mut event.module_name) }.is_err() {
+		return Ok(0);
+	}
+
+	emit(&event)?;
+
+	Ok(0)
+}
+
+/// Kernel-initiated `request_module()` (e.g. `net-pf-*` or `fs-*` aliases pulled
+/// in by a socket() or mount() from an unprivileged task).
+pub fn try_kernel_module_request(ctx: LsmContext) -> Result<i32, i32> {
+	let kmod_name: *const u8 = ctx.arg(0);
+	let ret: i32 = ctx.arg(1);
+
+	if ret != 0 {
+		return Ok(ret);
+	}
+	if kmod_name.is_null() {
+		return Ok(0);
+	}
+
+	let mut event = empty_event(MODULE_OP_REQUEST);
+	if unsafe { bpf_probe_read_kernel_str_bytes(kmod_name, &mut event.module_name) }.is_err() {
+		return Ok(0);
+	}
+
+	let _ = emit(&event);
 
 	Ok(0)
 }
@@ -0,0 +1,127 @@
+use aya_ebpf::{
+	helpers::{bpf_get_current_pid_tgid, bpf_probe_read_kernel, generated::bpf_ktime_get_ns},
+	macros::map,
+	maps::LruHashMap,
+	programs::LsmContext,
+};
+use lib_ebpf_common::{
+	EVT_MEM_PROT, FILE_PATH_LEN, MEM_OP_MMAP, MEM_OP_MPROTECT, MemProtEvent, PROT_EXEC, PROT_READ, PROT_WRITE,
+};
+
+use crate::{
+	utils::{event_header, resolve_file_path},
+	vmlinux::{file, vm_area_struct},
+};
+
+const PROT_MASK: u32 = PROT_READ | PROT_WRITE | PROT_EXEC;
+/// A (tgid, transition) mprotect is reported again after this long.
+const MPROTECT_REFRESH_NS: u64 = 60 * 1_000_000_000;
+
+#[repr(C)]
+#[derive(Clone, Copy)]
+struct MprotectKey {
+	tgid: u32,
+	old_prot: u16,
+	prot: u16,
+}
+
+/// Last report time per (process, transition). JITs flip pages between
+/// writable and executable all the time, so each kind of flip is reported once
+/// per interval.
+#[map]
+static MPROTECTS: LruHashMap<MprotectKey, u64> = LruHashMap::with_max_entries(8192, 0);
+
+/// Unlinked files (memfd, deleted payloads) have no link left; mapping one
+/// executable is fileless execution even though it is file backed.
+unsafe fn is_unlinked(file: *const file) -> bool {
+	let inode = match unsafe { bpf_probe_read_kernel(&(*file).f_inode) } {
+		Ok(inode) if !inode.is_null() => inode,
+		_ => return false,
+	};
+	matches!(
+		unsafe { bpf_probe_read_kernel(&(*inode).__bindgen_anon_1.i_nlink) },
+		Ok(0)
+	)
+}
+
+fn emit(file: *mut file, op: u8, prot: u32, old_prot: u32, flags: u32) {
+	let mut event = MemProtEvent {
+		header: event_header(EVT_MEM_PROT),
+		path: [0u8; FILE_PATH_LEN],
+		prot,
+		old_prot,
+		flags,
+		op,
+		anonymous: file.is_null() as u8,
+		_pad0: [0u8; 2],
+	};
+
+	if !file.is_null() {
+		unsafe { resolve_file_path(file, &mut event.path) };
+	}
+
+	let _ = crate::utils::emit(&event);
+}
+
+/// Executable mappings that are anonymous, writable at the same time, or
+/// backed by an unlinked file. Read-only executable mappings of files on disk
+/// are every shared library load and are left out.
+pub fn try_mmap_file(ctx: LsmContext) -> Result<i32, i32> {
+	let file: *mut file = ctx.arg(0);
+	let prot = ctx.arg::<u64>(2) as u32 & PROT_MASK;
+	let flags = ctx.arg::<u64>(3) as u32;
+	let ret: i32 = ctx.arg(4);
+
+	if ret != 0 {
+		return Ok(ret);
+	}
+	if prot & PROT_EXEC == 0 {
+		return Ok(0);
+	}
+	if !file.is_null() && prot & PROT_WRITE == 0 && !unsafe { is_unlinked(file) } {
+		return Ok(0);
+	}
+
+	emit(file, MEM_OP_MMAP, prot, 0, flags);
+
+	Ok(0)
+}
+
+/// Mappings gaining execute permission, e.g. the RW -> RX flip of a shellcode
+/// loader or a JIT.
+pub fn try_file_mprotect(ctx: LsmContext) -> Result<i32, i32> {
+	let vma: *const vm_area_struct = ctx.arg(0);
+	let prot = ctx.arg::<u64>(2) as u32 & PROT_MASK;
+	let ret: i32 = ctx.arg(3);
+
+	if ret != 0 {
+		return Ok(ret);
+	}
+	if vma.is_null() || prot & PROT_EXEC == 0 {
+		return Ok(0);
+	}
+
+	let vm_flags = unsafe { bpf_probe_read_kernel(&(*vma).__bindgen_anon_2.vm_flags) }.map_err(|_| 0)?;
+	let old_prot = vm_flags as u32 & PROT_MASK;
+	if old_prot & PROT_EXEC != 0 && prot & PROT_WRITE == 0 {
+		return Ok(0);
+	}
+
+	let now = unsafe { bpf_ktime_get_ns() };
+	let key = MprotectKey {
+		tgid: (bpf_get_current_pid_tgid() >> 32) as u32,
+		old_prot: old_prot as u16,
+		prot: prot as u16,
+	};
+	if let Some(last) = unsafe { MPROTECTS.get(key) }
+		&& now.saturating_sub(*last) < MPROTECT_REFRESH_NS
+	{
+		return Ok(0);
+	}
+	let _ = MPROTECTS.insert(key, now, 0);
+
+	let file = unsafe { bpf_probe_read_kernel(&(*vma).vm_file) }.unwrap_or(core::ptr::null_mut());
+	emit(file, MEM_OP_MPROTECT, prot, old_prot, 0);
+
+	Ok(0)
+}
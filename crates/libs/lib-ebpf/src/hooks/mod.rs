@@ -1,13 +1,29 @@
 mod bpf;
+mod caps;
+mod creds;
+mod dns;
 mod exec;
+mod file;
 mod fs;
+mod io_uring;
 mod kern_module;
+mod mem;
+mod mount;
 mod network;
+mod ns;
 mod process;
 
 pub use bpf::*;
+pub use caps::*;
+pub use creds::*;
+pub use dns::*;
 pub use exec::*;
+pub use file::*;
 pub use fs::*;
+pub use io_uring::*;
 pub use kern_module::*;
+pub use mem::*;
+pub use mount::*;
 pub use network::*;
+pub use ns::*;
 pub use process::*;
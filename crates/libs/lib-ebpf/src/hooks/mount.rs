@@ -0,0 +1,121 @@
+use core::ffi::c_char;
+
+use aya_ebpf::{
+	helpers::{bpf_probe_read_kernel, bpf_probe_read_kernel_str_bytes},
+	macros::map,
+	maps::PerCpuArray,
+	programs::LsmContext,
+};
+use lib_ebpf_common::{EVT_MOUNT, FILE_PATH_LEN, FS_TYPE_LEN, MOUNT_OP_MOUNT, MOUNT_OP_MOVE, MountEvent};
+
+use crate::{
+	utils::{emit, event_header, read_dentry_path},
+	vmlinux::path,
+};
+
+#[map]
+static MOUNT_SCRATCH: PerCpuArray<MountEvent> = PerCpuArray::with_max_entries(1, 0);
+
+fn mount_event(op: u8, target: *const path) -> Option<&'static mut MountEvent> {
+	if target.is_null() {
+		return None;
+	}
+	let event = unsafe { &mut *MOUNT_SCRATCH.get_ptr_mut(0)? };
+
+	event.header = event_header(EVT_MOUNT);
+	event.flags = 0;
+	event.op = op;
+	event.source_names = 0;
+	event._pad0 = [0u8; 6];
+	event.source = [0u8; FILE_PATH_LEN];
+	event.target = [0u8; FILE_PATH_LEN];
+	event.fstype = [0u8; FS_TYPE_LEN];
+
+	let dentry = unsafe { bpf_probe_read_kernel(&(*target).dentry) }.ok()?;
+	let mnt = unsafe { bpf_probe_read_kernel(&(*target).mnt) }.ok()?;
+	unsafe { read_dentry_path(dentry, mnt, &mut event.target) };
+
+	Some(event)
+}
+
+/// Name of the filesystem type `p` lives on, e.g. `ext4` or `overlay`.
+unsafe fn read_fs_type(p: *const path, buf: &mut [u8; FS_TYPE_LEN]) {
+	unsafe {
+		let Ok(mnt) = bpf_probe_read_kernel(&(*p).mnt) else {
+			return;
+		};
+		let Ok(sb) = bpf_probe_read_kernel(&(*mnt).mnt_sb) else {
+			return;
+		};
+		let Ok(fs_type) = bpf_probe_read_kernel(&(*sb).s_type) else {
+			return;
+		};
+		if let Ok(name) = bpf_probe_read_kernel(&(*fs_type).name) {
+			let _ = bpf_probe_read_kernel_str_bytes(name as *const u8, buf);
+		}
+	}
+}
+
+// LSM_HOOK(int, 0, sb_mount, const char *dev_name, const struct path *path,
+//          const char *type, unsigned long flags, void *data)
+pub fn try_sb_mount(ctx: LsmContext) -> Result<i32, i32> {
+	let dev_name: *const c_char = ctx.arg(0);
+	let target: *const path = ctx.arg(1);
+	let fstype: *const c_char = ctx.arg(2);
+	let flags: u64 = ctx.arg(3);
+	let ret: i32 = ctx.arg(5);
+
+	if ret != 0 {
+		return Ok(ret);
+	}
+
+	let event = match mount_event(MOUNT_OP_MOUNT, target) {
+		Some(e) => e,
+		None => return Ok(0),
+	};
+	event.flags = flags;
+	// both were copied in from userspace by the mount syscall
+	if !dev_name.is_null() {
+		let _ = unsafe { bpf_probe_read_kernel_str_bytes(dev_name as *const u8, &mut event.source) };
+	}
+	if !fstype.is_null() {
+		let _ = unsafe { bpf_probe_read_kernel_str_bytes(fstype as *const u8, &mut event.fstype) };
+	}
+
+	let _ = emit(event);
+
+	Ok(0)
+}
+
+// LSM_HOOK(int, 0, move_mount, const struct path *from_path, const struct path *to_path)
+pub fn try_move_mount(ctx: LsmContext) -> Result<i32, i32> {
+	let from: *const path = ctx.arg(0);
+	let to: *const path = ctx.arg(1);
+	let ret: i32 = ctx.arg(2);
+
+	if ret != 0 {
+		return Ok(ret);
+	}
+	if from.is_null() {
+		return Ok(0);
+	}
+
+	let event = match mount_event(MOUNT_OP_MOVE, to) {
+		Some(e) => e,
+		None => return Ok(0),
+	};
+	event.source_names = 1;
+	unsafe {
+		if let (Ok(dentry), Ok(mnt)) = (
+			bpf_probe_read_kernel(&(*from).dentry),
+			bpf_probe_read_kernel(&(*from).mnt),
+		) {
+			read_dentry_path(dentry, mnt, &mut event.source);
+		}
+		read_fs_type(from, &mut event.fstype);
+	}
+
+	let _ = emit(event);
+
+	Ok(0)
+}
@@ -1,94 +1,321 @@
 use aya_ebpf::{
 	bindings::xdp_action,
-	helpers::{
-		bpf_get_current_comm, bpf_get_current_pid_tgid, bpf_get_current_uid_gid,
-		generated::{bpf_get_current_cgroup_id, bpf_ktime_get_ns},
-	},
-	programs::{LsmContext, TracePointContext, XdpContext},
+	helpers::{bpf_get_current_pid_tgid, bpf_probe_read_kernel, generated::bpf_ktime_get_ns},
+	macros::map,
+	maps::LruHashMap,
+	programs::{LsmContext, RetProbeContext, TracePointContext, XdpContext},
 };
 use lib_ebpf_common::{
-	EVT_INET_SOCK_SET_STATE, EVT_SOCKET, EventHeader, InetSockSetStateEvent, SOCKET_OP_BIND, SOCKET_OP_CONNECT,
-	SocketEvent,
+	AF_INET, AF_INET6, AF_UNIX, EVT_INET_ACCEPT, EVT_INET_SOCK_SET_STATE, EVT_SOCKET, EVT_SOCKET_CREATE, EVT_UDP_SEND,
+	EVT_UNIX_SOCKET, InetAcceptEvent, InetSockSetStateEvent, SOCKET_OP_BIND, SOCKET_OP_CONNECT, SocketCreateEvent,
+	SocketEvent, UNIX_PATH_LEN, UdpSendEvent, UnixSocketEvent,
 };
 use network_types::{
 	eth::{EthHdr, EtherType},
-	ip::Ipv4Hdr,
+	ip::{Ipv4Hdr, Ipv6Hdr},
 };
 
 use crate::{
-	BLOCKLIST, EVT_MAP,
-	utils::{get_mnt_ns, get_parent_comm, get_ppid, ptr_at},
-	vmlinux::{sockaddr, sockaddr_in},
+	BLOCKLIST, BLOCKLIST_V6, UNIX_CONNECT_DENY, UNIX_CONNECT_DENY_ABSTRACT,
+	hooks::{DNS_PORT, emit_dns_query, emit_dns_response},
+	utils::{emit, event_header, ptr_at},
+	vmlinux::{msghdr, path, sock, sockaddr, sockaddr_in, sockaddr_in6, socket},
 };
 
-const AF_INET: u16 = 2;
+const EPERM: i32 = 1;
+const SOCK_DGRAM: u16 = 2;
+/// offsetof(struct sockaddr_un, sun_path)
+const SUN_PATH_OFFSET: i32 = 2;
+/// A (tgid, path) connect is reported again after this long.
+const UNIX_CONNECT_REFRESH_NS: u64 = 60 * 1_000_000_000;
+
+// struct sockaddr_un, not in vmlinux.rs
+#[repr(C)]
+#[derive(Clone, Copy)]
+struct SockaddrUn {
+	sun_family: u16,
+	sun_path: [u8; UNIX_PATH_LEN],
+}
+
+// struct unix_address, not in vmlinux.rs
+#[repr(C)]
+#[derive(Clone, Copy)]
+struct UnixAddress {
+	refcnt: u32,
+	len: i32,
+	name: SockaddrUn,
+}
+
+// head of struct unix_sock, not in vmlinux.rs
+#[repr(C)]
+struct UnixSock {
+	sk: sock,
+	addr: *const UnixAddress,
+	path: path,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy)]
+struct UnixConnectKey {
+	tgid: u32,
+	path: [u8; UNIX_PATH_LEN],
+}
+
+/// Last report per (process, socket path). Clients such as syslog(3) or nscd
+/// lookups reconnect constantly, so each pair is reported once per interval.
+#[map]
+static UNIX_CONNECTS: LruHashMap<UnixConnectKey, u64> = LruHashMap::with_max_entries(8192, 0);
+const IPPROTO_UDP: u8 = 17;
+/// A flow already reported is reported again after this long, so a socket
+/// reused by the kernel for a new flow is not hidden forever.
+const UDP_FLOW_REFRESH_NS: u64 = 60 * 1_000_000_000;
+
+#[repr(C)]
+#[derive(Clone, Copy)]
+struct UdpFlowKey {
+	sk: u64,
+	daddr: [u8; 16],
+	dport: u16,
+	_pad0: [u8; 6],
+}
+
+/// Last report time per (socket, destination), so a chatty resolver yields one
+/// event per flow instead of one per datagram.
+#[map]
+static UDP_FLOWS: LruHashMap<UdpFlowKey, u64> = LruHashMap::with_max_entries(8192, 0);
+/// A (tgid, family, type, protocol) socket creation is reported again after this long.
+const SOCKET_CREATE_REFRESH_NS: u64 = 60 * 1_000_000_000;
+
+#[repr(C)]
+#[derive(Clone, Copy)]
+struct SocketCreateKey {
+	tgid: u32,
+	family: u16,
+	_pad0: [u8; 2],
+	sock_type: u32,
+	protocol: u32,
+}
+
+/// Last report time per (process, family, type, protocol). Resolvers and
+/// daemons open a socket per request, so each kind is reported once per interval.
+#[map]
+static SOCKET_CREATES: LruHashMap<SocketCreateKey, u64> = LruHashMap::with_max_entries(8192, 0);
 
 fn block_ip(addr: u32) -> bool {
 	unsafe { BLOCKLIST.get(&addr).is_some() }
 }
 
-pub fn try_socket_connect(ctx: LsmContext) -> Result<i32, i32> {
-	let addr: *const sockaddr = ctx.arg(1);
-	let ret: i32 = ctx.arg(3);
+fn block_ipv6(addr: &[u8; 16]) -> bool {
+	unsafe { BLOCKLIST_V6.get(addr).is_some() }
+}
 
-	if addr.is_null() {
+/// (family, address, port) of an AF_INET/AF_INET6 sockaddr. A v4 address
+/// occupies the first 4 bytes; the port stays in network byte order.
+unsafe fn read_sockaddr(addr: *const sockaddr) -> Option<(u16, [u8; 16], u16)> {
+	let mut out = [0u8; 16];
+
+	match unsafe { bpf_probe_read_kernel(&(*addr).sa_family) }.ok()? {
+		AF_INET => {
+			let addr_in = unsafe { bpf_probe_read_kernel(addr as *const sockaddr_in) }.ok()?;
+			out[..4].copy_from_slice(&addr_in.sin_addr.s_addr.to_ne_bytes());
+			Some((AF_INET, out, addr_in.sin_port))
+		}
+		AF_INET6 => {
+			let addr_in6 = unsafe { bpf_probe_read_kernel(addr as *const sockaddr_in6) }.ok()?;
+			out = unsafe { addr_in6.sin6_addr.in6_u.u6_addr8 };
+			Some((AF_INET6, out, addr_in6.sin6_port))
+		}
+		_ => None,
+	}
+}
+
+/// Copies the name out of `sun` given `addrlen`, with its length. Abstract
+/// names are length delimited and may hold NULs, paths end at the first one.
+fn sun_path(sun: &SockaddrUn, addrlen: i32) -> ([u8; UNIX_PATH_LEN], usize) {
+	let max = (addrlen - SUN_PATH_OFFSET).clamp(0, UNIX_PATH_LEN as i32) as usize;
+	let abstract_ns = sun.sun_path[0] == 0;
+
+	let mut out = [0u8; UNIX_PATH_LEN];
+	let mut len = 0;
+	for (i, (dst, &c)) in out.iter_mut().zip(&sun.sun_path).enumerate() {
+		if i >= max || (!abstract_ns && c == 0) {
+			break;
+		}
+		*dst = c;
+		len = i + 1;
+	}
+
+	(out, len)
+}
+
+/// Reports an AF_UNIX connect with the path as the caller gave it. Unnamed
+/// addresses are ignored. Refusing is left to `unix_stream_connect`, which
+/// sees the socket the path resolved to.
+fn unix_connect(addr: *const sockaddr, addrlen: i32) -> Result<i32, i32> {
+	let sun = unsafe { bpf_probe_read_kernel(addr as *const SockaddrUn) }.map_err(|_| 0)?;
+	let (path, len) = sun_path(&sun, addrlen);
+	if len == 0 {
 		return Ok(0);
 	}
 
+	let key = &UnixConnectKey {
+		tgid: (bpf_get_current_pid_tgid() >> 32) as u32,
+		path,
+	};
+	let now = unsafe { bpf_ktime_get_ns() };
+	if let Some(last) = unsafe { UNIX_CONNECTS.get(key) }
+		&& now.saturating_sub(*last) < UNIX_CONNECT_REFRESH_NS
+	{
+		return Ok(0);
+	}
+	let _ = UNIX_CONNECTS.insert(key, now, 0);
+
+	let event = UnixSocketEvent {
+		header: event_header(EVT_UNIX_SOCKET),
+		path,
+		path_len: len as u16,
+		op: SOCKET_OP_CONNECT,
+		abstract_ns: (path[0] == 0) as u8,
+		denied: 0,
+		_pad0: [0u8; 3],
+	};
+	let _ = emit(&event);
+
+	Ok(0)
+}
+
+// int security_unix_stream_connect(struct sock *sock, struct sock *other, struct sock *newsk)
+/// Refuses stream and seqpacket connects to a listener in `UNIX_CONNECT_DENY`.
+/// Path sockets are matched by the inode they are bound to, so relative paths,
+/// symlinks and bind mounts all lead to the same entry; abstract names are
+/// matched by name. The event carries the name the listener bound.
+pub fn try_unix_stream_connect(ctx: LsmContext) -> Result<i32, i32> {
+	let other: *const UnixSock = ctx.arg(1);
+	let ret: i32 = ctx.arg(3);
+
 	if ret != 0 {
 		return Ok(ret);
 	}
 
-	let sa_family = unsafe { (*addr).sa_family };
-	if sa_family != AF_INET {
+	if other.is_null() {
 		return Ok(0);
 	}
 
-	let addr_in = addr as *const sockaddr_in;
+	// unbound listeners have no address to match
+	let addr = unsafe { bpf_probe_read_kernel(&(*other).addr) }.map_err(|_| 0)?;
+	if addr.is_null() {
+		return Ok(0);
+	}
+	let addr = unsafe { bpf_probe_read_kernel(addr) }.map_err(|_| 0)?;
+	let (path, len) = sun_path(&addr.name, addr.len);
+	let abstract_ns = path[0] == 0;
+
+	let denied = if abstract_ns {
+		let name = &path;
+		unsafe { UNIX_CONNECT_DENY_ABSTRACT.get(name) }.is_some()
+	} else {
+		let dentry = unsafe { bpf_probe_read_kernel(&(*other).path.dentry) }.map_err(|_| 0)?;
+		if dentry.is_null() {
+			return Ok(0);
+		}
+		let inode = unsafe { bpf_probe_read_kernel(&(*dentry).d_inode) }.map_err(|_| 0)?;
+		if inode.is_null() {
+			return Ok(0);
+		}
+		let ino = unsafe { bpf_probe_read_kernel(&(*inode).i_ino) }.map_err(|_| 0)?;
+		let sb = unsafe { bpf_probe_read_kernel(&(*inode).i_sb) }.map_err(|_| 0)?;
+		let dev = unsafe { bpf_probe_read_kernel(&(*sb).s_dev) }.map_err(|_| 0)?;
+		unsafe { UNIX_CONNECT_DENY.get([u64::from(dev), ino]) }.is_some()
+	};
 
-	if addr_in.is_null() {
+	if !denied {
 		return Ok(0);
 	}
 
-	let addr = unsafe { (*addr_in).sin_addr.s_addr };
-	let port = unsafe { (*addr_in).sin_port };
-	let family = unsafe { (*addr_in).sin_family };
-	let ts = unsafe { bpf_ktime_get_ns() };
-	let uid = bpf_get_current_uid_gid() as u32;
-	let pid = bpf_get_current_pid_tgid() as u32;
-	let tgid = (bpf_get_current_pid_tgid() >> 32) as u32;
-	let comm = bpf_get_current_comm().unwrap_or([0u8; 16]);
-	let ppid = unsafe { get_ppid() };
-	let parent_comm = unsafe { get_parent_comm() };
+	let event = UnixSocketEvent {
+		header: event_header(EVT_UNIX_SOCKET),
+		path,
+		path_len: len as u16,
+		op: SOCKET_OP_CONNECT,
+		abstract_ns: abstract_ns as u8,
+		denied: 1,
+		_pad0: [0u8; 3],
+	};
+	let _ = emit(&event);
 
-	let cgroup_id = unsafe { bpf_get_current_cgroup_id() };
-	let mnt_ns = unsafe { get_mnt_ns() };
+	Ok(-EPERM)
+}
+
+pub fn try_socket_connect(ctx: LsmContext) -> Result<i32, i32> {
+	let addr: *const sockaddr = ctx.arg(1);
+	let addrlen: i32 = ctx.arg(2);
+	let ret: i32 = ctx.arg(3);
+
+	if addr.is_null() {
+		return Ok(0);
+	}
+
+	if ret != 0 {
+		return Ok(ret);
+	}
+
+	if unsafe { bpf_probe_read_kernel(&(*addr).sa_family) } == Ok(AF_UNIX) {
+		return unix_connect(addr, addrlen);
+	}
+
+	let Some((family, addr, port)) = (unsafe { read_sockaddr(addr) }) else {
+		return Ok(0);
+	};
 
 	let event = SocketEvent {
-		header: EventHeader {
-			ts,
-			event_type: EVT_SOCKET,
-			cgroup_id,
-			mnt_ns,
-			pid,
-			ppid: ppid as u32,
-			uid,
-			tgid,
-			comm,
-			parent_comm,
-			_pad0: [0u8; 3],
-		},
+		header: event_header(EVT_SOCKET),
 		addr,
 		port,
 		family,
 		op: SOCKET_OP_CONNECT,
-		_pad0: [0u8; 7],
+		_pad0: [0u8; 3],
 	};
 
 	// if let Err(e) = EVT_MAP.output::<SocketEvent>(&event, 0) {
 	// 	error!(&ctx, "ringbuf write failed: {}", e);
 	// }
-	let _ = EVT_MAP.output::<SocketEvent>(&event, 0);
+	let _ = emit(&event);
+	Ok(0)
+}
+
+// struct sock *inet_csk_accept(struct sock *sk, struct proto_accept_arg *arg)
+pub fn try_inet_csk_accept(ctx: RetProbeContext) -> Result<u32, u32> {
+	let sk: *const sock = ctx.ret();
+	if sk.is_null() {
+		return Ok(0);
+	}
+
+	let common = unsafe { bpf_probe_read_kernel(&(*sk).__sk_common) }.map_err(|_| 1u32)?;
+	let family = common.skc_family;
+	let mut saddr = [0u8; 16];
+	let mut daddr = [0u8; 16];
+	if family == AF_INET6 {
+		saddr = unsafe { common.skc_v6_rcv_saddr.in6_u.u6_addr8 };
+		daddr = unsafe { common.skc_v6_daddr.in6_u.u6_addr8 };
+	} else if family == AF_INET {
+		saddr[..4].copy_from_slice(&unsafe { common.__bindgen_anon_1.__bindgen_anon_1.skc_rcv_saddr }.to_ne_bytes());
+		daddr[..4].copy_from_slice(&unsafe { common.__bindgen_anon_1.__bindgen_anon_1.skc_daddr }.to_ne_bytes());
+	} else {
+		return Ok(0);
+	}
+	let protocol = unsafe { bpf_probe_read_kernel(&(*sk).sk_protocol) }.map_err(|_| 1u32)?;
+
+	let event = InetAcceptEvent {
+		header: event_header(EVT_INET_ACCEPT),
+		saddr,
+		daddr,
+		sport: unsafe { common.__bindgen_anon_3.__bindgen_anon_1.skc_num },
+		dport: u16::from_be(unsafe { common.__bindgen_anon_3.__bindgen_anon_1.skc_dport }),
+		family,
+		protocol,
+	};
+	let _ = emit(&event);
+
 	Ok(0)
 }
 
@@ -104,52 +331,154 @@ pub fn try_socket_bind(ctx: LsmContext) -> Result<i32, i32> {
 		return Ok(ret);
 	}
 
-	let sa_family = unsafe { (*addr).sa_family };
-	if sa_family != AF_INET {
+	let Some((family, addr, port)) = (unsafe { read_sockaddr(addr) }) else {
 		return Ok(0);
-	}
+	};
+
+	let event = SocketEvent {
+		header: event_header(EVT_SOCKET),
+		addr,
+		port,
+		family,
+		op: SOCKET_OP_BIND,
+		_pad0: [0u8; 3],
+	};
 
-	let addr_in = addr as *const sockaddr_in;
+	let _ = emit(&event);
 
-	if addr_in.is_null() {
+	Ok(0)
+}
+
+pub fn try_socket_create(ctx: LsmContext) -> Result<i32, i32> {
+	let family: i32 = ctx.arg(0);
+	let sock_type: i32 = ctx.arg(1);
+	let protocol: i32 = ctx.arg(2);
+	let kern: i32 = ctx.arg(3);
+	let ret: i32 = ctx.arg(4);
+
+	if ret != 0 {
+		return Ok(ret);
+	}
+	// sockets the kernel opens for itself, whatever task happens to be current
+	if kern != 0 {
 		return Ok(0);
 	}
 
-	let addr = unsafe { (*addr_in).sin_addr.s_addr };
-	let port = unsafe { (*addr_in).sin_port };
-	let family = unsafe { (*addr_in).sin_family };
 	let ts = unsafe { bpf_ktime_get_ns() };
-	let uid = bpf_get_current_uid_gid() as u32;
-	let pid = bpf_get_current_pid_tgid() as u32;
 	let tgid = (bpf_get_current_pid_tgid() >> 32) as u32;
-	let comm = bpf_get_current_comm().unwrap_or([0u8; 16]);
-	let cgroup_id = unsafe { bpf_get_current_cgroup_id() };
-	let mnt_ns = unsafe { get_mnt_ns() };
-	let ppid = unsafe { get_ppid() };
-	let parent_comm = unsafe { get_parent_comm() };
+	let key = SocketCreateKey {
+		tgid,
+		family: family as u16,
+		_pad0: [0u8; 2],
+		sock_type: sock_type as u32,
+		protocol: protocol as u32,
+	};
+	if let Some(last) = unsafe { SOCKET_CREATES.get(key) }
+		&& ts.saturating_sub(*last) < SOCKET_CREATE_REFRESH_NS
+	{
+		return Ok(0);
+	}
+	let _ = SOCKET_CREATES.insert(key, ts, 0);
 
-	let event = SocketEvent {
-		header: EventHeader {
-			ts,
-			event_type: EVT_SOCKET,
-			cgroup_id,
-			mnt_ns,
-			pid,
-			ppid: ppid as u32,
-			uid,
-			tgid,
-			comm,
-			parent_comm,
-			_pad0: [0u8; 3],
-		},
-		addr,
-		port,
+	let event = SocketCreateEvent {
+		header: event_header(EVT_SOCKET_CREATE),
+		family: family as u16,
+		_pad0: [0u8; 2],
+		sock_type: sock_type as u32,
+		protocol: protocol as u32,
+		_pad1: [0u8; 4],
+	};
+
+	let _ = emit(&event);
+
+	Ok(0)
+}
+
+pub fn try_socket_sendmsg(ctx: LsmContext) -> Result<i32, i32> {
+	let sock: *const socket = ctx.arg(0);
+	let msg: *const msghdr = ctx.arg(1);
+	let ret: i32 = ctx.arg(3);
+
+	if ret != 0 {
+		return Ok(ret);
+	}
+
+	if sock.is_null() || msg.is_null() {
+		return Ok(0);
+	}
+
+	let sk: *const sock = unsafe { bpf_probe_read_kernel(&(*sock).sk) }.map_err(|_| 0)?;
+	if sk.is_null() {
+		return Ok(0);
+	}
+
+	let sk_type = unsafe { bpf_probe_read_kernel(&(*sk).sk_type) }.map_err(|_| 0)?;
+	let sk_protocol = unsafe { bpf_probe_read_kernel(&(*sk).sk_protocol) }.map_err(|_| 0)?;
+	if sk_type != SOCK_DGRAM || sk_protocol != u16::from(IPPROTO_UDP) {
+		return Ok(0);
+	}
+
+	let common = unsafe { bpf_probe_read_kernel(&(*sk).__sk_common) }.map_err(|_| 0)?;
+	let family = common.skc_family;
+	let mut saddr = [0u8; 16];
+	if family == AF_INET6 {
+		saddr = unsafe { common.skc_v6_rcv_saddr.in6_u.u6_addr8 };
+	} else if family == AF_INET {
+		saddr[..4].copy_from_slice(&unsafe { common.__bindgen_anon_1.__bindgen_anon_1.skc_rcv_saddr }.to_ne_bytes());
+	} else {
+		return Ok(0);
+	}
+	let sport = unsafe { common.__bindgen_anon_3.__bindgen_anon_1.skc_num };
+
+	// sendto() names the peer in the message, send() on a connected socket
+	// uses the one stored on the socket
+	let msg_name: *const sockaddr = unsafe { bpf_probe_read_kernel(&(*msg).msg_name) }.map_err(|_| 0)? as *const _;
+	let (daddr, dport) = if msg_name.is_null() {
+		let mut daddr = [0u8; 16];
+		if family == AF_INET6 {
+			daddr = unsafe { common.skc_v6_daddr.in6_u.u6_addr8 };
+		} else {
+			daddr[..4].copy_from_slice(&unsafe { common.__bindgen_anon_1.__bindgen_anon_1.skc_daddr }.to_ne_bytes());
+		}
+		(daddr, unsafe { common.__bindgen_anon_3.__bindgen_anon_1.skc_dport })
+	} else {
+		match unsafe { read_sockaddr(msg_name) } {
+			Some((_, daddr, dport)) => (daddr, dport),
+			None => return Ok(0),
+		}
+	};
+	let dport = u16::from_be(dport);
+
+	// every query is wanted, not just the first one on the flow
+	if dport == DNS_PORT {
+		let _ = emit_dns_query(msg, &saddr, &daddr, sport, dport, family);
+	}
+
+	let ts = unsafe { bpf_ktime_get_ns() };
+	let key = UdpFlowKey {
+		sk: sk as u64,
+		daddr,
+		dport,
+		_pad0: [0u8; 6],
+	};
+	if let Some(last) = unsafe { UDP_FLOWS.get(key) }
+		&& ts.saturating_sub(*last) < UDP_FLOW_REFRESH_NS
+	{
+		return Ok(0);
+	}
+	let _ = UDP_FLOWS.insert(key, ts, 0);
+
+	let event = UdpSendEvent {
+		header: event_header(EVT_UDP_SEND),
+		saddr,
+		daddr,
+		sport,
+		dport,
 		family,
-		op: SOCKET_OP_BIND,
-		_pad0: [0u8; 7],
+		_pad0: [0u8; 2],
 	};
 
-	let _ = EVT_MAP.output::<SocketEvent>(&event, 0);
+	let _ = emit(&event);
 
 	Ok(0)
 }
@@ -159,64 +488,73 @@ pub fn try_inet_sock_set_state(ctx: TracePointContext) -> Result<u32, u32> {
 	let newstate: i32 = unsafe { tp_try_read!(ctx, 20) };
 	let sport: u16 = unsafe { tp_try_read!(ctx, 24) };
 	let dport: u16 = unsafe { tp_try_read!(ctx, 26) };
+	let family: u16 = unsafe { tp_try_read!(ctx, 28) };
 	let protocol: u16 = unsafe { tp_try_read!(ctx, 30) };
-	let saddr: u32 = unsafe { tp_try_read!(ctx, 32) };
-	let daddr: u32 = unsafe { tp_try_read!(ctx, 36) };
-	let ts = unsafe { bpf_ktime_get_ns() };
-	let uid = bpf_get_current_uid_gid() as u32;
-	let pid = bpf_get_current_pid_tgid() as u32;
-	let tgid = (bpf_get_current_pid_tgid() >> 32) as u32;
-	let comm = bpf_get_current_comm().unwrap_or([0u8; 16]);
-	let cgroup_id = unsafe { bpf_get_current_cgroup_id() };
-	let mnt_ns = unsafe { get_mnt_ns() };
-	let ppid = unsafe { get_ppid() };
-	let parent_comm = unsafe { get_parent_comm() };
+	let mut saddr = [0u8; 16];
+	let mut daddr = [0u8; 16];
+	if family == AF_INET6 {
+		saddr = unsafe { tp_try_read!(ctx, 40) };
+		daddr = unsafe { tp_try_read!(ctx, 56) };
+	} else {
+		let saddr_v4: [u8; 4] = unsafe { tp_try_read!(ctx, 32) };
+		let daddr_v4: [u8; 4] = unsafe { tp_try_read!(ctx, 36) };
+		saddr[..4].copy_from_slice(&saddr_v4);
+		daddr[..4].copy_from_slice(&daddr_v4);
+	}
 
 	if protocol != 6 {
 		return Ok(0);
 	}
 
 	let event = InetSockSetStateEvent {
-		header: EventHeader {
-			ts,
-			event_type: EVT_INET_SOCK_SET_STATE,
-			cgroup_id,
-			mnt_ns,
-			pid,
-			ppid: ppid as u32,
-			uid,
-			tgid,
-			comm,
-			parent_comm,
-			_pad0: [0u8; 3],
-		},
+		header: event_header(EVT_INET_SOCK_SET_STATE),
 		oldstate,
 		newstate,
 		sport,
 		dport,
 		protocol,
-		_pad0: [0u8; 2],
+		family,
 		saddr,
 		daddr,
 	};
 
-	let _ = EVT_MAP.output::<InetSockSetStateEvent>(&event, 0);
+	let _ = emit(&event);
 
 	Ok(0)
 }
 
 pub fn try_xdp(ctx: XdpContext) -> Result<u32, ()> {
 	let ethhdr: *const EthHdr = unsafe { ptr_at(&ctx, 0)? };
-	match unsafe { (*ethhdr).ether_type() } {
-		Ok(EtherType::Ipv4) => {}
+	let mut saddr = [0u8; 16];
+	let mut daddr = [0u8; 16];
+	let (blocked, family, udp_off) = match unsafe { (*ethhdr).ether_type() } {
+		Ok(EtherType::Ipv4) => {
+			let ipv4hdr: *const Ipv4Hdr = unsafe { ptr_at(&ctx, EthHdr::LEN)? };
+			let src = unsafe { (*ipv4hdr).src_addr };
+			saddr[..4].copy_from_slice(&src);
+			daddr[..4].copy_from_slice(&unsafe { (*ipv4hdr).dst_addr });
+			let udp_off = (unsafe { (*ipv4hdr).proto } == IPPROTO_UDP)
+				.then(|| EthHdr::LEN + usize::from(unsafe { (*ipv4hdr).ihl() }));
+			(block_ip(u32::from_be_bytes(src)), AF_INET, udp_off)
+		}
+		Ok(EtherType::Ipv6) => {
+			let ipv6hdr: *const Ipv6Hdr = unsafe { ptr_at(&ctx, EthHdr::LEN)? };
+			saddr = unsafe { (*ipv6hdr).src_addr };
+			daddr = unsafe { (*ipv6hdr).dst_addr };
+			// extension headers are not walked
+			let udp_off = (unsafe { (*ipv6hdr).next_hdr } == IPPROTO_UDP).then_some(EthHdr::LEN + Ipv6Hdr::LEN);
+			(block_ipv6(&saddr), AF_INET6, udp_off)
+		}
 		_ => return Ok(xdp_action::XDP_PASS),
-	}
-	let ipv4hdr: *const Ipv4Hdr = unsafe { ptr_at(&ctx, EthHdr::LEN)? };
-	let src = u32::from_be_bytes(unsafe { (*ipv4hdr).src_addr });
-	let action = if block_ip(src) {
-		xdp_action::XDP_DROP
-	} else {
-		xdp_action::XDP_PASS
 	};
-	Ok(action)
+
+	if blocked {
+		return Ok(xdp_action::XDP_DROP);
+	}
+
+	if let Some(udp_off) = udp_off {
+		let _ = emit_dns_response(&ctx, udp_off, &saddr, &daddr, family);
+	}
+
+	Ok(xdp_action::XDP_PASS)
 }
@@ -0,0 +1,162 @@
+use aya_ebpf::{
+	helpers::{bpf_get_current_pid_tgid, bpf_probe_read_kernel, generated::bpf_get_current_task},
+	macros::map,
+	maps::LruHashMap,
+	programs::TracePointContext,
+};
+use lib_ebpf_common::{
+	CLONE_NEWCGROUP, CLONE_NEWIPC, CLONE_NEWNET, CLONE_NEWNS, CLONE_NEWPID, CLONE_NEWTIME, CLONE_NEWUSER, CLONE_NEWUTS,
+	EVT_NS, NS_OP_SETNS, NS_OP_UNSHARE, NsEvent,
+};
+
+use crate::{
+	utils::{emit, event_header},
+	vmlinux::task_struct,
+};
+
+const NS_FLAGS: u32 = CLONE_NEWTIME
+	| CLONE_NEWNS
+	| CLONE_NEWCGROUP
+	| CLONE_NEWUTS
+	| CLONE_NEWIPC
+	| CLONE_NEWUSER
+	| CLONE_NEWPID
+	| CLONE_NEWNET;
+
+/// Namespaces compared across a call, in the order `ns_inums` reads them.
+const NS_KINDS: [u32; 7] = [
+	CLONE_NEWNS,
+	CLONE_NEWUTS,
+	CLONE_NEWIPC,
+	CLONE_NEWPID,
+	CLONE_NEWNET,
+	CLONE_NEWCGROUP,
+	CLONE_NEWUSER,
+];
+
+#[repr(C)]
+#[derive(Clone, Copy)]
+struct NsCall {
+	inums: [u32; 7],
+	flags: u32,
+	fd: i32,
+	op: u8,
+	_pad0: [u8; 3],
+}
+
+/// Namespaces a thread had when it entered setns/unshare, keyed by pid_tgid
+/// until the syscall returns.
+#[map]
+static NS_CALLS: LruHashMap<u64, NsCall> = LruHashMap::with_max_entries(4096, 0);
+
+macro_rules! ns_inum {
+	($ns:expr) => {
+		match unsafe { bpf_probe_read_kernel(&$ns) } {
+			Ok(ns) if !ns.is_null() => unsafe { bpf_probe_read_kernel(&(*ns).ns.inum) }.unwrap_or(0),
+			_ => 0,
+		}
+	};
+}
+
+/// Inode numbers of the current task's namespaces. For pid namespaces this is
+/// the one new children are created in, which is what setns/unshare change.
+fn ns_inums() -> [u32; 7] {
+	let mut inums = [0u32; 7];
+
+	let task = unsafe { bpf_get_current_task() } as *const task_struct;
+	if task.is_null() {
+		return inums;
+	}
+	let nsproxy = match unsafe { bpf_probe_read_kernel(&(*task).nsproxy) } {
+		Ok(p) if !p.is_null() => p,
+		_ => return inums,
+	};
+
+	inums[0] = ns_inum!((*nsproxy).mnt_ns);
+	inums[1] = ns_inum!((*nsproxy).uts_ns);
+	inums[2] = ns_inum!((*nsproxy).ipc_ns);
+	inums[3] = ns_inum!((*nsproxy).pid_ns_for_children);
+	inums[4] = ns_inum!((*nsproxy).net_ns);
+	inums[5] = ns_inum!((*nsproxy).cgroup_ns);
+	if let Ok(cred) = unsafe { bpf_probe_read_kernel(&(*task).cred) }
+		&& !cred.is_null()
+	{
+		inums[6] = ns_inum!((*cred).user_ns);
+	}
+
+	inums
+}
+
+fn enter_ns_call(op: u8, flags: u32, fd: i32) -> Result<u32, u32> {
+	let call = NsCall {
+		inums: ns_inums(),
+		flags,
+		fd,
+		op,
+		_pad0: [0u8; 3],
+	};
+	let _ = NS_CALLS.insert(bpf_get_current_pid_tgid(), call, 0);
+
+	Ok(0)
+}
+
+// syscalls:sys_enter_setns (int fd, int nstype)
+pub fn try_sys_enter_setns(ctx: TracePointContext) -> Result<u32, u32> {
+	let fd: i64 = unsafe { tp_try_read!(ctx, 16) };
+	let nstype: i64 = unsafe { tp_try_read!(ctx, 24) };
+
+	enter_ns_call(NS_OP_SETNS, nstype as u32 & NS_FLAGS, fd as i32)
+}
+
+// syscalls:sys_enter_unshare (unsigned long unshare_flags)
+pub fn try_sys_enter_unshare(ctx: TracePointContext) -> Result<u32, u32> {
+	let flags: u64 = unsafe { tp_try_read!(ctx, 16) };
+	let flags = flags as u32 & NS_FLAGS;
+
+	if flags == 0 {
+		return Ok(0);
+	}
+
+	enter_ns_call(NS_OP_UNSHARE, flags, -1)
+}
+
+/// Shared by `sys_exit_setns` and `sys_exit_unshare`. Reports the namespaces
+/// that differ from the ones recorded on entry; calls that failed or changed
+/// nothing are dropped.
+pub fn try_sys_exit_ns(ctx: TracePointContext) -> Result<u32, u32> {
+	let key = bpf_get_current_pid_tgid();
+	let call = match unsafe { NS_CALLS.get(key) } {
+		Some(call) => *call,
+		None => return Ok(0),
+	};
+	let _ = NS_CALLS.remove(key);
+
+	let ret: i64 = unsafe { tp_try_read!(ctx, 16) };
+	if ret != 0 {
+		return Ok(0);
+	}
+
+	let inums = ns_inums();
+	let mut changed = 0;
+	for i in 0..NS_KINDS.len() {
+		if inums[i] != call.inums[i] {
+			changed |= NS_KINDS[i];
+		}
+	}
+	if changed == 0 {
+		return Ok(0);
+	}
+
+	let event = NsEvent {
+		header: event_header(EVT_NS),
+		flags: call.flags,
+		changed,
+		old_mnt_ns: call.inums[0],
+		fd: call.fd,
+		op: call.op,
+		_pad0: [0u8; 7],
+	};
+	let _ = emit(&event);
+
+	Ok(0)
+}
@@ -1,47 +1,22 @@
 use aya_ebpf::{
-	helpers::{
-		bpf_get_current_comm, bpf_get_current_pid_tgid, bpf_get_current_uid_gid, bpf_probe_read_kernel,
-		generated::{bpf_get_current_cgroup_id, bpf_ktime_get_ns},
-	},
-	programs::{LsmContext, TracePointContext},
+	helpers::bpf_probe_read_kernel,
+	programs::{BtfTracePointContext, LsmContext, TracePointContext},
 };
 // use aya_log_ebpf::error;
 use lib_ebpf_common::{
-	EVT_ENTER_PTRACE, EVT_KILL, EVT_PTRACE_ACCESS_CHECK, EventHeader, GenericEvent, META_KILL_SIG, META_PTRACE_SUCCESS,
-	PTRACE_STAGE_REQUEST, PtraceAccessCheckEvent,
+	EVT_ENTER_PTRACE, EVT_KILL, EVT_PROCESS_EXIT, EVT_PROCESS_FORK, EVT_PTRACE_ACCESS_CHECK, EventHeader, GenericEvent,
+	META_KILL_SIG, META_PTRACE_SUCCESS, PTRACE_STAGE_REQUEST, ProcessExitEvent, ProcessForkEvent,
+	PtraceAccessCheckEvent,
 };
 
 use crate::{
-	EVT_MAP,
-	utils::{get_mnt_ns, get_parent_comm, get_ppid},
+	utils::{emit, event_header},
 	vmlinux::task_struct,
 };
 
 pub fn try_sys_enter_ptrace(_ctx: TracePointContext) -> Result<u32, u32> {
-	let uid = bpf_get_current_uid_gid() as u32;
-	let pid = bpf_get_current_pid_tgid() as u32;
-	let tgid = (bpf_get_current_pid_tgid() >> 32) as u32;
-	let comm = bpf_get_current_comm().unwrap_or([0u8; 16]);
-	let cgroup_id = unsafe { bpf_get_current_cgroup_id() };
-	let mnt_ns = unsafe { get_mnt_ns() };
-	let ts = unsafe { bpf_ktime_get_ns() };
-	let ppid = unsafe { get_ppid() };
-	let parent_comm = unsafe { get_parent_comm() };
-
 	let event = GenericEvent {
-		header: EventHeader {
-			ts,
-			event_type: EVT_ENTER_PTRACE,
-			cgroup_id,
-			mnt_ns,
-			pid,
-			ppid: ppid as u32,
-			uid,
-			tgid,
-			comm,
-			parent_comm,
-			_pad0: [0u8; 3],
-		},
+		header: event_header(EVT_ENTER_PTRACE),
 		meta: 0, // success flag
 		meta_type: META_PTRACE_SUCCESS,
 		_pad0: [0u8; 2],
@@ -50,7 +25,7 @@ pub fn try_sys_enter_ptrace(_ctx: TracePointContext) -> Result<u32, u32> {
 	// if let Err(e) = EVT_MAP.output::<GenericEvent>(&event, 0) {
 	// 	error!(&ctx, "ringbuf write failed: {}", e);
 	// }
-	let _ = EVT_MAP.output::<GenericEvent>(&event, 0);
+	let _ = emit(&event);
 
 	Ok(0)
 }
@@ -63,52 +38,25 @@ pub fn try_sys_enter_kill(ctx: LsmContext) -> Result<i32, i32> {
 	}
 
 	let sig: u32 = ctx.arg(2);
+	// pid is the signalled task, the rest describes the sender
 	let pid = unsafe { (*task).pid as u32 };
-	let ts = unsafe { bpf_ktime_get_ns() };
-	let ppid = unsafe { get_ppid() };
-	let parent_comm = unsafe { get_parent_comm() };
-
-	let tgid = (bpf_get_current_pid_tgid() >> 32) as u32;
-	let comm = bpf_get_current_comm().unwrap_or([0u8; 16]);
-	let uid = bpf_get_current_uid_gid() as u32;
-	let cgroup_id = unsafe { bpf_get_current_cgroup_id() };
-	let mnt_ns = unsafe { get_mnt_ns() };
 
 	let event = GenericEvent {
 		header: EventHeader {
-			ts,
-			event_type: EVT_KILL,
-			cgroup_id,
-			mnt_ns,
 			pid,
-			ppid: ppid as u32,
-			uid,
-			tgid,
-			comm,
-			parent_comm,
-			_pad0: [0u8; 3],
+			..event_header(EVT_KILL)
 		},
 		meta: sig,
 		meta_type: META_KILL_SIG,
 		_pad0: [0u8; 2],
 	};
 
-	let _ = EVT_MAP.output::<GenericEvent>(&event, 0);
+	let _ = emit(&event);
 
 	Ok(0)
 }
 
 pub fn try_ptrace_access_check(ctx: LsmContext) -> Result<i32, i32> {
-	let uid = bpf_get_current_uid_gid() as u32;
-	let pid = bpf_get_current_pid_tgid() as u32;
-	let tgid = (bpf_get_current_pid_tgid() >> 32) as u32;
-	let comm = bpf_get_current_comm().unwrap_or([0u8; 16]);
-	let cgroup_id = unsafe { bpf_get_current_cgroup_id() };
-	let mnt_ns = unsafe { get_mnt_ns() };
-	let ts = unsafe { bpf_ktime_get_ns() };
-	let ppid = unsafe { get_ppid() };
-	let parent_comm = unsafe { get_parent_comm() };
-
 	let child: *const task_struct = ctx.arg(0);
 
 	if child.is_null() {
@@ -153,19 +101,7 @@ pub fn try_ptrace_access_check(ctx: LsmContext) -> Result<i32, i32> {
 	let target_comm: [u8; 16] = unsafe { core::mem::transmute(target_comm_i8) };
 
 	let event = PtraceAccessCheckEvent {
-		header: EventHeader {
-			ts,
-			event_type: EVT_PTRACE_ACCESS_CHECK,
-			cgroup_id,
-			mnt_ns,
-			pid,
-			ppid: ppid as u32,
-			uid,
-			tgid,
-			comm,
-			parent_comm,
-			_pad0: [0u8; 3],
-		},
+		header: event_header(EVT_PTRACE_ACCESS_CHECK),
 		target_pid,
 		target_tgid,
 		target_uid,
@@ -175,6 +111,61 @@ pub fn try_ptrace_access_check(ctx: LsmContext) -> Result<i32, i32> {
 		_pad0: [0; 7],
 	};
 
-	let _ = EVT_MAP.output::<PtraceAccessCheckEvent>(&event, 0);
+	let _ = emit(&event);
+	Ok(0)
+}
+
+pub fn try_sched_process_fork(ctx: BtfTracePointContext) -> Result<u32, u32> {
+	let child: *const task_struct = ctx.arg(1);
+	if child.is_null() {
+		return Ok(0);
+	}
+
+	let child_pid = unsafe { bpf_probe_read_kernel(&(*child).pid) }.map_err(|_| 1u32)? as u32;
+	let child_tgid = unsafe { bpf_probe_read_kernel(&(*child).tgid) }.map_err(|_| 1u32)? as u32;
+
+	// a new thread shares the parent's tgid
+	if child_pid != child_tgid {
+		return Ok(0);
+	}
+
+	let event = ProcessForkEvent {
+		header: event_header(EVT_PROCESS_FORK),
+		child_pid,
+		child_tgid,
+	};
+
+	let _ = emit(&event);
+
+	Ok(0)
+}
+
+pub fn try_sched_process_exit(ctx: BtfTracePointContext) -> Result<u32, u32> {
+	let task: *const task_struct = ctx.arg(0);
+	if task.is_null() {
+		return Ok(0);
+	}
+
+	// do_exit() has already dropped this thread from signal->live, so zero
+	// means it was the last one
+	let signal = unsafe { bpf_probe_read_kernel(&(*task).signal) }.map_err(|_| 1u32)?;
+	if signal.is_null() {
+		return Ok(0);
+	}
+	let live = unsafe { bpf_probe_read_kernel(&(*signal).live.counter) }.map_err(|_| 1u32)?;
+	if live != 0 {
+		return Ok(0);
+	}
+
+	let exit_code = unsafe { bpf_probe_read_kernel(&(*task).exit_code) }.map_err(|_| 1u32)?;
+
+	let event = ProcessExitEvent {
+		header: event_header(EVT_PROCESS_EXIT),
+		exit_code: exit_code as u32,
+		_pad0: [0u8; 4],
+	};
+
+	let _ = emit(&event);
+
 	Ok(0)
 }
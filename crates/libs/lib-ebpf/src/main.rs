@@ -3,11 +3,11 @@
 
 use aya_ebpf::{
 	bindings::xdp_action,
-	macros::{kprobe, lsm, map, tracepoint, xdp},
-	maps::{HashMap, RingBuf},
-	programs::{LsmContext, ProbeContext, TracePointContext, XdpContext},
+	macros::{btf_tracepoint, kprobe, kretprobe, lsm, map, tracepoint, xdp},
+	maps::{Array, HashMap, LpmTrie, PerCpuArray, RingBuf},
+	programs::{BtfTracePointContext, LsmContext, ProbeContext, RetProbeContext, TracePointContext, XdpContext},
 };
-use lib_ebpf_common::FILE_PATH_LEN;
+use lib_ebpf_common::{EVT_TYPE_COUNT, FILE_NAME_LEN, FILE_PATH_LEN, UNIX_PATH_LEN};
 
 #[macro_use]
 mod utils;
@@ -15,13 +15,54 @@ mod utils;
 mod hooks;
 mod vmlinux;
 
+/// Default size; userspace can override it at load time with `--ring-size`.
 #[map]
 static EVT_MAP: RingBuf = RingBuf::with_byte_size(32 * 1024, 0);
+/// Events lost to a full `EVT_MAP`, per CPU and event type.
+#[map]
+static EVT_DROPS: PerCpuArray<u64> = PerCpuArray::with_max_entries(EVT_TYPE_COUNT, 0);
 
 #[map]
 static LSM_EXEC_DENY: HashMap<[u8; FILE_PATH_LEN], u8> = HashMap::with_max_entries(1024, 0);
 #[map]
 static BLOCKLIST: HashMap<u32, u32> = HashMap::with_max_entries(1024, 0);
+#[map]
+static BLOCKLIST_V6: HashMap<[u8; 16], u32> = HashMap::with_max_entries(1024, 0);
+/// Refused capabilities keyed by `tgid << 32 | cap`, tgid 0 meaning every task.
+/// Values are the `bpf_ktime_get_ns` expiry, 0 for none. Filled from
+/// `--deny-cap` (host-wide) and the `deny_cap` response action (the matched
+/// process, for a limited time).
+#[map]
+static CAP_DENY: HashMap<u64, u64> = HashMap::with_max_entries(1024, 0);
+/// Set by userspace at load time; the agent and init are never refused a
+/// capability.
+#[unsafe(no_mangle)]
+static AGENT_TGID: u32 = 0;
+/// AF_UNIX listeners whose connects are refused, filled from
+/// `--deny-unix-connect`. Path sockets are keyed by the `[s_dev, i_ino]` of
+/// the socket inode, abstract ones by name (leading NUL, NUL padded).
+#[map]
+static UNIX_CONNECT_DENY: HashMap<[u64; 2], u8> = HashMap::with_max_entries(64, 0);
+#[map]
+static UNIX_CONNECT_DENY_ABSTRACT: HashMap<[u8; UNIX_PATH_LEN], u8> = HashMap::with_max_entries(64, 0);
+#[map]
+static FILE_WATCH: LpmTrie<[u8; FILE_PATH_LEN], u8> = LpmTrie::with_max_entries(1024, 0);
+#[map]
+static FILE_WATCH_NAMES: HashMap<[u8; FILE_NAME_LEN], u8> = HashMap::with_max_entries(256, 0);
+/// Pre-filter policy per event type: `POLICY_*` flags and a 1-in-N sampling
+/// rate (0 and 1 keep everything). Filled by the `HookRegistry`.
+#[map]
+static EVT_POLICY: Array<u32> = Array::with_max_entries(EVT_TYPE_COUNT, 0);
+#[map]
+static EVT_SAMPLE: Array<u32> = Array::with_max_entries(EVT_TYPE_COUNT, 0);
+/// Allowlists consulted when an event type has the matching `POLICY_*_ALLOW`
+/// flag. Values are masks of the event types (`1 << EVT_*`) the entry applies to.
+#[map]
+static POLICY_COMM: HashMap<[u8; 16], u32> = HashMap::with_max_entries(256, 0);
+#[map]
+static POLICY_UID: HashMap<u32, u32> = HashMap::with_max_entries(256, 0);
+#[map]
+static POLICY_CGROUP: HashMap<u64, u32> = HashMap::with_max_entries(1024, 0);
 
 #[xdp]
 pub fn xdp_hook(ctx: XdpContext) -> u32 {
@@ -55,6 +96,30 @@ pub fn socket_connect(ctx: LsmContext) -> i32 {
 	}
 }
 
+#[lsm(hook = "unix_stream_connect")]
+pub fn unix_stream_connect(ctx: LsmContext) -> i32 {
+	match hooks::try_unix_stream_connect(ctx) {
+		Ok(ret) => ret,
+		Err(ret) => ret,
+	}
+}
+
+#[lsm(hook = "socket_sendmsg")]
+pub fn socket_sendmsg(ctx: LsmContext) -> i32 {
+	match hooks::try_socket_sendmsg(ctx) {
+		Ok(ret) => ret,
+		Err(ret) => ret,
+	}
+}
+
+#[lsm(hook = "socket_create")]
+pub fn socket_create(ctx: LsmContext) -> i32 {
+	match hooks::try_socket_create(ctx) {
+		Ok(ret) => ret,
+		Err(ret) => ret,
+	}
+}
+
 #[lsm(hook = "inode_rmdir")]
 pub fn inode_rmdir(ctx: LsmContext) -> i32 {
 	match hooks::try_inode_rmdir(ctx) {
@@ -87,6 +152,22 @@ pub fn inode_rename(ctx: LsmContext) -> i32 {
 	}
 }
 
+#[lsm(hook = "inode_create")]
+pub fn inode_create(ctx: LsmContext) -> i32 {
+	match hooks::try_inode_create(ctx) {
+		Ok(ret) => ret,
+		Err(ret) => ret,
+	}
+}
+
+#[lsm(hook = "inode_setattr")]
+pub fn inode_setattr(ctx: LsmContext) -> i32 {
+	match hooks::try_inode_setattr(ctx) {
+		Ok(ret) => ret,
+		Err(ret) => ret,
+	}
+}
+
 #[lsm(hook = "inode_mkdir")]
 pub fn inode_mkdir(ctx: LsmContext) -> i32 {
 	match hooks::try_inode_mkdir(ctx) {
@@ -127,6 +208,94 @@ pub fn sys_enter_ptrace(ctx: TracePointContext) -> u32 {
 	}
 }
 
+#[tracepoint]
+pub fn sys_exit_execve(ctx: TracePointContext) -> u32 {
+	match hooks::try_sys_exit_execve(ctx) {
+		Ok(ret) => ret,
+		Err(ret) => ret,
+	}
+}
+
+#[tracepoint]
+pub fn sys_exit_execveat(ctx: TracePointContext) -> u32 {
+	match hooks::try_sys_exit_execve(ctx) {
+		Ok(ret) => ret,
+		Err(ret) => ret,
+	}
+}
+
+#[tracepoint]
+pub fn sys_enter_setns(ctx: TracePointContext) -> u32 {
+	match hooks::try_sys_enter_setns(ctx) {
+		Ok(ret) => ret,
+		Err(ret) => ret,
+	}
+}
+
+#[tracepoint]
+pub fn sys_exit_setns(ctx: TracePointContext) -> u32 {
+	match hooks::try_sys_exit_ns(ctx) {
+		Ok(ret) => ret,
+		Err(ret) => ret,
+	}
+}
+
+#[tracepoint]
+pub fn sys_enter_unshare(ctx: TracePointContext) -> u32 {
+	match hooks::try_sys_enter_unshare(ctx) {
+		Ok(ret) => ret,
+		Err(ret) => ret,
+	}
+}
+
+#[tracepoint]
+pub fn sys_exit_unshare(ctx: TracePointContext) -> u32 {
+	match hooks::try_sys_exit_ns(ctx) {
+		Ok(ret) => ret,
+		Err(ret) => ret,
+	}
+}
+
+#[btf_tracepoint(function = "sched_process_fork")]
+pub fn sched_process_fork(ctx: BtfTracePointContext) -> u32 {
+	match hooks::try_sched_process_fork(ctx) {
+		Ok(ret) => ret,
+		Err(ret) => ret,
+	}
+}
+
+#[btf_tracepoint(function = "sched_process_exec")]
+pub fn sched_process_exec(ctx: BtfTracePointContext) -> u32 {
+	match hooks::try_sched_process_exec(ctx) {
+		Ok(ret) => ret,
+		Err(ret) => ret,
+	}
+}
+
+#[btf_tracepoint(function = "sched_process_exit")]
+pub fn sched_process_exit(ctx: BtfTracePointContext) -> u32 {
+	match hooks::try_sched_process_exit(ctx) {
+		Ok(ret) => ret,
+		Err(ret) => ret,
+	}
+}
+
+#[btf_tracepoint(function = "io_uring_create")]
+pub fn io_uring_create(ctx: BtfTracePointContext) -> u32 {
+	match hooks::try_io_uring_create(ctx) {
+		Ok(ret) => ret,
+		Err(ret) => ret,
+	}
+}
+
+#[btf_tracepoint(function = "io_uring_submit_req")]
+pub fn io_uring_submit_req(ctx: BtfTracePointContext) -> u32 {
+	match hooks::try_io_uring_submit_req(ctx) {
+		Ok(ret) => ret,
+		Err(ret) => ret,
+	}
+}
+
 #[lsm(hook = "task_kill")]
 pub fn sys_enter_kill(ctx: LsmContext) -> i32 {
 	match hooks::try_sys_enter_kill(ctx) {
@@ -155,7 +324,127 @@ pub fn ptrace_access_check(ctx: LsmContext) -> i32 {
 pub fn do_init_module(ctx: ProbeContext) -> u32 {
 	match hooks::try_do_init_module(ctx) {
 		Ok(ret) => ret,
-		Err(ret) => ret.try_into().unwrap_or(1),
+		Err(ret) => ret as u32,
+	}
+}
+
+#[lsm(hook = "capable")]
+pub fn capable(ctx: LsmContext) -> i32 {
+	match hooks::try_capable(ctx) {
+		Ok(ret) => ret,
+		Err(ret) => ret,
+	}
+}
+
+#[lsm(hook = "capset")]
+pub fn capset(ctx: LsmContext) -> i32 {
+	match hooks::try_capset(ctx) {
+		Ok(ret) => ret,
+		Err(ret) => ret,
+	}
+}
+
+#[lsm(hook = "sb_mount")]
+pub fn sb_mount(ctx: LsmContext) -> i32 {
+	match hooks::try_sb_mount(ctx) {
+		Ok(ret) => ret,
+		Err(ret) => ret,
+	}
+}
+
+#[lsm(hook = "move_mount")]
+pub fn move_mount(ctx: LsmContext) -> i32 {
+	match hooks::try_move_mount(ctx) {
+		Ok(ret) => ret,
+		Err(ret) => ret,
+	}
+}
+
+#[lsm(hook = "file_open")]
+pub fn file_open(ctx: LsmContext) -> i32 {
+	match hooks::try_file_open(ctx) {
+		Ok(ret) => ret,
+		Err(ret) => ret,
+	}
+}
+
+#[lsm(hook = "file_permission")]
+pub fn file_permission(ctx: LsmContext) -> i32 {
+	match hooks::try_file_permission(ctx) {
+		Ok(ret) => ret,
+		Err(ret) => ret,
+	}
+}
+
+#[lsm(hook = "file_free_security")]
+pub fn file_free_security(ctx: LsmContext) -> i32 {
+	match hooks::try_file_free_security(ctx) {
+		Ok(ret) => ret,
+		Err(ret) => ret,
+	}
+}
+
+#[lsm(hook = "mmap_file")]
+pub fn mmap_file(ctx: LsmContext) -> i32 {
+	match hooks::try_mmap_file(ctx) {
+		Ok(ret) => ret,
+		Err(ret) => ret,
+	}
+}
+
+#[lsm(hook = "file_mprotect")]
+pub fn file_mprotect(ctx: LsmContext) -> i32 {
+	match hooks::try_file_mprotect(ctx) {
+		Ok(ret) => ret,
+		Err(ret) => ret,
+	}
+}
+
+#[lsm(hook = "kernel_read_file")]
+pub fn kernel_read_file(ctx: LsmContext) -> i32 {
+	match hooks::try_kernel_read_file(ctx) {
+		Ok(ret) => ret,
+		Err(ret) => ret,
+	}
+}
+
+#[lsm(hook = "kernel_load_data")]
+pub fn kernel_load_data(ctx: LsmContext) -> i32 {
+	match hooks::try_kernel_load_data(ctx) {
+		Ok(ret) => ret,
+		Err(ret) => ret,
+	}
+}
+
+#[lsm(hook = "kernel_module_request")]
+pub fn kernel_module_request(ctx: LsmContext) -> i32 {
+	match hooks::try_kernel_module_request(ctx) {
+		Ok(ret) => ret,
+		Err(ret) => ret,
+	}
+}
+
+#[tracepoint]
+pub fn sys_enter_delete_module(ctx: TracePointContext) -> u32 {
+	match hooks::try_sys_enter_delete_module(ctx) {
+		Ok(ret) => ret,
+		Err(ret) => ret as u32,
+	}
+}
+
+#[kretprobe]
+pub fn inet_csk_accept(ctx: RetProbeContext) -> u32 {
+	match hooks::try_inet_csk_accept(ctx) {
+		Ok(ret) => ret,
+		Err(ret) => ret,
+	}
+}
+
+#[kprobe]
+pub fn commit_creds(ctx: ProbeContext) -> u32 {
+	match hooks::try_commit_creds(ctx) {
+		Ok(ret) => ret,
+		Err(ret) => ret as u32,
 	}
 }
 
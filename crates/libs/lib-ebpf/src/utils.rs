@@ -4,14 +4,231 @@ use aya_ebpf::{
 	bindings::path,
 	cty::c_char,
 	helpers::{
-		bpf_probe_read_kernel,
-		generated::{bpf_d_path, bpf_get_current_task},
+		bpf_get_current_comm, bpf_get_current_pid_tgid, bpf_get_current_uid_gid, bpf_probe_read_kernel,
+		bpf_probe_read_kernel_buf,
+		generated::{
+			bpf_d_path, bpf_get_current_cgroup_id, bpf_get_current_task, bpf_get_prandom_u32, bpf_ktime_get_ns,
+			bpf_probe_read_kernel_str,
+		},
 	},
+	macros::map,
+	maps::PerCpuArray,
 	programs::XdpContext,
 };
-use lib_ebpf_common::{FILE_NAME_LEN, FILE_PATH_LEN};
+use lib_ebpf_common::{
+	DENTRY_PATH_MAX_DEPTH, EVENT_WIRE_VERSION, EventHeader, FILE_NAME_LEN, FILE_PATH_LEN, POLICY_CGROUP_ALLOW,
+	POLICY_COMM_ALLOW, POLICY_DROP, POLICY_UID_ALLOW, VAR_EVENT_LEN, VAR_NAME_LEN, VAR_PATH_LEN,
+};
+
+use crate::{
+	EVT_DROPS, EVT_MAP, EVT_POLICY, EVT_SAMPLE, POLICY_CGROUP, POLICY_COMM, POLICY_UID,
+	vmlinux::{dentry, mnt_namespace, mount, nsproxy, vfsmount},
+};
+
+// room for a name read at any offset below FILE_PATH_LEN
+#[map]
+static DENTRY_PATH_SCRATCH: PerCpuArray<[u8; FILE_PATH_LEN + VAR_NAME_LEN]> = PerCpuArray::with_max_entries(1, 0);
+#[map]
+static VAR_EVENT_SCRATCH: PerCpuArray<[u8; VAR_EVENT_LEN]> = PerCpuArray::with_max_entries(1, 0);
+
+/// Header of an event raised by the current task.
+pub fn event_header(event_type: u8) -> EventHeader {
+	EventHeader {
+		ts: unsafe { bpf_ktime_get_ns() },
+		event_type,
+		cgroup_id: unsafe { bpf_get_current_cgroup_id() },
+		mnt_ns: unsafe { get_mnt_ns() },
+		pid: bpf_get_current_pid_tgid() as u32,
+		ppid: unsafe { get_ppid() } as u32,
+		uid: bpf_get_current_uid_gid() as u32,
+		tgid: (bpf_get_current_pid_tgid() >> 32) as u32,
+		comm: bpf_get_current_comm().unwrap_or([0u8; 16]),
+		parent_comm: unsafe { get_parent_comm() },
+		version: EVENT_WIRE_VERSION,
+		_pad0: [0u8; 2],
+	}
+}
+
+/// Sends `event` to userspace unless the pre-filter policy of its type drops it.
+/// Every event is `#[repr(C)]` and starts with its `EventHeader`.
+pub fn emit<T>(event: &T) -> Result<(), i32> {
+	let header = unsafe { &*(event as *const T as *const EventHeader) };
+	if !policy_allows(header) {
+		return Ok(());
+	}
+	EVT_MAP.output::<T>(event, 0).inspect_err(|_| count_drop(header.event_type))
+}
+
+/// Assembles an event whose fixed part is followed by length-prefixed
+/// sections, each a little-endian `u16` length and that many bytes. A section
+/// that can't be read is still written, empty, so the ones after it keep their
+/// position.
+///
+/// The record is copied out with `bpf_ringbuf_output`: `bpf_ringbuf_reserve`
+/// only takes sizes the verifier knows to be constant, which would mean
+/// reserving `VAR_EVENT_LEN` for every event.
+pub struct VarEvent {
+	buf: *mut u8,
+	len: usize,
+	// offset and length of the last section's bytes
+	last: usize,
+	last_len: usize,
+}
+
+impl VarEvent {
+	/// Starts a record with `event`, which begins with its `EventHeader`.
+	#[inline(always)]
+	pub fn new<T: Copy>(event: &T) -> Option<Self> {
+		let buf = VAR_EVENT_SCRATCH.get_ptr_mut(0)? as *mut u8;
+		unsafe { *(buf as *mut T) = *event };
+		Some(Self {
+			buf,
+			len: mem::size_of::<T>(),
+			last: 0,
+			last_len: 0,
+		})
+	}
+
+	/// Where the next section's bytes go, if `room` of them fit.
+	#[inline(always)]
+	fn section(&self, room: usize) -> Option<*mut u8> {
+		if self.len > VAR_EVENT_LEN - 2 - room {
+			return None;
+		}
+		Some(unsafe { self.buf.add(self.len + 2) })
+	}
+
+	#[inline(always)]
+	fn close_section(&mut self, len: usize) {
+		if self.len > VAR_EVENT_LEN - 2 - len {
+			return;
+		}
+		unsafe { *(self.buf.add(self.len) as *mut [u8; 2]) = (len as u16).to_le_bytes() };
+		self.last = self.len + 2;
+		self.last_len = len;
+		self.len += 2 + len;
+	}
+
+	/// Copies the last section into `key`, NUL padded, so a hook can look up
+	/// what it just pushed. Returns false if it is empty or doesn't fit.
+	#[inline(always)]
+	pub fn last_section_key(&self, key: &mut [u8; FILE_PATH_LEN]) -> bool {
+		*key = [0u8; FILE_PATH_LEN];
+		let len = self.last_len;
+		if len == 0 || len >= FILE_PATH_LEN || self.last > VAR_EVENT_LEN - FILE_PATH_LEN {
+			return false;
+		}
+		unsafe { bpf_probe_read_kernel_buf(self.buf.add(self.last), &mut key[..len]) }.is_ok()
+	}
+
+	/// Appends a NUL-terminated kernel string of up to `N` bytes, without its
+	/// NUL. Returns false if it couldn't be read.
+	#[inline(always)]
+	pub fn push_str<const N: usize>(&mut self, src: *const u8) -> bool {
+		let mut len = 0;
+		if let Some(dst) = self.section(N) {
+			let ret = unsafe { bpf_probe_read_kernel_str(dst as *mut _, N as u32, src as *const _) };
+			if ret > 0 {
+				len = (ret as usize - 1).min(N);
+			}
+		}
+		self.close_section(len);
+		len > 0
+	}
+
+	/// Appends the name of `dentry`. Returns false if it has none.
+	#[inline(always)]
+	pub fn push_dentry_name(&mut self, dentry: *const dentry) -> bool {
+		let name = if dentry.is_null() {
+			core::ptr::null()
+		} else {
+			unsafe { bpf_probe_read_kernel(&(*dentry).__bindgen_anon_1.d_name.name) }.unwrap_or(core::ptr::null())
+		};
+		if name.is_null() {
+			self.close_section(0);
+			return false;
+		}
+		self.push_str::<VAR_NAME_LEN>(name)
+	}
+
+	/// Appends the full path of `file` as `bpf_d_path` resolves it.
+	#[inline(always)]
+	pub fn push_file_path(&mut self, file: *mut crate::vmlinux::file) -> bool {
+		let mut len = 0;
+		if let Some(dst) = self.section(VAR_PATH_LEN)
+			&& !file.is_null()
+		{
+			let f_path = unsafe { &(*file).__bindgen_anon_1.f_path } as *const _ as *mut path;
+			let ret = unsafe { bpf_d_path(f_path, dst as *mut c_char, VAR_PATH_LEN as u32) };
+			if ret > 0 {
+				len = (ret as usize - 1).min(VAR_PATH_LEN);
+			}
+		}
+		self.close_section(len);
+		len > 0
+	}
+
+	/// Appends the path names of `dentry` as `read_dentry_path` writes them,
+	/// up to `VAR_PATH_LEN` bytes. The hooks using it only have the dentry, so
+	/// the mount is guessed from the superblock.
+	#[inline(always)]
+	pub fn push_dentry_path(&mut self, dentry: *const dentry) -> bool {
+		let mut len = 0;
+		// names are read whole past the cap and cut afterwards
+		if let Some(dst) = self.section(VAR_PATH_LEN + VAR_NAME_LEN) {
+			len = unsafe { walk_dentry_path(dentry, core::ptr::null(), dst, VAR_PATH_LEN) };
+		}
+		self.close_section(len);
+		len > 0
+	}
+
+	/// Sends the record unless the pre-filter policy of its type drops it.
+	pub fn emit(self) -> Result<(), i32> {
+		let header = unsafe { &*(self.buf as *const EventHeader) };
+		if !policy_allows(header) {
+			return Ok(());
+		}
+		let data = unsafe { core::slice::from_raw_parts(self.buf, self.len.min(VAR_EVENT_LEN)) };
+		EVT_MAP.output::<[u8]>(data, 0).inspect_err(|_| count_drop(header.event_type))
+	}
+}
+
+/// Counts an event of `event_type` that did not fit in the ring buffer.
+pub fn count_drop(event_type: u8) {
+	if let Some(count) = EVT_DROPS.get_ptr_mut(event_type as u32) {
+		unsafe { *count += 1 };
+	}
+}
+
+/// Whether the policy drops every event of `event_type`, for events whose
+/// header does not describe a task (XDP).
+pub fn policy_drops_type(event_type: u8) -> bool {
+	EVT_POLICY.get(event_type as u32).is_some_and(|flags| flags & POLICY_DROP != 0)
+}
+
+fn policy_allows(header: &EventHeader) -> bool {
+	let event_type = header.event_type as u32;
+	let flags = EVT_POLICY.get(event_type).copied().unwrap_or(0);
+	if flags & POLICY_DROP != 0 {
+		return false;
+	}
+
+	let bit = 1u32 << (event_type & 31);
+	if flags & POLICY_COMM_ALLOW != 0 && unsafe { POLICY_COMM.get(header.comm) }.is_none_or(|m| m & bit == 0) {
+		return false;
+	}
+	if flags & POLICY_UID_ALLOW != 0 && unsafe { POLICY_UID.get(header.uid) }.is_none_or(|m| m & bit == 0) {
+		return false;
+	}
+	if flags & POLICY_CGROUP_ALLOW != 0 && unsafe { POLICY_CGROUP.get(header.cgroup_id) }.is_none_or(|m| m & bit == 0) {
+		return false;
+	}
 
-use crate::vmlinux::{dentry, mnt_namespace, nsproxy};
+	match EVT_SAMPLE.get(event_type) {
+		Some(&rate) if rate > 1 => (unsafe { bpf_get_prandom_u32() }) % rate == 0,
+		_ => true,
+	}
+}
 
 pub unsafe fn get_ppid() -> i32 {
 	let task = unsafe { bpf_get_current_task() } as *const crate::vmlinux::task_struct;
@@ -152,3 +369,122 @@ pub unsafe fn resolve_file_path(file: *mut crate::vmlinux::file, buf: *mut [u8;
 
 	ret as u32
 }
+
+/// Full path of `dentry` for hooks where `bpf_d_path` is not allowed.
+/// Walks `d_parent` up to the root of `mnt`, then continues at its mountpoint,
+/// until the root of the mount namespace.
+///
+/// Hooks that only have a dentry pass a null `mnt`; the walk then takes the
+/// superblock's first mount, which is wrong for bind mounts and for the same
+/// filesystem mounted in several places.
+///
+/// The names are written leaf first, each NUL terminated, and the walk ends
+/// with a `/` entry once the namespace root is reached (`passwd\0etc\0/\0`),
+/// or `/?` when the mount was guessed and the superblock has more than one.
+/// A missing terminator means the path was cut at `FILE_PATH_LEN` or
+/// `DENTRY_PATH_MAX_DEPTH`. Returns false if nothing could be read.
+pub unsafe fn read_dentry_path(dentry: *const dentry, mnt: *const vfsmount, buf: *mut [u8; FILE_PATH_LEN]) -> bool {
+	if buf.is_null() {
+		return false;
+	}
+	let Some(scratch) = DENTRY_PATH_SCRATCH.get_ptr_mut(0) else {
+		return false;
+	};
+	let scratch = scratch as *mut u8;
+	unsafe { *(scratch as *mut [u8; FILE_PATH_LEN]) = [0u8; FILE_PATH_LEN] };
+
+	if unsafe { walk_dentry_path(dentry, mnt, scratch, FILE_PATH_LEN) } == 0 {
+		return false;
+	}
+	unsafe { *buf = *(scratch as *const [u8; FILE_PATH_LEN]) };
+	true
+}
+
+/// Writes the names of `read_dentry_path` to `dst`, which has room for
+/// `limit + VAR_NAME_LEN` bytes, and returns how many of the first `limit`
+/// were used.
+#[inline(always)]
+unsafe fn walk_dentry_path(dentry: *const dentry, vfsmnt: *const vfsmount, dst: *mut u8, limit: usize) -> usize {
+	if dentry.is_null() {
+		return 0;
+	}
+
+	let mut d = dentry;
+	// `struct vfsmount` is embedded in `struct mount`
+	let mut mnt: *const mount = if vfsmnt.is_null() {
+		core::ptr::null()
+	} else {
+		unsafe { (vfsmnt as *const u8).sub(mem::offset_of!(mount, mnt)) as *const mount }
+	};
+	let mut exact = !mnt.is_null();
+	let mut off = 0usize;
+
+	for _ in 0..DENTRY_PATH_MAX_DEPTH {
+		if off >= limit || off >= VAR_PATH_LEN {
+			break;
+		}
+		let parent = match unsafe { bpf_probe_read_kernel(&(*d).d_parent) } {
+			Ok(p) if !p.is_null() => p as *const dentry,
+			_ => break,
+		};
+
+		let mnt_root = if mnt.is_null() {
+			core::ptr::null()
+		} else {
+			unsafe { bpf_probe_read_kernel(&(*mnt).mnt.mnt_root) }.unwrap_or(core::ptr::null_mut()) as *const dentry
+		};
+
+		// a bind mount is rooted below its filesystem root
+		if parent == d || d == mnt_root {
+			if mnt.is_null() {
+				mnt = unsafe {
+					bpf_probe_read_kernel(&(*d).d_sb)
+						.and_then(|sb| bpf_probe_read_kernel(&(*sb).s_mounts))
+						.unwrap_or(core::ptr::null_mut())
+				};
+				// the guess holds only if the filesystem is mounted once, whole
+				exact = !mnt.is_null()
+					&& unsafe { bpf_probe_read_kernel(&(*mnt).mnt_next_for_sb) }.is_ok_and(|next| next.is_null())
+					&& unsafe { bpf_probe_read_kernel(&(*mnt).mnt.mnt_root) }.is_ok_and(|root| core::ptr::eq(root, d));
+			}
+			let mnt_parent = if mnt.is_null() {
+				core::ptr::null()
+			} else {
+				unsafe { bpf_probe_read_kernel(&(*mnt).mnt_parent) }.unwrap_or(core::ptr::null_mut()) as *const mount
+			};
+			if mnt_parent.is_null() || mnt_parent == mnt {
+				unsafe {
+					*dst.add(off) = b'/';
+					if exact {
+						*dst.add(off + 1) = 0;
+						off += 2;
+					} else {
+						*dst.add(off + 1) = b'?';
+						*dst.add(off + 2) = 0;
+						off += 3;
+					}
+				}
+				break;
+			}
+			d = match unsafe { bpf_probe_read_kernel(&(*mnt).mnt_mountpoint) } {
+				Ok(p) if !p.is_null() => p,
+				_ => break,
+			};
+			mnt = mnt_parent;
+			continue;
+		}
+
+		let name = match unsafe { bpf_probe_read_kernel(&(*d).__bindgen_anon_1.d_name.name) } {
+			Ok(n) if !n.is_null() => n,
+			_ => break,
+		};
+		let ret = unsafe { bpf_probe_read_kernel_str(dst.add(off) as *mut _, VAR_NAME_LEN as u32, name as *const _) };
+		if ret <= 0 {
+			break;
+		}
+		off += ret as usize;
+		d = parent;
+	}
+
+	off.min(limit)
+}
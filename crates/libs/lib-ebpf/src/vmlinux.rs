@@ -43980,6 +43980,15 @@ pub struct sockaddr_in {
 	pub __pad: [::aya_ebpf::cty::c_uchar; 8usize],
 }
 #[repr(C)]
+#[derive(Copy, Clone)]
+pub struct sockaddr_in6 {
+	pub sin6_family: ::aya_ebpf::cty::c_ushort,
+	pub sin6_port: __be16,
+	pub sin6_flowinfo: __be32,
+	pub sin6_addr: in6_addr,
+	pub sin6_scope_id: __u32,
+}
+#[repr(C)]
 #[derive(Debug, Copy, Clone)]
 pub struct phy_mse_capability {
 	pub max_average_mse: u64_,
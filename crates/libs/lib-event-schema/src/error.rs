@@ -9,6 +9,8 @@ pub enum Error {
 	Custom(String),
 	#[display("Failed to parse field: {field}")]
 	FieldParseFail { field: String },
+	#[display("Invalid IP address or CIDR block: {value}")]
+	InvalidIpNet { value: String },
 }
 
 // region:    --- Custom
@@ -5,7 +5,7 @@ use strum_macros::EnumCount;
 
 use crate::Error;
 
-const _: () = assert!(Field::COUNT <= 64, "Field no longer fits in a u64 mask");
+const _: () = assert!(Field::COUNT <= 128, "Field no longer fits in a u128 mask");
 
 #[repr(u8)]
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, EnumCount)]
@@ -20,27 +20,112 @@ pub enum Field {
 	ProcessTargetTgid,
 	ProcessTargetUid,
 	ProcessTargetComm,
+	ProcessChildPid,
+	ProcessChildTgid,
+	ProcessExecFilename,
+	ProcessExecSuccess,
+	ProcessExecErrno,
+	ProcessArgs,
+	ProcessCmdline,
+	ProcessEnv,
+	ProcessEnvTruncated,
+	ProcessExitCode,
+	ProcessExitSignal,
+
+	CredOldUid,
+	CredNewUid,
+	CredOldEuid,
+	CredNewEuid,
+	CredOldGid,
+	CredNewGid,
+	CredCapsGained,
+
+	IoUringOp,
+	IoUringFd,
+	IoUringPath,
+	IoUringAddr,
+	IoUringPort,
+	IoUringSqpoll,
+
+	MemOp,
+	MemProt,
+	MemOldProt,
+	MemTransition,
+	MemAnonymous,
+	MemPath,
+
+	CapId,
+	CapName,
+	CapAudit,
+	CapDenied,
+	CapInitUserns,
+	CapGained,
+	CapEffective,
+
+	MountOp,
+	MountSource,
+	MountTarget,
+	MountFstype,
+	MountFlags,
+	MountHostNs,
+
+	NsOp,
+	NsRequested,
+	NsChanged,
+	NsOldMnt,
+	NsNewMnt,
+	NsJoinsHost,
+
+	FilePath,
+	FileOp,
+	FileAccess,
 
 	SocketOldState,
 	SocketNewState,
 	SocketPort,
+	SocketAddr,
 	SocketFamily,
 	SocketOp,
+	SocketType,
+	SocketProtocol,
+	SocketUnixPath,
+	SocketUnixAbstract,
+	SocketDenied,
 
 	NetworkSport,
 	NetworkDport,
 	NetworkSaddr,
 	NetworkDaddr,
 	NetworkProtocol,
+	NetworkDirection,
+
+	DnsQname,
+	DnsQtype,
+	DnsRcode,
+	DnsAnswerIp,
 
 	ModuleName,
 	ModuleOp,
+	ModulePath,
+	ModuleSource,
 
 	InodeFilename,
 	InodeOldFilename,
 	InodeNewFilename,
 	InodeOp,
 	InodeMutationType,
+	InodePath,
+	InodeDir,
+	InodeNewPath,
+	InodeAttrOp,
+	InodeAttrMode,
+	InodeAttrExec,
+	InodeAttrSuid,
+	InodeAttrUid,
+	InodeAttrGid,
+	InodeAttrChanges,
+	InodeAttrTimesSet,
+	InodeAttrMtime,
 
 	PtraceMode,
 	PtraceStage,
@@ -64,6 +149,9 @@ pub enum FieldType {
 	Int,
 	String,
 	Ip,
+	IntList,
+	StringList,
+	IpList,
 }
 
 impl FromStr for Field {
@@ -81,27 +169,106 @@ impl FromStr for Field {
 			"process.target.tgid" => Field::ProcessTargetTgid,
 			"process.target.uid" => Field::ProcessTargetUid,
 			"process.target.comm" => Field::ProcessTargetComm,
+			"process.child.pid" => Field::ProcessChildPid,
+			"process.child.tgid" => Field::ProcessChildTgid,
+			"process.exec.filename" => Field::ProcessExecFilename,
+			"process.exec.success" => Field::ProcessExecSuccess,
+			"process.exec.errno" => Field::ProcessExecErrno,
+			"process.args" => Field::ProcessArgs,
+			"process.cmdline" => Field::ProcessCmdline,
+			"process.env" => Field::ProcessEnv,
+			"process.env_truncated" => Field::ProcessEnvTruncated,
+			"process.exit.code" => Field::ProcessExitCode,
+			"process.exit.signal" => Field::ProcessExitSignal,
+
+			"cred.old.uid" => Field::CredOldUid,
+			"cred.new.uid" => Field::CredNewUid,
+			"cred.old.euid" => Field::CredOldEuid,
+			"cred.new.euid" => Field::CredNewEuid,
+			"cred.old.gid" => Field::CredOldGid,
+			"cred.new.gid" => Field::CredNewGid,
+			"cred.caps_gained" => Field::CredCapsGained,
+			"io_uring.op" => Field::IoUringOp,
+			"io_uring.fd" => Field::IoUringFd,
+			"io_uring.path" => Field::IoUringPath,
+			"io_uring.addr" => Field::IoUringAddr,
+			"io_uring.port" => Field::IoUringPort,
+			"io_uring.sqpoll" => Field::IoUringSqpoll,
+			"mem.op" => Field::MemOp,
+			"mem.prot" => Field::MemProt,
+			"mem.old_prot" => Field::MemOldProt,
+			"mem.transition" => Field::MemTransition,
+			"mem.anonymous" => Field::MemAnonymous,
+			"mem.path" => Field::MemPath,
+			"cap.id" => Field::CapId,
+			"cap.name" => Field::CapName,
+			"cap.audit" => Field::CapAudit,
+			"cap.denied" => Field::CapDenied,
+			"cap.init_userns" => Field::CapInitUserns,
+			"cap.gained" => Field::CapGained,
+			"cap.effective" => Field::CapEffective,
+			"mount.op" => Field::MountOp,
+			"mount.source" => Field::MountSource,
+			"mount.target" => Field::MountTarget,
+			"mount.fstype" => Field::MountFstype,
+			"mount.flags" => Field::MountFlags,
+			"mount.host_ns" => Field::MountHostNs,
+			"ns.op" => Field::NsOp,
+			"ns.requested" => Field::NsRequested,
+			"ns.changed" => Field::NsChanged,
+			"ns.old_mnt" => Field::NsOldMnt,
+			"ns.new_mnt" => Field::NsNewMnt,
+			"ns.joins_host" => Field::NsJoinsHost,
+			"file.path" => Field::FilePath,
+			"file.op" => Field::FileOp,
+			"file.access" => Field::FileAccess,
 
 			"socket.old_state" => Field::SocketOldState,
 			"socket.new_state" => Field::SocketNewState,
 			"socket.port" => Field::SocketPort,
+			"socket.addr" => Field::SocketAddr,
 			"socket.family" => Field::SocketFamily,
 			"socket.op" => Field::SocketOp,
+			"socket.type" => Field::SocketType,
+			"socket.protocol" => Field::SocketProtocol,
+			"socket.unix_path" => Field::SocketUnixPath,
+			"socket.unix_abstract" => Field::SocketUnixAbstract,
+			"socket.denied" => Field::SocketDenied,
 
 			"network.saddr" => Field::NetworkSaddr,
 			"network.daddr" => Field::NetworkDaddr,
 			"network.sport" => Field::NetworkSport,
 			"network.dport" => Field::NetworkDport,
 			"network.protocol" => Field::NetworkProtocol,
+			"network.direction" => Field::NetworkDirection,
+
+			"dns.qname" => Field::DnsQname,
+			"dns.qtype" => Field::DnsQtype,
+			"dns.rcode" => Field::DnsRcode,
+			"dns.answer_ip" => Field::DnsAnswerIp,
 
 			"module.name" => Field::ModuleName,
 			"module.op" => Field::ModuleOp,
+			"module.path" => Field::ModulePath,
+			"module.source" => Field::ModuleSource,
 
 			"inode.filename" => Field::InodeFilename,
 			"inode.old_filename" => Field::InodeOldFilename,
 			"inode.new_filename" => Field::InodeNewFilename,
 			"inode.op" => Field::InodeOp,
 			"inode.mutation.type" => Field::InodeMutationType,
+			"inode.path" => Field::InodePath,
+			"inode.dir" => Field::InodeDir,
+			"inode.new_path" => Field::InodeNewPath,
+			"inode.attr.op" => Field::InodeAttrOp,
+			"inode.attr.mode" => Field::InodeAttrMode,
+			"inode.attr.exec" => Field::InodeAttrExec,
+			"inode.attr.suid" => Field::InodeAttrSuid,
+			"inode.attr.uid" => Field::InodeAttrUid,
+			"inode.attr.gid" => Field::InodeAttrGid,
+			"inode.attr.changes" => Field::InodeAttrChanges,
+			"inode.attr.times_set" => Field::InodeAttrTimesSet,
+			"inode.attr.mtime" => Field::InodeAttrMtime,
 
 			"ptrace.mode" => Field::PtraceMode,
 			"ptrace.stage" => Field::PtraceStage,
@@ -129,8 +296,25 @@ impl FieldType {
 			FieldType::Int => "int",
 			FieldType::String => "string",
 			FieldType::Ip => "ip",
+			FieldType::IntList => "int_list",
+			FieldType::StringList => "string_list",
+			FieldType::IpList => "ip_list",
+		}
+	}
+
+	/// Scalar type of a single element; scalars map to themselves.
+	pub const fn element(self) -> FieldType {
+		match self {
+			FieldType::IntList => FieldType::Int,
+			FieldType::StringList => FieldType::String,
+			FieldType::IpList => FieldType::Ip,
+			other => other,
 		}
 	}
+
+	pub const fn is_list(self) -> bool {
+		matches!(self, FieldType::IntList | FieldType::StringList | FieldType::IpList)
+	}
 }
 
 impl Field {
@@ -139,8 +323,8 @@ impl Field {
 		self as usize
 	}
 	#[inline]
-	pub const fn mask(self) -> u64 {
-		1u64 << (self as u64)
+	pub const fn mask(self) -> u128 {
+		1u128 << (self as u32)
 	}
 
 	pub const fn as_str(&self) -> &'static str {
@@ -155,27 +339,106 @@ impl Field {
 			Field::ProcessTargetTgid => "process.target.tgid",
 			Field::ProcessTargetUid => "process.target.uid",
 			Field::ProcessTargetComm => "process.target.comm",
+			Field::ProcessChildPid => "process.child.pid",
+			Field::ProcessChildTgid => "process.child.tgid",
+			Field::ProcessExecFilename => "process.exec.filename",
+			Field::ProcessExecSuccess => "process.exec.success",
+			Field::ProcessExecErrno => "process.exec.errno",
+			Field::ProcessArgs => "process.args",
+			Field::ProcessCmdline => "process.cmdline",
+			Field::ProcessEnv => "process.env",
+			Field::ProcessEnvTruncated => "process.env_truncated",
+			Field::ProcessExitCode => "process.exit.code",
+			Field::ProcessExitSignal => "process.exit.signal",
+
+			Field::CredOldUid => "cred.old.uid",
+			Field::CredNewUid => "cred.new.uid",
+			Field::CredOldEuid => "cred.old.euid",
+			Field::CredNewEuid => "cred.new.euid",
+			Field::CredOldGid => "cred.old.gid",
+			Field::CredNewGid => "cred.new.gid",
+			Field::CredCapsGained => "cred.caps_gained",
+			Field::IoUringOp => "io_uring.op",
+			Field::IoUringFd => "io_uring.fd",
+			Field::IoUringPath => "io_uring.path",
+			Field::IoUringAddr => "io_uring.addr",
+			Field::IoUringPort => "io_uring.port",
+			Field::IoUringSqpoll => "io_uring.sqpoll",
+			Field::MemOp => "mem.op",
+			Field::MemProt => "mem.prot",
+			Field::MemOldProt => "mem.old_prot",
+			Field::MemTransition => "mem.transition",
+			Field::MemAnonymous => "mem.anonymous",
+			Field::MemPath => "mem.path",
+			Field::CapId => "cap.id",
+			Field::CapName => "cap.name",
+			Field::CapAudit => "cap.audit",
+			Field::CapDenied => "cap.denied",
+			Field::CapInitUserns => "cap.init_userns",
+			Field::CapGained => "cap.gained",
+			Field::CapEffective => "cap.effective",
+			Field::MountOp => "mount.op",
+			Field::MountSource => "mount.source",
+			Field::MountTarget => "mount.target",
+			Field::MountFstype => "mount.fstype",
+			Field::MountFlags => "mount.flags",
+			Field::MountHostNs => "mount.host_ns",
+			Field::NsOp => "ns.op",
+			Field::NsRequested => "ns.requested",
+			Field::NsChanged => "ns.changed",
+			Field::NsOldMnt => "ns.old_mnt",
+			Field::NsNewMnt => "ns.new_mnt",
+			Field::NsJoinsHost => "ns.joins_host",
+			Field::FilePath => "file.path",
+			Field::FileOp => "file.op",
+			Field::FileAccess => "file.access",
 
 			Field::SocketOldState => "socket.old_state",
 			Field::SocketNewState => "socket.new_state",
 			Field::SocketPort => "socket.port",
+			Field::SocketAddr => "socket.addr",
 			Field::SocketFamily => "socket.family",
 			Field::SocketOp => "socket.op",
+			Field::SocketType => "socket.type",
+			Field::SocketProtocol => "socket.protocol",
+			Field::SocketUnixPath => "socket.unix_path",
+			Field::SocketUnixAbstract => "socket.unix_abstract",
+			Field::SocketDenied => "socket.denied",
 
 			Field::NetworkDaddr => "network.daddr",
 			Field::NetworkSaddr => "network.saddr",
 			Field::NetworkSport => "network.sport",
 			Field::NetworkDport => "network.dport",
 			Field::NetworkProtocol => "network.protocol",
+			Field::NetworkDirection => "network.direction",
+
+			Field::DnsQname => "dns.qname",
+			Field::DnsQtype => "dns.qtype",
+			Field::DnsRcode => "dns.rcode",
+			Field::DnsAnswerIp => "dns.answer_ip",
 
 			Field::ModuleName => "module.name",
 			Field::ModuleOp => "module.op",
+			Field::ModulePath => "module.path",
+			Field::ModuleSource => "module.source",
 
 			Field::InodeFilename => "inode.filename",
 			Field::InodeOldFilename => "inode.old_filename",
 			Field::InodeNewFilename => "inode.new_filename",
 			Field::InodeOp => "inode.op",
 			Field::InodeMutationType => "inode.mutation.type",
+			Field::InodePath => "inode.path",
+			Field::InodeDir => "inode.dir",
+			Field::InodeNewPath => "inode.new_path",
+			Field::InodeAttrOp => "inode.attr.op",
+			Field::InodeAttrMode => "inode.attr.mode",
+			Field::InodeAttrExec => "inode.attr.exec",
+			Field::InodeAttrSuid => "inode.attr.suid",
+			Field::InodeAttrUid => "inode.attr.uid",
+			Field::InodeAttrGid => "inode.attr.gid",
+			Field::InodeAttrChanges => "inode.attr.changes",
+			Field::InodeAttrTimesSet => "inode.attr.times_set",
+			Field::InodeAttrMtime => "inode.attr.mtime",
 
 			Field::PtraceMode => "ptrace.mode",
 			Field::PtraceStage => "ptrace.stage",
@@ -201,33 +464,110 @@ impl Field {
 			| Field::ProcessTgid
 			| Field::ProcessTargetPid
 			| Field::ProcessTargetTgid
-			| Field::ProcessTargetUid => FieldType::Int,
-
-			Field::ProcessComm | Field::ProcessFilepath | Field::ProcessTargetComm | Field::ProcessParentComm => {
+			| Field::ProcessTargetUid
+			| Field::ProcessChildPid
+			| Field::ProcessChildTgid
+			| Field::ProcessExecErrno
+			| Field::ProcessExitCode
+			| Field::ProcessExitSignal => FieldType::Int,
+
+			Field::ProcessComm
+			| Field::ProcessFilepath
+			| Field::ProcessTargetComm
+			| Field::ProcessParentComm
+			| Field::ProcessExecFilename
+			| Field::ProcessCmdline => FieldType::String,
+
+			Field::ProcessArgs | Field::ProcessEnv => FieldType::StringList,
+
+			Field::ProcessExecSuccess | Field::ProcessEnvTruncated => FieldType::Bool,
+
+			// Credentials
+			Field::CredOldUid
+			| Field::CredNewUid
+			| Field::CredOldEuid
+			| Field::CredNewEuid
+			| Field::CredOldGid
+			| Field::CredNewGid => FieldType::Int,
+
+			Field::CredCapsGained => FieldType::IntList,
+
+			// io_uring
+			Field::IoUringOp | Field::IoUringPath => FieldType::String,
+			Field::IoUringFd | Field::IoUringPort => FieldType::Int,
+			Field::IoUringAddr => FieldType::Ip,
+			Field::IoUringSqpoll => FieldType::Bool,
+
+			// Memory protection
+			Field::MemOp | Field::MemProt | Field::MemOldProt | Field::MemTransition | Field::MemPath => {
 				FieldType::String
 			}
+			Field::MemAnonymous => FieldType::Bool,
+
+			// Capabilities
+			Field::CapId => FieldType::Int,
+			Field::CapName => FieldType::String,
+			Field::CapAudit | Field::CapDenied | Field::CapInitUserns => FieldType::Bool,
+			Field::CapGained | Field::CapEffective => FieldType::StringList,
+
+			// Mounts
+			Field::MountOp | Field::MountSource | Field::MountTarget | Field::MountFstype => FieldType::String,
+			Field::MountFlags => FieldType::StringList,
+			Field::MountHostNs => FieldType::Bool,
+
+			// Namespaces
+			Field::NsOp => FieldType::String,
+			Field::NsRequested | Field::NsChanged => FieldType::StringList,
+			Field::NsOldMnt | Field::NsNewMnt => FieldType::Int,
+			Field::NsJoinsHost => FieldType::Bool,
+
+			// Watched files
+			Field::FilePath | Field::FileOp => FieldType::String,
+			Field::FileAccess => FieldType::StringList,
 
 			// Socket
 			Field::SocketOldState | Field::SocketNewState => FieldType::String,
 
-			Field::SocketPort | Field::SocketFamily | Field::SocketOp => FieldType::Int,
+			Field::SocketPort | Field::SocketFamily | Field::SocketOp | Field::SocketType | Field::SocketProtocol => {
+				FieldType::Int
+			}
+
+			Field::SocketUnixAbstract | Field::SocketDenied => FieldType::Bool,
+			Field::SocketUnixPath => FieldType::String,
+
+			Field::SocketAddr => FieldType::Ip,
 
 			// Network
 			Field::NetworkSport | Field::NetworkDport => FieldType::Int,
 
 			Field::NetworkDaddr | Field::NetworkSaddr => FieldType::Ip,
 
-			Field::NetworkProtocol => FieldType::String,
+			Field::NetworkProtocol | Field::NetworkDirection => FieldType::String,
+
+			// DNS
+			Field::DnsQname => FieldType::String,
+			Field::DnsQtype | Field::DnsRcode => FieldType::Int,
+			Field::DnsAnswerIp => FieldType::IpList,
 
 			// Module
-			Field::ModuleName => FieldType::String,
-			Field::ModuleOp => FieldType::Int,
+			Field::ModuleName | Field::ModulePath => FieldType::String,
+			Field::ModuleOp | Field::ModuleSource => FieldType::Int,
 
 			// Inode
-			Field::InodeFilename | Field::InodeOldFilename | Field::InodeNewFilename => FieldType::String,
+			Field::InodeFilename
+			| Field::InodeOldFilename
+			| Field::InodeNewFilename
+			| Field::InodePath
+			| Field::InodeDir
+			| Field::InodeNewPath => FieldType::String,
 
 			Field::InodeOp | Field::InodeMutationType => FieldType::Int,
 
+			Field::InodeAttrOp => FieldType::String,
+			Field::InodeAttrMode | Field::InodeAttrUid | Field::InodeAttrGid | Field::InodeAttrMtime => FieldType::Int,
+			Field::InodeAttrExec | Field::InodeAttrSuid | Field::InodeAttrTimesSet => FieldType::Bool,
+			Field::InodeAttrChanges => FieldType::StringList,
+
 			// Ptrace
 			Field::PtraceMode | Field::PtraceStage => FieldType::Int,
 
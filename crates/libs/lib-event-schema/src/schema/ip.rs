@@ -0,0 +1,94 @@
+use std::{
+	fmt,
+	net::{IpAddr, Ipv4Addr, Ipv6Addr},
+	str::FromStr,
+};
+
+use crate::Error;
+
+/// Address block from a rule, `10.0.0.0/8` or `fd00::/8`. A bare address is a
+/// host block (`/32` or `/128`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct IpNet {
+	addr: IpAddr,
+	prefix: u8,
+}
+
+impl IpNet {
+	pub fn new(addr: IpAddr, prefix: u8) -> Option<Self> {
+		let addr = addr.to_canonical();
+		if prefix > max_prefix(addr) {
+			return None;
+		}
+
+		Some(Self {
+			addr: mask(addr, prefix),
+			prefix,
+		})
+	}
+
+	pub fn host(addr: IpAddr) -> Self {
+		let addr = addr.to_canonical();
+		Self {
+			addr,
+			prefix: max_prefix(addr),
+		}
+	}
+
+	pub fn addr(&self) -> IpAddr {
+		self.addr
+	}
+
+	pub fn prefix(&self) -> u8 {
+		self.prefix
+	}
+
+	/// v4-mapped v6 addresses (`::ffff:a.b.c.d`) match v4 blocks.
+	pub fn contains(&self, ip: &IpAddr) -> bool {
+		let ip = ip.to_canonical();
+		ip.is_ipv4() == self.addr.is_ipv4() && mask(ip, self.prefix) == self.addr
+	}
+}
+
+impl FromStr for IpNet {
+	type Err = Error;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		let invalid = || Error::InvalidIpNet { value: s.to_string() };
+
+		match s.split_once('/') {
+			Some((addr, prefix)) => {
+				let addr: IpAddr = addr.parse().map_err(|_| invalid())?;
+				let prefix: u8 = prefix.parse().map_err(|_| invalid())?;
+				IpNet::new(addr, prefix).ok_or_else(invalid)
+			}
+			None => s.parse::<IpAddr>().map(IpNet::host).map_err(|_| invalid()),
+		}
+	}
+}
+
+impl fmt::Display for IpNet {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(f, "{}/{}", self.addr, self.prefix)
+	}
+}
+
+fn max_prefix(addr: IpAddr) -> u8 {
+	match addr {
+		IpAddr::V4(_) => 32,
+		IpAddr::V6(_) => 128,
+	}
+}
+
+fn mask(addr: IpAddr, prefix: u8) -> IpAddr {
+	match addr {
+		IpAddr::V4(v4) => {
+			let bits = u32::MAX.checked_shl(32 - u32::from(prefix)).unwrap_or(0);
+			IpAddr::V4(Ipv4Addr::from_bits(v4.to_bits() & bits))
+		}
+		IpAddr::V6(v6) => {
+			let bits = u128::MAX.checked_shl(128 - u32::from(prefix)).unwrap_or(0);
+			IpAddr::V6(Ipv6Addr::from_bits(v6.to_bits() & bits))
+		}
+	}
+}
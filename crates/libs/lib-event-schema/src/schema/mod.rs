@@ -1,5 +1,7 @@
 mod field;
+mod ip;
 mod value;
 
 pub use field::*;
+pub use ip::*;
 pub use value::*;
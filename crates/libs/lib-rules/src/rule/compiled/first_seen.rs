@@ -0,0 +1,34 @@
+use lib_event_schema::Field;
+
+use crate::{
+	Error,
+	error::Result,
+	rule::{FirstSeen, compiled::field::compile_field},
+};
+
+#[derive(Debug, Clone)]
+pub struct CompiledFirstSeen {
+	pub keys: Vec<Field>,
+}
+
+impl CompiledFirstSeen {
+	pub fn mask(&self) -> u128 {
+		self.keys.iter().fold(0u128, |acc, k| acc | k.mask())
+	}
+}
+
+pub fn compile_first_seen(raw: FirstSeen, rule_id: &str) -> Result<CompiledFirstSeen> {
+	if raw.keys.is_empty() {
+		return Err(Error::FirstSeenWithoutKeys {
+			rule_id: rule_id.into(),
+		});
+	}
+
+	let mut keys = raw.keys.iter().map(|k| compile_field(k)).collect::<Result<Vec<_>>>()?;
+
+	// key order does not change what counts as a combination
+	keys.sort_by_key(|k| k.index());
+	keys.dedup();
+
+	Ok(CompiledFirstSeen { keys })
+}
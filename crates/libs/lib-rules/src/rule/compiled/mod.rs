@@ -1,7 +1,9 @@
 pub mod condition;
 pub mod field;
+pub mod first_seen;
 pub mod op;
 pub mod response;
 pub mod rule;
 pub mod ruleset;
+pub mod schedule;
 pub mod sequence;
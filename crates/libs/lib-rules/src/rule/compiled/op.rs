@@ -15,6 +15,11 @@ pub enum Op {
 	Regex,
 	BitAnd,
 	Exists,
+	AnyContains,
+	AnyRegex,
+	AnyIn,
+	AllIn,
+	Len,
 }
 
 pub fn compile_op(s: &str) -> Result<Op> {
@@ -32,6 +37,11 @@ pub fn compile_op(s: &str) -> Result<Op> {
 		"regex" | "matches_regex" => Op::Regex,
 		"bit_and" => Op::BitAnd,
 		"exists" => Op::Exists,
+		"any_contains" => Op::AnyContains,
+		"any_regex" => Op::AnyRegex,
+		"any_in" => Op::AnyIn,
+		"all_in" => Op::AllIn,
+		"len" => Op::Len,
 		_ => return Err(Error::UnknownOp { op: s.into() }),
 	})
 }
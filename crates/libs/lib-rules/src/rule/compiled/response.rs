@@ -1,5 +1,6 @@
-use std::{net::Ipv4Addr, str::FromStr};
+use std::{net::IpAddr, str::FromStr};
 
+use lib_common::event::CAP_NAMES;
 use lib_event_schema::{Field, FieldType, FieldValue};
 use strum::EnumCount;
 
@@ -20,6 +21,7 @@ pub enum CompiledAction {
 	KillProcess { pid: CompiledActionValue },
 	BlockIp { ip: CompiledActionValue },
 	DenyExec { path: CompiledActionValue },
+	DenyCap { cap: CompiledActionValue },
 }
 
 impl CompiledAction {
@@ -28,15 +30,27 @@ impl CompiledAction {
 			CompiledAction::BlockIp { .. } => "BlockIp",
 			CompiledAction::KillProcess { .. } => "KillProcess",
 			CompiledAction::DenyExec { .. } => "DenyExecution",
+			CompiledAction::DenyCap { .. } => "DenyCapability",
 		}
 	}
 }
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum ResolvedAction {
-	KillProcess { pid: u32 },
-	BlockIp { ip: Ipv4Addr },
-	DenyExec { path_key: [u8; 128] },
+	KillProcess {
+		pid: u32,
+	},
+	BlockIp {
+		ip: IpAddr,
+	},
+	DenyExec {
+		path_key: [u8; 128],
+	},
+	/// Refuses `cap` to the process group `tgid` of the matched event.
+	DenyCap {
+		cap: u32,
+		tgid: u32,
+	},
 }
 
 #[derive(Debug, Clone)]
@@ -53,52 +67,91 @@ pub fn compile_response_chain(raw: ResponseChain) -> Result<CompiledResponseChai
 		actions,
 	})
 }
-pub fn resolve_action(action: &CompiledAction, fields: &[Option<FieldValue>; Field::COUNT]) -> Result<ResolvedAction> {
-	match action {
-		CompiledAction::BlockIp { ip } => {
-			let value = resolve_param(ip, fields)?;
-
-			match value {
-				FieldValue::Ip(ip) => Ok(ResolvedAction::BlockIp { ip: ip.into() }),
+/// Resolves an action against the event fields. A list-valued field reference
+/// fans out into one action per element.
+pub fn resolve_action(
+	action: &CompiledAction,
+	fields: &[Option<FieldValue>; Field::COUNT],
+) -> Result<Vec<ResolvedAction>> {
+	let param = match action {
+		CompiledAction::BlockIp { ip } => ip,
+		CompiledAction::KillProcess { pid } => pid,
+		CompiledAction::DenyExec { path } => path,
+		CompiledAction::DenyCap { cap } => cap,
+	};
 
-				other => Err(Error::InvalidActionParamValue {
-					expected: "ip".into(),
-					actual: other.ty().as_str().into(),
-				}),
-			}
-		}
+	match resolve_param(param, fields)? {
+		FieldValue::IntList(items) => items
+			.into_iter()
+			.map(|v| resolve_scalar(action, FieldValue::Int(v), fields))
+			.collect(),
+		FieldValue::StringList(items) => items
+			.into_iter()
+			.map(|v| resolve_scalar(action, FieldValue::String(v), fields))
+			.collect(),
+		FieldValue::IpList(items) => items
+			.into_iter()
+			.map(|v| resolve_scalar(action, FieldValue::Ip(v), fields))
+			.collect(),
+		value => Ok(vec![resolve_scalar(action, value, fields)?]),
+	}
+}
 
-		CompiledAction::KillProcess { pid } => {
-			let value = resolve_param(pid, fields)?;
+fn resolve_scalar(
+	action: &CompiledAction,
+	value: FieldValue,
+	fields: &[Option<FieldValue>; Field::COUNT],
+) -> Result<ResolvedAction> {
+	match action {
+		CompiledAction::BlockIp { .. } => match value {
+			FieldValue::Ip(ip) => Ok(ResolvedAction::BlockIp { ip: ip.to_canonical() }),
 
-			match value {
-				FieldValue::Int(pid) => {
-					let pid = u32::try_from(pid).map_err(|_| Error::InvalidActionParamValue {
-						expected: "non-negative 32-bit integer".into(),
-						actual: pid.to_string(),
-					})?;
+			other => Err(Error::InvalidActionParamValue {
+				expected: "ip".into(),
+				actual: other.ty().as_str().into(),
+			}),
+		},
 
-					Ok(ResolvedAction::KillProcess { pid })
-				}
+		CompiledAction::KillProcess { .. } => match value {
+			FieldValue::Int(pid) => {
+				let pid = u32::try_from(pid).map_err(|_| Error::InvalidActionParamValue {
+					expected: "non-negative 32-bit integer".into(),
+					actual: pid.to_string(),
+				})?;
 
-				other => Err(Error::InvalidActionParamValue {
-					expected: "integer".into(),
-					actual: other.ty().as_str().into(),
-				}),
+				Ok(ResolvedAction::KillProcess { pid })
 			}
-		}
-		CompiledAction::DenyExec { path } => {
-			let value = resolve_param(path, fields)?;
-			match value {
-				FieldValue::String(s) => Ok(ResolvedAction::DenyExec {
-					path_key: path_to_deny_key(&s),
+
+			other => Err(Error::InvalidActionParamValue {
+				expected: "integer".into(),
+				actual: other.ty().as_str().into(),
+			}),
+		},
+		CompiledAction::DenyExec { .. } => match value {
+			FieldValue::String(s) => Ok(ResolvedAction::DenyExec {
+				path_key: path_to_deny_key(&s),
+			}),
+			other => Err(Error::InvalidActionParamValue {
+				expected: "string".into(),
+				actual: other.ty().as_str().into(),
+			}),
+		},
+		CompiledAction::DenyCap { .. } => match value {
+			FieldValue::Int(cap) => match u32::try_from(cap) {
+				Ok(cap) if (cap as usize) < CAP_NAMES.len() => Ok(ResolvedAction::DenyCap {
+					cap,
+					tgid: event_tgid(fields)?,
 				}),
-				other => Err(Error::InvalidActionParamValue {
-					expected: "string".into(),
-					actual: other.ty().as_str().into(),
+				_ => Err(Error::InvalidActionParamValue {
+					expected: format!("capability number below {}", CAP_NAMES.len()),
+					actual: cap.to_string(),
 				}),
-			}
-		}
+			},
+			other => Err(Error::InvalidActionParamValue {
+				expected: "integer".into(),
+				actual: other.ty().as_str().into(),
+			}),
+		},
 	}
 }
 fn compile_action(action: Action) -> Result<CompiledAction> {
@@ -113,6 +166,9 @@ fn compile_action(action: Action) -> Result<CompiledAction> {
 		Action::DenyExec { path } => CompiledAction::DenyExec {
 			path: compile_action_value(path, FieldType::String)?,
 		},
+		Action::DenyCap { cap } => CompiledAction::DenyCap {
+			cap: compile_action_value(cap, FieldType::Int)?,
+		},
 	})
 }
 fn compile_action_value(raw: ActionValue, expected: FieldType) -> Result<CompiledActionValue> {
@@ -120,7 +176,8 @@ fn compile_action_value(raw: ActionValue, expected: FieldType) -> Result<Compile
 		ActionValue::Field(name) => {
 			let field = Field::from_str(&name).map_err(|_| Error::UnknownField { field: name })?;
 
-			if field.ty() != expected {
+			// list fields bind element-wise, see `resolve_action`
+			if field.ty().element() != expected {
 				return Err(Error::InvalidBinding {
 					field: field.as_str().into(),
 					expected: expected.as_str().into(),
@@ -157,8 +214,8 @@ fn compile_literal(value: toml::Value, expected: FieldType) -> Result<FieldValue
 
 		FieldType::Ip => match value {
 			toml::Value::String(v) => {
-				let ip: Ipv4Addr = v.parse()?;
-				Ok(FieldValue::Ip(ip.into()))
+				let ip: IpAddr = v.parse()?;
+				Ok(FieldValue::Ip(ip))
 			}
 			other => Err(Error::ExpectedType {
 				expected: expected_name,
@@ -173,6 +230,11 @@ fn compile_literal(value: toml::Value, expected: FieldType) -> Result<FieldValue
 				found: other.type_str().into(),
 			}),
 		},
+
+		FieldType::IntList | FieldType::StringList | FieldType::IpList => Err(Error::ExpectedType {
+			expected: expected_name,
+			found: value.type_str().into(),
+		}),
 	}
 }
 fn resolve_param(param: &CompiledActionValue, fields: &[Option<FieldValue>; Field::COUNT]) -> Result<FieldValue> {
@@ -184,6 +246,23 @@ fn resolve_param(param: &CompiledActionValue, fields: &[Option<FieldValue>; Fiel
 		}),
 	}
 }
+/// Process a capability deny is scoped to. 0 would make it host-wide, so an
+/// event without a process can't deny anything.
+fn event_tgid(fields: &[Option<FieldValue>; Field::COUNT]) -> Result<u32> {
+	match &fields[Field::ProcessTgid.index()] {
+		Some(FieldValue::Int(tgid)) => match u32::try_from(*tgid) {
+			Ok(tgid) if tgid != 0 => Ok(tgid),
+			_ => Err(Error::InvalidActionParamValue {
+				expected: "non-zero process.tgid".into(),
+				actual: tgid.to_string(),
+			}),
+		},
+		_ => Err(Error::MissingField {
+			field: Field::ProcessTgid.as_str().to_string(),
+		}),
+	}
+}
+
 fn path_to_deny_key(path: &str) -> [u8; 128] {
 	let bytes = path.as_bytes();
 	let len = bytes.len().min(128);
@@ -194,3 +273,32 @@ fn path_to_deny_key(path: &str) -> [u8; 128] {
 // pub fn compile_response_chain(raw: ResponseChain) -> Result<CompiledResponseChain> {
 // 	Ok(raw.into())
 // }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn deny_cap(cap: i64) -> CompiledAction {
+		CompiledAction::DenyCap {
+			cap: CompiledActionValue::Literal(FieldValue::Int(cap)),
+		}
+	}
+
+	#[test]
+	fn deny_cap_is_scoped_to_the_event_process() {
+		let mut fields: [Option<FieldValue>; Field::COUNT] = std::array::from_fn(|_| None);
+		fields[Field::ProcessTgid.index()] = Some(FieldValue::Int(4242));
+
+		let resolved = resolve_action(&deny_cap(21), &fields).unwrap();
+		assert_eq!(resolved, vec![ResolvedAction::DenyCap { cap: 21, tgid: 4242 }]);
+	}
+
+	#[test]
+	fn deny_cap_without_process_is_refused() {
+		let mut fields: [Option<FieldValue>; Field::COUNT] = std::array::from_fn(|_| None);
+		assert!(resolve_action(&deny_cap(21), &fields).is_err());
+
+		fields[Field::ProcessTgid.index()] = Some(FieldValue::Int(0));
+		assert!(resolve_action(&deny_cap(21), &fields).is_err());
+	}
+}
@@ -7,8 +7,10 @@ use crate::{
 	error::Result,
 	rule::compiled::{
 		condition::{CompiledCondition, compile_condition},
+		first_seen::{CompiledFirstSeen, compile_first_seen},
 		op::Op,
 		response::{CompiledResponseChain, compile_response_chain},
+		schedule::{CompiledSchedule, compile_schedule},
 		sequence::{CompiledSequence, compile_sequence},
 	},
 };
@@ -23,13 +25,17 @@ pub struct CompiledRuleInner {
 	pub id: Arc<str>,
 	pub description: Arc<str>,
 	pub severity: Severity,
+	pub risk: u32,
 	pub conditions: Vec<CompiledCondition>,
 	pub sequence: Option<CompiledSequence>,
+	pub first_seen: Option<CompiledFirstSeen>,
+	/// Checked before any condition; outside the window the rule is skipped.
+	pub active: Option<CompiledSchedule>,
 	pub response_chain: Option<Arc<CompiledResponseChain>>,
 	/// Fields whose *absence* makes this rule fail. Drives the runtime prefilter:
 	/// `required_mask & !ctx.present() != 0` means the rule cannot possibly match,
 	/// without evaluating a single condition.
-	pub required_mask: u64,
+	pub required_mask: u128,
 }
 
 fn op_cost(cond: &CompiledCondition) -> u8 {
@@ -42,7 +48,7 @@ fn op_cost(cond: &CompiledCondition) -> u8 {
 		// single-word integer work
 		(Op::BitAnd, _) => 1,
 		(Op::Gt | Op::Gte | Op::Lt | Op::Lte, _) => 1,
-		(Op::Eq | Op::NotEq, V::Int(_) | V::Ip(_) | V::Bool(_)) => 1,
+		(Op::Eq | Op::NotEq, V::Int(_) | V::Ip(_) | V::IpNet(_) | V::Bool(_)) => 1,
 
 		// string equality: length check, then memcmp
 		(Op::Eq | Op::NotEq, _) => 2,
@@ -56,6 +62,12 @@ fn op_cost(cond: &CompiledCondition) -> u8 {
 		(Op::StartsWith, _) => 5,
 		(Op::Contains, _) => 6,
 		(Op::Regex, _) => 7,
+
+		// list ops repeat the scalar work once per element
+		(Op::Len, _) => 1,
+		(Op::AnyIn | Op::AllIn, _) => 8,
+		(Op::AnyContains, _) => 9,
+		(Op::AnyRegex, _) => 10,
 	}
 }
 
@@ -73,10 +85,16 @@ pub fn compile_rule(raw: Rule, hash: [u8; 32], hash_hex: Arc<str>) -> Result<Com
 
 	conditions.sort_by_key(op_cost);
 
-	let required_mask = conditions.iter().fold(0u64, |acc, c| acc | c.field.mask());
-
 	let sequence = raw.inner.sequence.map(compile_sequence).transpose()?;
 
+	let first_seen = raw.inner.first_seen.map(|f| compile_first_seen(f, &raw.inner.id)).transpose()?;
+
+	let active = raw.inner.active.map(|a| compile_schedule(a, &raw.inner.id)).transpose()?;
+
+	// first_seen keys are part of the match: without them there is nothing to look up
+	let required_mask = conditions.iter().fold(0u128, |acc, c| acc | c.field.mask())
+		| first_seen.as_ref().map_or(0, CompiledFirstSeen::mask);
+
 	let response_chain = raw.inner.response_chain.map(compile_response_chain).transpose()?.map(Arc::new);
 
 	if let Some(chain) = &response_chain {
@@ -92,8 +110,11 @@ pub fn compile_rule(raw: Rule, hash: [u8; 32], hash_hex: Arc<str>) -> Result<Com
 			id: raw.inner.id.into(),
 			description: raw.inner.description.into(),
 			severity: raw.inner.severity,
+			risk: raw.inner.risk,
 			conditions,
 			sequence,
+			first_seen,
+			active,
 			response_chain,
 			required_mask,
 		},
@@ -126,8 +147,11 @@ mod tests {
 				id: id.to_string(),
 				description: "test".to_string(),
 				severity: Severity::Low,
+				risk: 0,
 				conditions,
 				sequence: None,
+				first_seen: None,
+				active: None,
 				response_chain: None,
 			},
 			hash: [0u8; 32],
@@ -189,6 +213,36 @@ mod tests {
 		assert!(matches!(err, Err(Error::RuleWithoutConditions { .. })));
 	}
 
+	#[test]
+	fn first_seen_keys_are_required_fields() -> Result<()> {
+		// -- Setup & Fixtures
+		let mut raw = raw_rule("novel", vec![cond("process.uid", "equals", Value::Integer(0))]);
+		raw.inner.first_seen = Some(crate::rule::FirstSeen {
+			keys: vec!["network.dport".to_string(), "process.comm".to_string()],
+		});
+
+		// -- Exec
+		let compiled = compile(raw)?;
+
+		// -- Check
+		assert_eq!(
+			compiled.inner.required_mask,
+			Field::ProcessUid.mask() | Field::ProcessComm.mask() | Field::NetworkDport.mask()
+		);
+
+		Ok(())
+	}
+
+	#[test]
+	fn first_seen_without_keys_is_rejected() {
+		let mut raw = raw_rule("novel", vec![cond("process.uid", "equals", Value::Integer(0))]);
+		raw.inner.first_seen = Some(crate::rule::FirstSeen { keys: vec![] });
+
+		let err = compile_rule(raw, [0u8; 32], Arc::from("0".repeat(64)));
+
+		assert!(matches!(err, Err(Error::FirstSeenWithoutKeys { .. })));
+	}
+
 	#[test]
 	fn exists_is_a_presence_requirement() -> Result<()> {
 		// -- Setup & Fixtures
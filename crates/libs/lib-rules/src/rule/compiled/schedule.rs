@@ -0,0 +1,194 @@
+use chrono::{DateTime, Datelike, Duration, Local, NaiveDateTime, Timelike, Utc};
+
+use crate::{
+	Error,
+	error::Result,
+	rule::{Active, Day, ScheduleTz},
+};
+
+const MINUTES_PER_DAY: u16 = 24 * 60;
+
+#[derive(Debug, Clone)]
+pub struct CompiledSchedule {
+	/// Bit `n` set = window may start on the n-th day from Monday. All bits set when unrestricted.
+	days: u8,
+	/// Minutes since midnight, end exclusive. `None` = all day.
+	hours: Option<(u16, u16)>,
+	tz: ScheduleTz,
+}
+
+impl CompiledSchedule {
+	pub fn is_active(&self, now: DateTime<Utc>) -> bool {
+		match self.tz {
+			ScheduleTz::Utc => self.is_active_at(now.naive_utc()),
+			ScheduleTz::Local => self.is_active_at(now.with_timezone(&Local).naive_local()),
+		}
+	}
+
+	fn is_active_at(&self, at: NaiveDateTime) -> bool {
+		let minute = (at.hour() * 60 + at.minute()) as u16;
+
+		let Some((start, end)) = self.hours else {
+			return self.day_allowed(at);
+		};
+
+		if start < end {
+			return (start..end).contains(&minute) && self.day_allowed(at);
+		}
+
+		// overnight window: the early-morning part belongs to the previous day's window
+		if minute >= start {
+			self.day_allowed(at)
+		} else if minute < end {
+			self.day_allowed(at - Duration::days(1))
+		} else {
+			false
+		}
+	}
+
+	fn day_allowed(&self, at: NaiveDateTime) -> bool {
+		self.days & (1 << at.weekday().num_days_from_monday()) != 0
+	}
+}
+
+pub fn compile_schedule(raw: Active, rule_id: &str) -> Result<CompiledSchedule> {
+	let days = if raw.days.is_empty() {
+		0x7f
+	} else {
+		raw.days.iter().fold(0u8, |acc, d| acc | 1 << day_index(*d))
+	};
+
+	let hours = raw
+		.hours
+		.as_deref()
+		.map(|h| {
+			parse_hours(h).ok_or_else(|| Error::InvalidActiveHours {
+				rule_id: rule_id.into(),
+				hours: h.into(),
+			})
+		})
+		.transpose()?;
+
+	Ok(CompiledSchedule {
+		days,
+		hours,
+		tz: raw.tz,
+	})
+}
+
+fn day_index(day: Day) -> u8 {
+	match day {
+		Day::Mon => 0,
+		Day::Tue => 1,
+		Day::Wed => 2,
+		Day::Thu => 3,
+		Day::Fri => 4,
+		Day::Sat => 5,
+		Day::Sun => 6,
+	}
+}
+
+fn parse_hours(s: &str) -> Option<(u16, u16)> {
+	let (start, end) = s.split_once('-')?;
+	let (start, end) = (parse_hhmm(start.trim())?, parse_hhmm(end.trim())?);
+
+	// an empty window would silently disable the rule
+	(start != end).then_some((start, end))
+}
+
+fn parse_hhmm(s: &str) -> Option<u16> {
+	let (h, m) = s.split_once(':')?;
+	let (h, m): (u16, u16) = (h.parse().ok()?, m.parse().ok()?);
+
+	// "24:00" is accepted as an end-of-day marker
+	match (h, m) {
+		(24, 0) => Some(MINUTES_PER_DAY),
+		(0..=23, 0..=59) => Some(h * 60 + m),
+		_ => None,
+	}
+}
+
+// region:    --- Tests
+
+#[cfg(test)]
+mod tests {
+	type Result<T> = core::result::Result<T, Box<dyn std::error::Error>>;
+
+	use chrono::TimeZone;
+
+	use super::*;
+
+	fn active(days: Vec<Day>, hours: Option<&str>) -> Active {
+		Active {
+			days,
+			hours: hours.map(Into::into),
+			tz: ScheduleTz::Utc,
+		}
+	}
+
+	fn at(y: i32, mo: u32, d: u32, h: u32, mi: u32) -> Result<DateTime<Utc>> {
+		Utc.with_ymd_and_hms(y, mo, d, h, mi, 0)
+			.single()
+			.ok_or_else(|| "bad date".into())
+	}
+
+	#[test]
+	fn overnight_window_wraps_midnight() -> Result<()> {
+		// -- Setup & Fixtures
+		let schedule = compile_schedule(active(vec![], Some("22:00-06:00")), "r")?;
+
+		// -- Exec & Check
+		assert!(schedule.is_active(at(2026, 3, 4, 23, 30)?));
+		assert!(schedule.is_active(at(2026, 3, 4, 3, 0)?));
+		assert!(!schedule.is_active(at(2026, 3, 4, 6, 0)?), "end is exclusive");
+		assert!(!schedule.is_active(at(2026, 3, 4, 12, 0)?));
+
+		Ok(())
+	}
+
+	#[test]
+	fn overnight_window_belongs_to_start_day() -> Result<()> {
+		// -- Setup & Fixtures
+		// 2026-03-06 is a Friday
+		let schedule = compile_schedule(active(vec![Day::Fri], Some("22:00-06:00")), "r")?;
+
+		// -- Exec & Check
+		assert!(schedule.is_active(at(2026, 3, 6, 22, 30)?));
+		assert!(
+			schedule.is_active(at(2026, 3, 7, 2, 0)?),
+			"saturday early morning is friday's window"
+		);
+		assert!(
+			!schedule.is_active(at(2026, 3, 6, 2, 0)?),
+			"friday early morning is thursday's window"
+		);
+
+		Ok(())
+	}
+
+	#[test]
+	fn days_without_hours_cover_the_whole_day() -> Result<()> {
+		// -- Setup & Fixtures
+		let schedule = compile_schedule(active(vec![Day::Sat, Day::Sun], None), "r")?;
+
+		// -- Exec & Check
+		assert!(schedule.is_active(at(2026, 3, 7, 0, 0)?));
+		assert!(schedule.is_active(at(2026, 3, 8, 23, 59)?));
+		assert!(!schedule.is_active(at(2026, 3, 9, 12, 0)?));
+
+		Ok(())
+	}
+
+	#[test]
+	fn invalid_hours_are_rejected() {
+		for hours in ["22:00", "25:00-06:00", "08:00-08:00", "8h-9h"] {
+			let res = compile_schedule(active(vec![], Some(hours)), "r");
+			assert!(
+				matches!(res, Err(Error::InvalidActiveHours { .. })),
+				"'{hours}' should be rejected"
+			);
+		}
+	}
+}
+
+// endregion: --- Tests
@@ -16,6 +16,7 @@ pub struct CompiledSequence {
 	pub threshold: Option<u32>,
 	pub scope: Option<Scope>,
 	pub steps: Vec<CompiledStep>,
+	pub risk: u32,
 }
 
 #[derive(Debug, Clone)]
@@ -50,6 +51,7 @@ pub fn compile_sequence(raw: Sequence) -> Result<CompiledSequence> {
 
 		scope: raw.scope,
 		threshold: raw.threshold,
+		risk: raw.risk,
 		steps: raw
 			.steps
 			.into_iter()
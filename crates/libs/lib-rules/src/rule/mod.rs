@@ -2,9 +2,11 @@ mod common;
 pub mod compiled;
 mod rule;
 mod ruleset;
+mod schedule;
 mod sequence;
 
 pub use common::*;
 pub use rule::*;
 pub use ruleset::*;
+pub use schedule::*;
 pub use sequence::*;
@@ -3,7 +3,7 @@ use std::{path::Path, sync::Arc};
 use crate::{
 	error::{Error, Result},
 	hash_utils,
-	rule::{Sequence, Trigger, common::Severity},
+	rule::{Active, Sequence, Trigger, common::Severity},
 };
 use serde::{Deserialize, Deserializer};
 use simple_fs::SPath;
@@ -27,13 +27,27 @@ pub struct RuleInner {
 	pub id: String,
 	pub description: String,
 	pub severity: Severity,
+	/// Added to the risk score of every entity the matching event belongs to.
+	#[serde(default)]
+	pub risk: u32,
 	pub conditions: Vec<Condition>,
 	#[serde(default)]
 	pub sequence: Option<Sequence>,
 	#[serde(default)]
+	pub first_seen: Option<FirstSeen>,
+	#[serde(default)]
+	pub active: Option<Active>,
+	#[serde(default)]
 	pub response_chain: Option<ResponseChain>,
 }
 
+/// Restricts the rule to the first occurrence of each combination of `keys` values.
+#[cfg_attr(test, derive(PartialEq))]
+#[derive(Debug, Deserialize, Clone)]
+pub struct FirstSeen {
+	pub keys: Vec<String>,
+}
+
 #[cfg_attr(test, derive(PartialEq))]
 #[derive(Debug, Deserialize, Clone)]
 pub struct ResponseChain {
@@ -83,19 +97,51 @@ pub enum Action {
 	KillProcess { pid: ActionValue },
 	BlockIp { ip: ActionValue },
 	DenyExec { path: ActionValue },
+	DenyCap { cap: ActionValue },
+}
+
+/// Serialization a rule file is written in, picked from its extension.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RuleFormat {
+	Toml,
+	Yaml,
+	Json,
+}
+
+impl RuleFormat {
+	pub const TOML_EXTENSION: &str = "toml";
+	pub const EXTENSIONS: [&str; 4] = [Self::TOML_EXTENSION, "yaml", "yml", "json"];
+
+	pub fn from_path(path: impl AsRef<Path>) -> Option<Self> {
+		match path.as_ref().extension()?.to_str()? {
+			"toml" => Some(RuleFormat::Toml),
+			"yaml" | "yml" => Some(RuleFormat::Yaml),
+			"json" => Some(RuleFormat::Json),
+			_ => None,
+		}
+	}
+
+	fn parse(self, s: &str) -> Result<RuleInner> {
+		let rule_raw: RuleRaw = match self {
+			RuleFormat::Toml => toml::from_str(s)?,
+			RuleFormat::Yaml => serde_norway::from_str(s)?,
+			RuleFormat::Json => serde_json::from_str(s)?,
+		};
+		Ok(rule_raw.rule)
+	}
 }
 
 impl Rule {
 	pub fn from_str(s: &str) -> Result<Self> {
-		let rule_raw: RuleRaw = toml::from_str(&s)?;
+		Self::from_str_as(s, RuleFormat::Toml)
+	}
+
+	pub fn from_str_as(s: &str, format: RuleFormat) -> Result<Self> {
+		let inner = format.parse(s)?;
 		let hash = hash_utils::blake3(&s);
 		let hash_hex = hash_utils::hex_encode(hash);
 
-		Ok(Rule {
-			inner: rule_raw.rule,
-			hash,
-			hash_hex,
-		})
+		Ok(Rule { inner, hash, hash_hex })
 	}
 
 	pub fn from_file(rule_path: impl AsRef<Path>) -> Result<Self> {
@@ -105,16 +151,11 @@ impl Rule {
 			return Err(Error::RulePathNotFound(file_path.into()));
 		}
 
-		let str = std::fs::read_to_string(file_path)?;
-		let rule_raw: RuleRaw = toml::from_str(&str)?;
-		let hash = hash_utils::blake3(&str);
-		let hash_hex = hash_utils::hex_encode(hash);
+		let format = RuleFormat::from_path(file_path.std_path())
+			.ok_or_else(|| Error::UnsupportedRuleFormat(file_path.to_string()))?;
 
-		Ok(Rule {
-			inner: rule_raw.rule,
-			hash,
-			hash_hex,
-		})
+		let str = std::fs::read_to_string(file_path)?;
+		Self::from_str_as(&str, format)
 	}
 }
 
@@ -140,6 +181,7 @@ mod tests {
 			id: "test-rule".to_string(),
 			description: "Suspicious action in /tmp".to_string(),
 			severity: Severity::VeryLow,
+			risk: 0,
 
 			conditions: vec![
 				Condition {
@@ -154,6 +196,8 @@ mod tests {
 				},
 			],
 			sequence: None,
+			first_seen: None,
+			active: None,
 			response_chain: None,
 		};
 		let fx_rule = Rule {
@@ -172,6 +216,45 @@ mod tests {
 
 		Ok(())
 	}
+
+	#[test]
+	fn parse_rule_from_yaml_and_json_ok() -> Result<()> {
+		// -- Setup & Fixtures
+		let fx_yaml_path = "rules/test-rule-yaml.yaml";
+		let fx_json_path = "rules/test-rule-json.json";
+
+		// -- Exec
+		let yaml = Rule::from_file(fx_yaml_path)?;
+		let json = Rule::from_file(fx_json_path)?;
+
+		// -- Check
+		assert_eq!(yaml.inner.id, "test-rule-yaml");
+		assert_eq!(yaml.inner.severity, Severity::Low);
+		assert_eq!(
+			yaml.inner.conditions[0].value,
+			toml::Value::String("\\.so$".to_string())
+		);
+		assert_eq!(
+			yaml.inner.conditions[1].value,
+			toml::Value::Array(vec![toml::Value::Integer(0)])
+		);
+		assert_eq!(json.inner.id, "test-rule-json");
+		assert_eq!(json.inner.conditions[0].value, toml::Value::Integer(0));
+		assert_eq!(json.inner.conditions[1].op, "!=");
+
+		Ok(())
+	}
+
+	#[test]
+	fn parse_rule_unknown_extension_err() -> Result<()> {
+		// -- Exec
+		let res = Rule::from_file("src/lib.rs");
+
+		// -- Check
+		assert!(matches!(res, Err(Error::UnsupportedRuleFormat(_))));
+
+		Ok(())
+	}
 }
 
 // endregion: --- Tests
@@ -3,7 +3,7 @@ use std::path::Path;
 use std::sync::Arc;
 
 use crate::error::Result;
-use crate::{Error, Rule};
+use crate::{Error, Rule, rule::RuleFormat};
 use glob::glob;
 use serde::Deserialize;
 use tracing::warn;
@@ -72,35 +72,42 @@ impl RuleSet {
 		let mut seq_by_id = HashMap::new();
 
 		// Make sure the path is like: `rules/` or `some/stuff/rules/` and not `rules`
-		let pattern = format!("{}/**/*.toml", dir.as_ref().display());
+		let mut paths = Vec::new();
+		for ext in RuleFormat::EXTENSIONS {
+			let pattern = format!("{}/**/*.{ext}", dir.as_ref().display());
 
-		for glob in glob(&pattern)? {
-			match glob {
-				Ok(path) => {
-					let rule = Rule::from_file(&path)?;
-					let rule_id: Arc<str> = rule.inner.id.as_str().into();
+			for glob in glob(&pattern)? {
+				match glob {
+					Ok(path) => paths.push(path),
+					Err(e) => warn!("Glob pattern error: {:?}", e),
+				}
+			}
+		}
+		// keep load order independent of which format a rule happens to use
+		paths.sort();
 
-					if by_id.contains_key(&rule_id) {
-						return Err(crate::Error::DuplicateRuleId {
-							id: rule_id.to_string(),
-						});
-					}
+		for path in paths {
+			let rule = Rule::from_file(&path)?;
+			let rule_id: Arc<str> = rule.inner.id.as_str().into();
 
-					let idx = rules.len();
-					by_id.insert(rule_id.clone(), idx);
+			if by_id.contains_key(&rule_id) {
+				return Err(crate::Error::DuplicateRuleId {
+					id: rule_id.to_string(),
+				});
+			}
 
-					if let Some(seq) = &rule.inner.sequence {
-						let seq_id: Arc<str> = seq.id.as_str().into();
+			let idx = rules.len();
+			by_id.insert(rule_id.clone(), idx);
 
-						if seq_by_id.insert(seq_id, idx).is_some() {
-							return Err(Error::DuplicateSequenceId { id: seq.id.to_string() });
-						}
-					}
+			if let Some(seq) = &rule.inner.sequence {
+				let seq_id: Arc<str> = seq.id.as_str().into();
 
-					rules.push(rule);
+				if seq_by_id.insert(seq_id, idx).is_some() {
+					return Err(Error::DuplicateSequenceId { id: seq.id.to_string() });
 				}
-				Err(e) => warn!("Glob pattern error: {:?}", e),
 			}
+
+			rules.push(rule);
 		}
 
 		Ok(RuleSet { rules, by_id })
@@ -130,13 +137,18 @@ impl RuleSet {
 mod tests {
 	type Result<T> = core::result::Result<T, Box<dyn std::error::Error>>; // For tests.
 
+	use std::net::IpAddr;
+
+	use lib_common::event::*;
+
 	use super::*;
+	use crate::{EngineEvent, RuleEngine};
 
 	#[test]
 	fn load_ruleset_from_dir() -> Result<()> {
 		// -- Setup & Fixtures
 		let fx_rule_dir = "rules/";
-		let fx_rule_count = 4;
+		let fx_rule_count = 6;
 		// -- Exec
 		let ruleset = RuleSet::load_from_dir(fx_rule_dir)?;
 		// -- Check
@@ -144,6 +156,271 @@ mod tests {
 
 		Ok(())
 	}
+
+	fn header(uid: u32, comm: &str) -> EventHeader {
+		EventHeader {
+			cgroup_id: 0,
+			container: None,
+			ts: 0,
+			mnt_ns: 0,
+			pid: 42,
+			ppid: 1,
+			tgid: 42,
+			uid,
+			parent_comm: Arc::from("bash"),
+			comm: Arc::from(comm),
+		}
+	}
+
+	fn inode(uid: u32, path: &str) -> CerberusEvent {
+		CerberusEvent::Inode(InodeEvent {
+			header: header(uid, "rm"),
+			filename: Arc::from("shadow"),
+			filename_len: 6,
+			op: 0,
+			path: Some(Arc::from(path)),
+		})
+	}
+
+	fn exec(args: &[&str]) -> CerberusEvent {
+		CerberusEvent::ProcessExec(ProcessExecEvent {
+			header: header(0, "python3"),
+			filename: Arc::from("/usr/bin/python3"),
+			args: args.iter().map(|a| Arc::from(*a)).collect(),
+			cmdline: Arc::from(args.join(" ")),
+			env: vec![],
+			env_truncated: false,
+			old_pid: 42,
+			success: true,
+			errno: 0,
+		})
+	}
+
+	fn cred(comm: &str) -> CerberusEvent {
+		CerberusEvent::CredChange(CredChangeEvent {
+			header: header(1000, comm),
+			old_uid: 1000,
+			new_uid: 1000,
+			old_euid: 1000,
+			new_euid: 0,
+			old_gid: 1000,
+			new_gid: 1000,
+			old_egid: 1000,
+			new_egid: 1000,
+			old_cap_effective: 0,
+			new_cap_effective: u64::MAX >> 23,
+		})
+	}
+
+	fn io_uring(opcode: u8, addr: Option<IpAddr>) -> CerberusEvent {
+		CerberusEvent::IoUring(IoUringEvent {
+			header: header(1000, "implant"),
+			opcode,
+			setup: false,
+			fd: 3,
+			path: None,
+			addr,
+			port: 4444,
+			sqpoll: true,
+		})
+	}
+
+	fn module(op: u8, path: Option<&str>) -> CerberusEvent {
+		CerberusEvent::Module(ModuleEvent {
+			header: header(0, "insmod"),
+			module_name: Arc::from("rootkit"),
+			op,
+			source: if path.is_some() { 2 } else { 1 },
+			path: path.map(Arc::from),
+		})
+	}
+
+	fn mprotect(comm: &str, old_prot: Option<u32>) -> CerberusEvent {
+		CerberusEvent::MemProt(MemProtEvent {
+			header: header(1000, comm),
+			op: old_prot.is_some() as u8,
+			prot: 5,
+			old_prot,
+			flags: 0x22,
+			anonymous: true,
+			path: None,
+		})
+	}
+
+	fn file_open(access: u8) -> CerberusEvent {
+		CerberusEvent::File(FileEvent {
+			header: header(0, "tee"),
+			op: 0,
+			access,
+			flags: 0,
+			path: Arc::from("/etc/sudoers.d/backdoor"),
+		})
+	}
+
+	fn setattr(op: u8, mode: u32) -> CerberusEvent {
+		CerberusEvent::InodeAttr(InodeAttrEvent {
+			header: header(1000, "dropper"),
+			filename: Arc::from("payload"),
+			op,
+			valid: 0,
+			mode: Some(mode),
+			uid: None,
+			gid: None,
+			atime: None,
+			mtime: None,
+			path: Some(Arc::from("/tmp/payload")),
+		})
+	}
+
+	fn capable(comm: &str, cap: u32, init_userns: bool) -> CerberusEvent {
+		CerberusEvent::Capable(CapableEvent {
+			header: header(0, comm),
+			cap,
+			audit: true,
+			denied: false,
+			init_userns,
+		})
+	}
+
+	fn setns(old_mnt_ns: u32, mnt_ns: u32) -> CerberusEvent {
+		const HOST_MNT: u32 = 4026531841;
+		let mut header = header(0, "nsenter");
+		header.mnt_ns = mnt_ns;
+		CerberusEvent::Namespace(NamespaceEvent {
+			header,
+			op: 0,
+			flags: 0x0002_0000,
+			changed: 0x0002_0000,
+			old_mnt_ns,
+			fd: 3,
+			host_mnt_ns: HOST_MNT,
+		})
+	}
+
+	fn unix_connect(comm: &str, path: &str) -> CerberusEvent {
+		CerberusEvent::UnixSocket(UnixSocketEvent {
+			header: header(0, comm),
+			op: 1,
+			path: Arc::from(path),
+			abstract_ns: false,
+			denied: false,
+		})
+	}
+
+	fn accept(comm: &str) -> CerberusEvent {
+		CerberusEvent::InetAccept(InetAcceptEvent {
+			header: header(0, comm),
+			protocol: Arc::from("TCP"),
+			saddr: [10, 0, 0, 5].into(),
+			daddr: [203, 0, 113, 7].into(),
+			sport: 4444,
+			dport: 51234,
+		})
+	}
+
+	fn udp_send(comm: &str) -> CerberusEvent {
+		CerberusEvent::UdpSend(UdpSendEvent {
+			header: header(0, comm),
+			saddr: [10, 0, 0, 5].into(),
+			daddr: [203, 0, 113, 7].into(),
+			sport: 4444,
+			dport: 51234,
+		})
+	}
+
+	#[test]
+	fn shipped_rules_match_their_target_events_only() -> Result<()> {
+		// -- Setup & Fixtures
+		let engine = RuleEngine::new_from_ruleset(RuleSet::load_from_dir("../../../docs/example_rules/")?)?;
+		const HOST_MNT: u32 = 4026531841;
+		const CONTAINER_MNT: u32 = 4026532300;
+		// (rule id, event, whether the rule must match it)
+		let fx_cases = [
+			("etc-file-deleted", inode(1000, "/etc/shadow"), true),
+			("etc-file-deleted", inode(1000, "?/etc/shadow"), true),
+			("etc-file-deleted", inode(1000, "/tmp/shadow"), false),
+			("etc-file-deleted", inode(0, "/etc/shadow"), false),
+			("interpreter-inline-code", exec(&["python3", "-c", "import pty"]), true),
+			("interpreter-inline-code", exec(&["python3", "app.py"]), false),
+			("unexpected-root-transition", cred("exploit"), true),
+			("unexpected-root-transition", cred("sudo"), false),
+			("unexpected-root-transition", cred("passwd"), false),
+			("unexpected-root-transition", cred("mount"), false),
+			("unexpected-root-transition", cred("chsh"), false),
+			("io-uring-network", io_uring(16, Some([10, 0, 0, 1].into())), true),
+			("io-uring-network", io_uring(18, None), false),
+			("module-from-writable-dir", module(0, Some("/tmp/rootkit.ko")), true),
+			("module-from-writable-dir", module(0, None), false),
+			("module-from-writable-dir", module(1, None), false),
+			("anonymous-memory-made-executable", mprotect("loader", Some(3)), true),
+			("anonymous-memory-made-executable", mprotect("loader", None), false),
+			("anonymous-memory-made-executable", mprotect("java", Some(3)), false),
+			("privileged-config-modified", file_open(4 | 2), true),
+			("privileged-config-modified", file_open(1), false),
+			("setuid-bit-set", setattr(1, 0o4755), true),
+			("setuid-bit-set", setattr(1, 0o755), false),
+			("setuid-bit-set", setattr(0, 0o4755), false),
+			(
+				"module-capability-outside-package-manager",
+				capable("loader", 16, true),
+				true,
+			),
+			(
+				"module-capability-outside-package-manager",
+				capable("loader", 16, false),
+				false,
+			),
+			(
+				"module-capability-outside-package-manager",
+				capable("modprobe", 16, true),
+				false,
+			),
+			(
+				"module-capability-outside-package-manager",
+				capable("loader", 21, true),
+				false,
+			),
+			(
+				"container-joined-host-mount-namespace",
+				setns(CONTAINER_MNT, HOST_MNT),
+				true,
+			),
+			(
+				"container-joined-host-mount-namespace",
+				setns(HOST_MNT, CONTAINER_MNT),
+				false,
+			),
+			(
+				"container-runtime-socket-connect",
+				unix_connect("curl", "/var/run/docker.sock"),
+				true,
+			),
+			(
+				"container-runtime-socket-connect",
+				unix_connect("curl", "/run/systemd/journal/socket"),
+				false,
+			),
+			(
+				"container-runtime-socket-connect",
+				unix_connect("docker", "/var/run/docker.sock"),
+				false,
+			),
+			("shell-accepting-inbound-connection", accept("nc"), true),
+			("shell-accepting-inbound-connection", accept("nginx"), false),
+			("shell-accepting-inbound-connection", udp_send("nc"), false),
+		];
+
+		// -- Exec & Check
+		for (rule_id, event, expected) in fx_cases {
+			let matched = engine.process_event(&event).iter().any(|e| match e {
+				EngineEvent::Matched(m) => m.rule_id.as_ref() == rule_id,
+				_ => false,
+			});
+			assert_eq!(matched, expected, "{rule_id} on {event:?}");
+		}
+
+		Ok(())
+	}
 }
 
 // endregion: --- Tests
@@ -0,0 +1,44 @@
+use serde::Deserialize;
+
+/// When a rule is evaluated. Outside the window the rule never matches.
+#[cfg_attr(test, derive(PartialEq))]
+#[derive(Debug, Deserialize, Clone)]
+pub struct Active {
+	/// Days the window starts on. Empty means every day.
+	#[serde(default)]
+	pub days: Vec<Day>,
+	/// `"HH:MM-HH:MM"`, end exclusive. Wraps past midnight when end < start.
+	#[serde(default)]
+	pub hours: Option<String>,
+	#[serde(default)]
+	pub tz: ScheduleTz,
+}
+
+#[cfg_attr(test, derive(PartialEq))]
+#[derive(Debug, Deserialize, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+pub enum Day {
+	#[serde(alias = "monday")]
+	Mon,
+	#[serde(alias = "tuesday")]
+	Tue,
+	#[serde(alias = "wednesday")]
+	Wed,
+	#[serde(alias = "thursday")]
+	Thu,
+	#[serde(alias = "friday")]
+	Fri,
+	#[serde(alias = "saturday")]
+	Sat,
+	#[serde(alias = "sunday")]
+	Sun,
+}
+
+#[cfg_attr(test, derive(PartialEq))]
+#[derive(Debug, Deserialize, Clone, Copy, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum ScheduleTz {
+	#[default]
+	Local,
+	Utc,
+}
@@ -12,6 +12,9 @@ pub struct Sequence {
 	pub steps: Vec<Step>,
 	#[serde(default)]
 	pub scope: Option<Scope>,
+	/// Added to the entities of the event that completes the sequence.
+	#[serde(default)]
+	pub risk: u32,
 }
 
 #[cfg_attr(test, derive(PartialEq))]
@@ -0,0 +1,835 @@
+use lib_event_schema::{Field, FieldType};
+use serde_norway::{Mapping, Value};
+
+use crate::{
+	Error, Severity,
+	error::Result,
+	rule::{Condition, RuleFormat},
+};
+
+/// Sigma log sources that have a Cerberus event counterpart.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Logsource {
+	ProcessCreation,
+	NetworkConnection,
+	FileEvent,
+}
+
+impl Logsource {
+	fn from_category(category: &str) -> Option<Self> {
+		match category {
+			"process_creation" => Some(Logsource::ProcessCreation),
+			"network_connection" => Some(Logsource::NetworkConnection),
+			"file_event" => Some(Logsource::FileEvent),
+			_ => None,
+		}
+	}
+
+	fn field(self, sigma_field: &str) -> Option<Field> {
+		Some(match (self, sigma_field) {
+			// process_creation is the committed exec, which also carries the arguments
+			(Logsource::ProcessCreation, "Image") => Field::ProcessExecFilename,
+			(Logsource::ProcessCreation, "CommandLine") => Field::ProcessCmdline,
+			(Logsource::ProcessCreation, "ProcessId") => Field::ProcessPid,
+
+			(Logsource::NetworkConnection, "DestinationIp") => Field::NetworkDaddr,
+			(Logsource::NetworkConnection, "DestinationPort") => Field::NetworkDport,
+			(Logsource::NetworkConnection, "SourceIp") => Field::NetworkSaddr,
+			(Logsource::NetworkConnection, "SourcePort") => Field::NetworkSport,
+
+			(Logsource::FileEvent, "TargetFilename") => Field::InodePath,
+			_ => return None,
+		})
+	}
+
+	/// Conditions that narrow the mapped fields to the events the log source
+	/// describes. `inode.path` is also set on deletes, renames and setattr, and
+	/// `network.*` on accepts, UDP sends and every TCP state change, so without
+	/// these a converted rule fires on far more than the original.
+	fn scope(self) -> Vec<Condition> {
+		match self {
+			Logsource::ProcessCreation => vec![],
+			// an outbound TCP connect that completed; UDP flows are not covered
+			Logsource::NetworkConnection => vec![
+				condition(Field::SocketOldState, "==", "TCP_SYN_SENT".into()),
+				condition(Field::SocketNewState, "==", "TCP_ESTABLISHED".into()),
+			],
+			Logsource::FileEvent => vec![condition(Field::InodeAttrOp, "==", "create".into())],
+		}
+	}
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Modifier {
+	None,
+	Contains,
+	StartsWith,
+	EndsWith,
+	Re,
+	Cidr,
+}
+
+/// Result of translating one Sigma rule. `unsupported` lists every construct
+/// that was dropped, so a partial translation matches more than the original.
+#[derive(Debug, Clone)]
+pub struct SigmaConversion {
+	pub id: String,
+	pub title: String,
+	pub sigma_id: Option<String>,
+	pub severity: Severity,
+	pub conditions: Vec<Condition>,
+	pub unsupported: Vec<String>,
+}
+
+impl SigmaConversion {
+	pub fn is_complete(&self) -> bool {
+		self.unsupported.is_empty()
+	}
+
+	/// Renders the rule as a Cerberus TOML rule file. Dropped constructs are kept
+	/// as comments at the top of the file.
+	pub fn to_toml(&self) -> Result<String> {
+		if self.conditions.is_empty() {
+			return Err(Error::RuleWithoutConditions {
+				rule_id: self.id.clone(),
+			});
+		}
+
+		let conditions = self
+			.conditions
+			.iter()
+			.map(|c| {
+				let mut table = toml::Table::new();
+				table.insert("field".into(), c.field.clone().into());
+				table.insert("op".into(), c.op.clone().into());
+				table.insert("value".into(), c.value.clone());
+				toml::Value::Table(table)
+			})
+			.collect::<Vec<_>>();
+
+		let mut rule = toml::Table::new();
+		rule.insert("id".into(), self.id.clone().into());
+		rule.insert("description".into(), self.title.clone().into());
+		rule.insert("severity".into(), self.severity.as_str().into());
+		rule.insert("conditions".into(), conditions.into());
+
+		let mut root = toml::Table::new();
+		root.insert("rule".into(), rule.into());
+
+		let mut out = format!("# Converted from Sigma rule '{}'", self.title);
+		if let Some(sigma_id) = &self.sigma_id {
+			out.push_str(&format!(" ({sigma_id})"));
+		}
+		out.push('\n');
+		for item in &self.unsupported {
+			out.push_str(&format!("# not translated: {item}\n"));
+		}
+		out.push('\n');
+		out.push_str(&toml::to_string(&root).map_err(|e| Error::SigmaConversion { reason: e.to_string() })?);
+
+		Ok(out)
+	}
+
+	pub fn file_name(&self) -> String {
+		format!("{}.{}", self.id, RuleFormat::TOML_EXTENSION)
+	}
+}
+
+/// Translates a Sigma rule for one of the `process_creation`, `network_connection`
+/// or `file_event` log sources. Only conjunctions of selections translate, since
+/// Cerberus conditions are always and-ed.
+pub fn convert_sigma(src: &str) -> Result<SigmaConversion> {
+	let doc: Value = serde_norway::from_str(src)?;
+
+	let title = doc
+		.get("title")
+		.and_then(Value::as_str)
+		.ok_or_else(|| sigma_err("missing 'title'"))?
+		.to_string();
+	let sigma_id = doc.get("id").and_then(Value::as_str).map(str::to_string);
+
+	let category = doc
+		.get("logsource")
+		.and_then(|l| l.get("category"))
+		.and_then(Value::as_str)
+		.ok_or_else(|| sigma_err("missing 'logsource.category'"))?;
+	let logsource = Logsource::from_category(category)
+		.ok_or_else(|| sigma_err(&format!("unsupported logsource category '{category}'")))?;
+
+	let detection = doc
+		.get("detection")
+		.and_then(Value::as_mapping)
+		.ok_or_else(|| sigma_err("missing 'detection'"))?;
+
+	let mut converter = Converter {
+		logsource,
+		detection,
+		conditions: Vec::new(),
+		unsupported: Vec::new(),
+	};
+	converter.translate_condition()?;
+	// a rule with nothing translated stays empty rather than matching every event of the source
+	if !converter.conditions.is_empty() {
+		converter.conditions.extend(logsource.scope());
+	}
+
+	let severity = match doc.get("level").and_then(Value::as_str) {
+		Some("informational") => Severity::Info,
+		Some("low") => Severity::Low,
+		Some("high") => Severity::High,
+		Some("critical") => Severity::Critical,
+		_ => Severity::Medium,
+	};
+
+	Ok(SigmaConversion {
+		id: format!("sigma-{}", slug(&title)),
+		title,
+		sigma_id,
+		severity,
+		conditions: converter.conditions,
+		unsupported: converter.unsupported,
+	})
+}
+
+struct Converter<'a> {
+	logsource: Logsource,
+	detection: &'a Mapping,
+	conditions: Vec<Condition>,
+	unsupported: Vec<String>,
+}
+
+impl Converter<'_> {
+	fn translate_condition(&mut self) -> Result<()> {
+		let expr = match self.detection.get("condition") {
+			Some(Value::String(s)) => s.clone(),
+			Some(Value::Sequence(items)) if items.len() == 1 => items[0]
+				.as_str()
+				.ok_or_else(|| sigma_err("'condition' must be a string"))?
+				.to_string(),
+			Some(Value::Sequence(_)) => {
+				self.unsupported.push("multiple detection conditions (or-ed)".into());
+				return Ok(());
+			}
+			_ => return Err(sigma_err("missing 'detection.condition'")),
+		};
+
+		let tokens: Vec<&str> = expr.split_whitespace().collect();
+		if tokens.iter().any(|t| *t == "or" || *t == "|" || t.contains(['(', ')'])) {
+			self.unsupported
+				.push(format!("condition '{expr}' (only 'and' of selections is supported)"));
+			return Ok(());
+		}
+
+		for term in tokens.split(|t| *t == "and") {
+			match term {
+				[name] => self.translate_named(name),
+				// dropping the filter would match more than the original, so refuse outright
+				["not", ..] => {
+					return Err(Error::SigmaConversion {
+						reason: format!("negated selection '{}' cannot be translated", term.join(" ")),
+					});
+				}
+				["all", "of", pattern] => {
+					for name in self.matching_selections(pattern) {
+						self.translate_named(&name);
+					}
+				}
+				["1", "of", pattern] | ["any", "of", pattern] => {
+					let names = self.matching_selections(pattern);
+					match names.as_slice() {
+						[name] => self.translate_named(name),
+						_ => self.unsupported.push(format!(
+							"'1 of {pattern}' over {} selections (alternatives cannot be expressed)",
+							names.len()
+						)),
+					}
+				}
+				_ => self.unsupported.push(format!("condition term '{}'", term.join(" "))),
+			}
+		}
+
+		Ok(())
+	}
+
+	fn matching_selections(&self, pattern: &str) -> Vec<String> {
+		let prefix = pattern.strip_suffix('*');
+
+		self.detection
+			.keys()
+			.filter_map(Value::as_str)
+			.filter(|name| *name != "condition")
+			.filter(|name| match (pattern, prefix) {
+				("them", _) => true,
+				(_, Some(prefix)) => name.starts_with(prefix),
+				_ => *name == pattern,
+			})
+			.map(str::to_string)
+			.collect()
+	}
+
+	fn translate_named(&mut self, name: &str) {
+		match self.detection.get(name) {
+			Some(Value::Mapping(map)) => self.translate_map(name, map),
+			Some(Value::Sequence(items)) => match items.as_slice() {
+				[Value::Mapping(map)] => self.translate_map(name, map),
+				[Value::Mapping(_), ..] => {
+					self.unsupported.push(format!("selection '{name}' is a list of or-ed maps"));
+				}
+				_ => self.unsupported.push(format!("keyword selection '{name}'")),
+			},
+			Some(_) => self.unsupported.push(format!("keyword selection '{name}'")),
+			None => self.unsupported.push(format!("unknown selection '{name}'")),
+		}
+	}
+
+	fn translate_map(&mut self, selection: &str, map: &Mapping) {
+		for (key, value) in map {
+			let Some(key) = key.as_str() else {
+				self.unsupported.push(format!("non-string key in selection '{selection}'"));
+				continue;
+			};
+
+			if let Err(reason) = self.translate_field(key, value) {
+				self.unsupported.push(format!("'{key}' in selection '{selection}': {reason}"));
+			}
+		}
+	}
+
+	fn translate_field(&mut self, key: &str, value: &Value) -> core::result::Result<(), String> {
+		let mut parts = key.split('|');
+		let sigma_field = parts.next().unwrap_or_default();
+
+		let mut modifier = Modifier::None;
+		let mut all = false;
+		for m in parts {
+			modifier = match (m, modifier) {
+				("all", _) => {
+					all = true;
+					continue;
+				}
+				("contains", Modifier::None) => Modifier::Contains,
+				("startswith", Modifier::None) => Modifier::StartsWith,
+				("endswith", Modifier::None) => Modifier::EndsWith,
+				("re", Modifier::None) => Modifier::Re,
+				("cidr", Modifier::None) => Modifier::Cidr,
+				_ => return Err(format!("unsupported modifier '{m}'")),
+			};
+		}
+
+		let field = self
+			.logsource
+			.field(sigma_field)
+			.ok_or_else(|| "no matching Cerberus field".to_string())?;
+
+		let values = match value {
+			Value::Sequence(items) => items.iter().map(scalar).collect::<Option<Vec<_>>>(),
+			other => scalar(other).map(|v| vec![v]),
+		}
+		.filter(|v| !v.is_empty())
+		.ok_or_else(|| "values must be non-empty strings or numbers".to_string())?;
+
+		let conditions = match field.ty() {
+			FieldType::String => string_conditions(field, modifier, all, &values),
+			FieldType::Int if modifier == Modifier::None && !all => exact_conditions(field, &values),
+			// ip fields take both addresses and blocks, so `|cidr` needs no translation
+			FieldType::Ip if matches!(modifier, Modifier::None | Modifier::Cidr) && !all => {
+				exact_conditions(field, &values)
+			}
+			_ => return Err(format!("modifiers are not supported on '{}'", field.as_str())),
+		}?;
+
+		self.conditions.extend(conditions);
+		Ok(())
+	}
+}
+
+fn string_conditions(
+	field: Field,
+	modifier: Modifier,
+	all: bool,
+	values: &[String],
+) -> core::result::Result<Vec<Condition>, String> {
+	// `|all` ands the values, so each becomes its own condition
+	if all && values.len() > 1 {
+		return values
+			.iter()
+			.map(|v| string_conditions(field, modifier, false, std::slice::from_ref(v)))
+			.collect::<core::result::Result<Vec<_>, _>>()
+			.map(|v| v.into_iter().flatten().collect());
+	}
+
+	// Sigma compares strings case-insensitively and Cerberus operators do not,
+	// so everything but `|re` (case-sensitive in Sigma too) becomes a `(?i)` regex
+	let patterns = values.iter().map(|v| wildcard_regex(v)).collect::<Vec<_>>().join("|");
+
+	let value = match modifier {
+		Modifier::None => format!("(?i)^(?:{patterns})$"),
+		Modifier::Contains => format!("(?i)(?:{patterns})"),
+		Modifier::StartsWith => format!("(?i)^(?:{patterns})"),
+		Modifier::EndsWith => format!("(?i)(?:{patterns})$"),
+		Modifier::Re => match values {
+			[v] => v.clone(),
+			_ => values.iter().map(|v| format!("(?:{v})")).collect::<Vec<_>>().join("|"),
+		},
+		Modifier::Cidr => return Err(format!("'cidr' is not supported on '{}'", field.as_str())),
+	};
+
+	Ok(vec![condition(field, "regex", value.into())])
+}
+
+fn exact_conditions(field: Field, values: &[String]) -> core::result::Result<Vec<Condition>, String> {
+	let parsed = values
+		.iter()
+		.map(|v| match field.ty() {
+			FieldType::Int => v.parse::<i64>().map(toml::Value::from).map_err(|_| v),
+			_ => v
+				.parse::<lib_event_schema::IpNet>()
+				.map(|_| toml::Value::from(v.as_str()))
+				.map_err(|_| v),
+		})
+		.collect::<core::result::Result<Vec<_>, _>>()
+		.map_err(|v| format!("'{v}' is not a valid {} value", field.ty().as_str()))?;
+
+	let (op, value) = match parsed.as_slice() {
+		[v] => ("==", v.clone()),
+		_ => ("in", toml::Value::Array(parsed)),
+	};
+
+	Ok(vec![condition(field, op, value)])
+}
+
+fn condition(field: Field, op: &str, value: toml::Value) -> Condition {
+	Condition {
+		field: field.as_str().to_string(),
+		op: op.to_string(),
+		value,
+	}
+}
+
+fn scalar(value: &Value) -> Option<String> {
+	match value {
+		Value::String(s) if !s.is_empty() => Some(s.clone()),
+		Value::Number(n) => Some(n.to_string()),
+		_ => None,
+	}
+}
+
+/// Sigma values are literals where `*` and `?` are wildcards and `\` escapes them.
+fn wildcard_regex(value: &str) -> String {
+	let mut out = String::with_capacity(value.len());
+	let mut chars = value.chars().peekable();
+
+	while let Some(c) = chars.next() {
+		match c {
+			'\\' if matches!(chars.peek(), Some('*' | '?' | '\\')) => {
+				let next = chars.next().unwrap_or(c);
+				out.push_str(&regex::escape(&next.to_string()));
+			}
+			'*' => out.push_str(".*"),
+			'?' => out.push('.'),
+			_ => out.push_str(&regex::escape(&c.to_string())),
+		}
+	}
+
+	out
+}
+
+fn slug(title: &str) -> String {
+	let mut out = String::with_capacity(title.len());
+	for c in title.chars() {
+		if c.is_ascii_alphanumeric() {
+			out.push(c.to_ascii_lowercase());
+		} else if !out.ends_with('-') {
+			out.push('-');
+		}
+	}
+	out.trim_matches('-').to_string()
+}
+
+fn sigma_err(reason: &str) -> Error {
+	Error::SigmaConversion { reason: reason.into() }
+}
+
+// region:    --- Tests
+
+#[cfg(test)]
+mod tests {
+	type Result<T> = core::result::Result<T, Box<dyn std::error::Error>>; // For tests.
+
+	use std::sync::Arc;
+
+	use lib_common::event::*;
+
+	use super::*;
+	use crate::{EngineEvent, Rule, RuleEngine, RuleSet};
+
+	fn engine_for(sigma: &str) -> Result<RuleEngine> {
+		let rule = Rule::from_str(&convert_sigma(sigma)?.to_toml()?)?;
+		Ok(RuleEngine::new_from_ruleset(RuleSet::new(vec![rule])?)?)
+	}
+
+	fn matches(engine: &RuleEngine, event: &CerberusEvent) -> bool {
+		engine.process_event(event).iter().any(|e| matches!(e, EngineEvent::Matched(_)))
+	}
+
+	fn header() -> EventHeader {
+		EventHeader {
+			cgroup_id: 0,
+			container: None,
+			ts: 0,
+			mnt_ns: 0,
+			pid: 42,
+			ppid: 1,
+			tgid: 42,
+			uid: 1000,
+			parent_comm: Arc::from("bash"),
+			comm: Arc::from("implant"),
+		}
+	}
+
+	fn inet_sock(old_state: &str, new_state: &str) -> CerberusEvent {
+		CerberusEvent::InetSock(InetSockEvent {
+			header: header(),
+			old_state: Arc::from(old_state),
+			new_state: Arc::from(new_state),
+			protocol: Arc::from("TCP"),
+			saddr: [10, 0, 0, 5].into(),
+			daddr: [203, 0, 113, 7].into(),
+			sport: 51234,
+			dport: 4444,
+		})
+	}
+
+	fn inode_attr(op: u8, path: &str) -> CerberusEvent {
+		CerberusEvent::InodeAttr(InodeAttrEvent {
+			header: header(),
+			filename: Arc::from("authorized_keys"),
+			op,
+			valid: 0,
+			mode: Some(0o600),
+			uid: None,
+			gid: None,
+			atime: None,
+			mtime: None,
+			path: Some(Arc::from(path)),
+		})
+	}
+
+	#[test]
+	fn convert_process_creation_modifiers_ok() -> Result<()> {
+		// -- Setup & Fixtures
+		let fx_sigma = r#"
+title: Shell Spawned From Temp Directory
+id: 4b3c1d2e-0000-4000-8000-000000000001
+level: high
+logsource:
+  product: linux
+  category: process_creation
+detection:
+  selection_path:
+    Image|startswith: '/tmp/'
+  selection_name:
+    Image|endswith:
+      - '/sh'
+      - '/bash'
+  selection_contains:
+    Image|contains|all:
+      - 'x'
+      - 'y'
+  selection_re:
+    Image|re: '^/tmp/[a-z]+$'
+  condition: all of selection_*
+"#;
+
+		// -- Exec
+		let conv = convert_sigma(fx_sigma)?;
+
+		// -- Check
+		assert!(conv.is_complete(), "{:?}", conv.unsupported);
+		assert_eq!(conv.id, "sigma-shell-spawned-from-temp-directory");
+		assert_eq!(conv.severity, Severity::High);
+		let ops: Vec<(&str, &str)> = conv.conditions.iter().map(|c| (c.field.as_str(), c.op.as_str())).collect();
+		assert_eq!(
+			ops,
+			vec![
+				("process.exec.filename", "regex"),
+				("process.exec.filename", "regex"),
+				("process.exec.filename", "regex"),
+				("process.exec.filename", "regex"),
+				("process.exec.filename", "regex"),
+			]
+		);
+		assert_eq!(conv.conditions[0].value, toml::Value::from("(?i)^(?:/tmp/)"));
+		assert_eq!(conv.conditions[1].value, toml::Value::from("(?i)(?:/sh|/bash)$"));
+		assert_eq!(conv.conditions[4].value, toml::Value::from("^/tmp/[a-z]+$"));
+
+		let rule = Rule::from_str(&conv.to_toml()?)?;
+		assert_eq!(rule.inner.conditions.len(), 5);
+
+		Ok(())
+	}
+
+	#[test]
+	fn convert_network_connection_ok() -> Result<()> {
+		// -- Setup & Fixtures
+		let fx_sigma = r#"
+title: Egress To Mining Pool Port
+logsource:
+  category: network_connection
+detection:
+  selection:
+    DestinationPort:
+      - 3333
+      - 4444
+    DestinationIp|cidr: '10.0.0.0/8'
+  condition: selection
+"#;
+
+		// -- Exec
+		let conv = convert_sigma(fx_sigma)?;
+
+		// -- Check
+		assert!(conv.is_complete(), "{:?}", conv.unsupported);
+		assert_eq!(conv.severity, Severity::Medium);
+		assert_eq!(conv.conditions[0].op, "in");
+		assert_eq!(
+			conv.conditions[0].value,
+			toml::Value::Array(vec![3333.into(), 4444.into()])
+		);
+		assert_eq!(conv.conditions[1].field, "network.daddr");
+		assert_eq!(conv.conditions[1].op, "==");
+		assert_eq!(conv.conditions[1].value, toml::Value::from("10.0.0.0/8"));
+		assert_eq!(conv.conditions.len(), 4);
+		assert_eq!(conv.conditions[2].field, "socket.old_state");
+		assert_eq!(conv.conditions[3].field, "socket.new_state");
+
+		Ok(())
+	}
+
+	#[test]
+	fn convert_network_connection_matches_outbound_connects_only() -> Result<()> {
+		// -- Setup & Fixtures
+		let fx_sigma = r#"
+title: Egress To 4444
+logsource:
+  category: network_connection
+detection:
+  selection:
+    DestinationPort: 4444
+  condition: selection
+"#;
+		let engine = engine_for(fx_sigma)?;
+		let fx_cases = [
+			(inet_sock("TCP_SYN_SENT", "TCP_ESTABLISHED"), true),
+			(inet_sock("TCP_ESTABLISHED", "TCP_FIN_WAIT1"), false),
+			(inet_sock("TCP_CLOSE", "TCP_SYN_SENT"), false),
+			(
+				CerberusEvent::InetAccept(InetAcceptEvent {
+					header: header(),
+					protocol: Arc::from("TCP"),
+					saddr: [10, 0, 0, 5].into(),
+					daddr: [203, 0, 113, 7].into(),
+					sport: 51234,
+					dport: 4444,
+				}),
+				false,
+			),
+			(
+				CerberusEvent::UdpSend(UdpSendEvent {
+					header: header(),
+					saddr: [10, 0, 0, 5].into(),
+					daddr: [203, 0, 113, 7].into(),
+					sport: 51234,
+					dport: 4444,
+				}),
+				false,
+			),
+		];
+
+		// -- Exec & Check
+		for (event, expected) in fx_cases {
+			assert_eq!(matches(&engine, &event), expected, "{event:?}");
+		}
+
+		Ok(())
+	}
+
+	#[test]
+	fn convert_file_event_matches_creates_only() -> Result<()> {
+		// -- Setup & Fixtures
+		let fx_sigma = r#"
+title: Authorized Keys Written
+logsource:
+  category: file_event
+detection:
+  selection:
+    TargetFilename|endswith: '/authorized_keys'
+  condition: selection
+"#;
+		let engine = engine_for(fx_sigma)?;
+		let fx_path = "/root/.ssh/authorized_keys";
+		let fx_cases = [
+			(inode_attr(0, fx_path), true),
+			(inode_attr(1, fx_path), false),
+			(inode_attr(0, "/root/.ssh/known_hosts"), false),
+			(
+				CerberusEvent::Inode(InodeEvent {
+					header: header(),
+					filename: Arc::from("authorized_keys"),
+					filename_len: 15,
+					op: 0,
+					path: Some(Arc::from(fx_path)),
+				}),
+				false,
+			),
+			(
+				CerberusEvent::InodeMutation(InodeMutationEvent {
+					header: header(),
+					new_filename: Arc::from("authorized_keys"),
+					old_filename: Arc::from("keys.tmp"),
+					new_filename_len: 15,
+					old_filename_len: 8,
+					mutation: 0,
+					old_path: Some(Arc::from(fx_path)),
+					new_path: Some(Arc::from(fx_path)),
+				}),
+				false,
+			),
+		];
+
+		// -- Exec & Check
+		for (event, expected) in fx_cases {
+			assert_eq!(matches(&engine, &event), expected, "{event:?}");
+		}
+
+		Ok(())
+	}
+
+	#[test]
+	fn convert_string_values_case_insensitive_ok() -> Result<()> {
+		// -- Setup & Fixtures
+		let fx_sigma = r#"
+title: Curl Upload
+logsource:
+  category: process_creation
+detection:
+  selection:
+    Image: '/usr/bin/curl'
+    CommandLine|contains:
+      - '--upload-file'
+      - '-T'
+  condition: selection
+"#;
+
+		// -- Exec
+		let conv = convert_sigma(fx_sigma)?;
+
+		// -- Check
+		assert!(conv.is_complete(), "{:?}", conv.unsupported);
+		let image = conv.conditions[0].value.as_str().ok_or("image value")?;
+		let cmdline = conv.conditions[1].value.as_str().ok_or("cmdline value")?;
+		assert_eq!(image, "(?i)^(?:/usr/bin/curl)$");
+		assert!(regex::Regex::new(image)?.is_match("/USR/BIN/Curl"));
+		assert!(!regex::Regex::new(image)?.is_match("/usr/bin/curl2"));
+		assert!(regex::Regex::new(cmdline)?.is_match("curl --UPLOAD-FILE x"));
+
+		Ok(())
+	}
+
+	#[test]
+	fn convert_reports_unsupported_constructs() -> Result<()> {
+		// -- Setup & Fixtures
+		let fx_sigma = r#"
+title: Suspicious Curl
+logsource:
+  category: process_creation
+detection:
+  selection:
+    Image|endswith: '/curl'
+    CommandLine|contains: '--upload-file'
+    ParentImage|base64: 'abc'
+  condition: selection
+"#;
+
+		// -- Exec
+		let conv = convert_sigma(fx_sigma)?;
+
+		// -- Check
+		assert!(!conv.is_complete());
+		assert_eq!(conv.conditions.len(), 2);
+		assert_eq!(conv.conditions[1].field, "process.cmdline");
+		assert_eq!(conv.unsupported.len(), 1, "{:?}", conv.unsupported);
+		assert!(conv.unsupported[0].contains("base64"));
+		assert!(conv.to_toml()?.contains("# not translated:"));
+
+		Ok(())
+	}
+
+	#[test]
+	fn convert_negated_selection_err() -> Result<()> {
+		// -- Setup & Fixtures
+		let fx_sigma = r#"
+title: Curl Outside Usr
+logsource:
+  category: process_creation
+detection:
+  selection:
+    Image|endswith: '/curl'
+  filter:
+    Image|startswith: '/usr/'
+  condition: selection and not filter
+"#;
+
+		// -- Exec
+		let res = convert_sigma(fx_sigma);
+
+		// -- Check
+		assert!(
+			matches!(&res, Err(Error::SigmaConversion { reason }) if reason.contains("not filter")),
+			"{res:?}"
+		);
+
+		Ok(())
+	}
+
+	#[test]
+	fn convert_or_condition_translates_nothing() -> Result<()> {
+		// -- Setup & Fixtures
+		let fx_sigma = r#"
+title: Either
+logsource:
+  category: file_event
+detection:
+  a:
+    TargetFilename: 'authorized_keys'
+  b:
+    TargetFilename|endswith: '.so'
+  condition: a or b
+"#;
+
+		// -- Exec
+		let conv = convert_sigma(fx_sigma)?;
+
+		// -- Check
+		assert!(conv.conditions.is_empty());
+		assert!(conv.to_toml().is_err());
+
+		Ok(())
+	}
+
+	#[test]
+	fn convert_unknown_logsource_err() -> Result<()> {
+		// -- Setup & Fixtures
+		let fx_sigma = "title: x\nlogsource:\n  category: registry_set\ndetection:\n  condition: sel\n";
+
+		// -- Exec
+		let res = convert_sigma(fx_sigma);
+
+		// -- Check
+		assert!(matches!(res, Err(Error::SigmaConversion { .. })));
+
+		Ok(())
+	}
+}
+
+// endregion: --- Tests